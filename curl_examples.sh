@@ -186,10 +186,11 @@ curl -X POST "$SERVER_URL" \
     "params": {
       "name": "swap_tokens",
       "arguments": {
-        "from_token": "0xA0b86a33E6441b8C4C8C0C4C8C0C4C8C0C4C8C0C",
+        "from_token": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
         "to_token": "0xdAC17F958D2ee523a2206206994597C13D831ec7",
         "amount": "100.0",
-        "slippage_tolerance": "0.5"
+        "slippage_tolerance": "0.5",
+        "skip_balance_check": true
       }
     }
   }' \
@@ -211,9 +212,10 @@ curl -X POST "$SERVER_URL" \
       "name": "swap_tokens",
       "arguments": {
         "from_token": "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
-        "to_token": "0xA0b86a33E6441b8C4C8C0C4C8C0C4C8C0C4C8C0C",
+        "to_token": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
         "amount": "1.0",
-        "slippage_tolerance": "1.0"
+        "slippage_tolerance": "1.0",
+        "skip_balance_check": true
       }
     }
   }' \
@@ -237,7 +239,8 @@ curl -X POST "$SERVER_URL" \
         "from_token": "0x6B175474E89094C44Da98b954EedeAC495271d0F",
         "to_token": "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
         "amount": "1000.0",
-        "slippage_tolerance": "0.3"
+        "slippage_tolerance": "0.3",
+        "skip_balance_check": true
       }
     }
   }' \
@@ -0,0 +1,67 @@
+use crate::error::{InputValidator, MCPError};
+use ethers::types::Address;
+use serde::{Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Sentinel used by aggregators to denote the native currency
+const NATIVE_ETH_SENTINEL: &str = "0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee";
+
+/// Whether a token argument denotes native ETH rather than an ERC20 contract:
+/// the literal `ETH`, the zero address, or `0xEeee...EEeE`
+pub fn is_native_eth(token: &str) -> bool {
+    token.eq_ignore_ascii_case("ETH")
+        || token.eq_ignore_ascii_case(NATIVE_ETH_SENTINEL)
+        || token.parse::<Address>().is_ok_and(|address| address.is_zero())
+}
+
+/// An Ethereum address validated once at the tool boundary.
+/// Displays and serializes in EIP-55 checksummed form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParsedAddress(Address);
+
+impl ParsedAddress {
+    /// Validate format and, for mixed-case input, the EIP-55 checksum
+    pub fn parse(address: &str) -> Result<Self, MCPError> {
+        InputValidator::validate_address(address)?;
+        address.parse::<Address>()
+            .map(Self)
+            .map_err(|_| MCPError::InvalidAddress(address.to_string()))
+    }
+
+    /// Underlying address
+    pub fn address(&self) -> Address {
+        self.0
+    }
+
+    /// EIP-55 checksummed string
+    pub fn checksummed(&self) -> String {
+        ethers::utils::to_checksum(&self.0, None)
+    }
+}
+
+impl From<Address> for ParsedAddress {
+    fn from(address: Address) -> Self {
+        Self(address)
+    }
+}
+
+impl FromStr for ParsedAddress {
+    type Err = MCPError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for ParsedAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.checksummed())
+    }
+}
+
+impl Serialize for ParsedAddress {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.checksummed())
+    }
+}
@@ -1,20 +1,30 @@
 use anyhow::Result;
-use std::env;
 use tracing::info;
-use web3_wallet::mcp_server::MCPServer;
+use web3_wallet::mcp_server::{MCPServer, MAX_BATCH_SIZE};
 use web3_wallet::logging::init_logging;
-use web3_wallet::types::MCPRequest;
-use serde_json::json;
+use web3_wallet::error::{ErrorHandler, MCPError};
+use web3_wallet::config::Config;
+use web3_wallet::ethereum::private_key_from_env;
+use web3_wallet::rate_limit::ClientRateLimiter;
+use web3_wallet::request_limit::with_body_limit;
+use web3_wallet::types::{KeySource, MCPRequest};
+use serde::Deserialize;
+use serde_json::{json, Value};
 use axum::{
-    extract::State,
-    http::StatusCode,
-    response::Json,
+    extract::{ConnectInfo, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Json, Response},
     routing::{get, post},
     Router,
 };
-use tower_http::cors::{CorsLayer, Any};
+use tower_http::compression::CompressionLayer;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+use std::net::SocketAddr;
 use std::sync::Arc;
 
+/// Header identifying a client for rate limiting, honoured only with `TRUST_CLIENT_ID_HEADER`
+const CLIENT_ID_HEADER: &str = "x-client-id";
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize structured logging
@@ -22,42 +32,114 @@ async fn main() -> Result<()> {
 
     info!("🚀 Starting Web3 Wallet MCP HTTP Server");
 
-    // Get configuration from environment
-    let rpc_url = "https://eth-mainnet.g.alchemy.com/v2/JZUYcRpkXq25weYd16Fuu".to_string();
+    // Get configuration from CONFIG_FILE and the environment
+    let config = Config::load()?;
     
-    let private_key = env::var("PRIVATE_KEY")
-        .unwrap_or_else(|_| "0x0000000000000000000000000000000000000000000000000000000000000001".to_string());
+    let (private_key, key_source) = private_key_from_env()?;
 
     // Create MCP server
-    let mcp_server = Arc::new(MCPServer::new(rpc_url, private_key).await?);
+    let mcp_server = Arc::new(MCPServer::from_config(&config, private_key).await?);
     
     info!("✅ MCP Server initialized successfully");
 
-    // Create HTTP router
+    let max_body_bytes = config.max_request_bytes;
+    info!(max_body_bytes = max_body_bytes, "Request body limit configured");
+
+    let allowed_origins = config.allowed_origins.clone();
+    let cors = cors_layer(&allowed_origins)?;
+    info!(allowed_origins = ?allowed_origins, "CORS configured");
+
+    let pretty_default = config.pretty_json;
+
+    let rate_limiter = ClientRateLimiter::from_config(&config).map(Arc::new);
+    let rate_limit_per_minute = rate_limiter.as_ref().map(|limiter| limiter.requests_per_minute());
+
+    // Create HTTP router. Only the bulky JSON endpoints are compressed (per the client's
+    // Accept-Encoding); /health stays plain for probes that do not decode
+    let compressed = Router::new()
+        .route("/mcp", post(move |state: State<Arc<MCPServer>>, connect_info: ConnectInfo<SocketAddr>, headers: HeaderMap, query: Query<ResponseFormat>, payload: Json<Value>| {
+            handle_mcp_request(state, connect_info, headers, query, payload, pretty_default, rate_limiter.clone())
+        }))
+        .route("/tools", get(handle_tools))
+        .layer(CompressionLayer::new());
     let app = Router::new()
-        .route("/mcp", post(handle_mcp_request))
-        .route("/health", get(handle_health))
-        .layer(CorsLayer::new().allow_origin(Any).allow_methods(Any).allow_headers(Any))
+        .merge(compressed)
+        .route("/health", get(move |state: State<Arc<MCPServer>>| {
+            handle_health(state, allowed_origins.clone(), rate_limit_per_minute, key_source)
+        }));
+    let app = with_body_limit(app, max_body_bytes)
+        .layer(cors)
         .with_state(mcp_server);
 
     // Start HTTP server
     let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await?;
     info!("🌐 HTTP server listening on http://0.0.0.0:3000");
     info!("📡 MCP endpoint: http://localhost:3000/mcp");
+    info!("🧰 Tool list: http://localhost:3000/tools");
     info!("❤️  Health check: http://localhost:3000/health");
     info!("🔧 Ready to accept requests!");
     
-    axum::serve(listener, app).await?;
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await?;
 
     Ok(())
 }
 
+/// Query parameters controlling how `/mcp` responses are serialized
+#[derive(Debug, Deserialize)]
+struct ResponseFormat {
+    pretty: Option<bool>,
+}
+
 async fn handle_mcp_request(
     State(mcp_server): State<Arc<MCPServer>>,
-    Json(request): Json<MCPRequest>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
-    match mcp_server.handle_request(request).await {
-        Ok(response) => Ok(Json(serde_json::to_value(response).unwrap())),
+    ConnectInfo(remote_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Query(format): Query<ResponseFormat>,
+    Json(payload): Json<Value>,
+    pretty_default: bool,
+    rate_limiter: Option<Arc<ClientRateLimiter>>,
+) -> Result<Response, StatusCode> {
+    let pretty = format.pretty.unwrap_or(pretty_default);
+
+    // Every batch element counts against the client's budget; oversized batches are
+    // charged the cap and then rejected by the server
+    if let Some(limiter) = rate_limiter {
+        let requests = match &payload {
+            Value::Array(batch) => batch.len().min(MAX_BATCH_SIZE) as u32,
+            _ => 1,
+        };
+        let client_id = headers.get(CLIENT_ID_HEADER).and_then(|value| value.to_str().ok());
+        let client = limiter.client_key(client_id, remote_addr.ip());
+        if let Err(error) = limiter.check_n(&client, requests) {
+            let body = serde_json::to_value(ErrorHandler::handle_error(error, None)).unwrap();
+            return Ok((StatusCode::TOO_MANY_REQUESTS, Json(body)).into_response());
+        }
+    }
+
+    // A JSON array is a batch; anything else is a single request
+    if let Value::Array(batch) = payload {
+        return match mcp_server.handle_batch(batch).await {
+            Ok(responses) if responses.is_empty() => Ok(StatusCode::NO_CONTENT.into_response()),
+            Ok(responses) => Ok(json_response(serde_json::to_value(responses).unwrap(), pretty)),
+            Err(e) => {
+                tracing::warn!("MCP batch rejected: {}", e);
+                Ok(json_response(serde_json::to_value(ErrorHandler::handle_error(e, None)).unwrap(), pretty))
+            }
+        };
+    }
+
+    let request = match serde_json::from_value::<MCPRequest>(payload) {
+        Ok(request) => request,
+        Err(e) => {
+            let error = MCPError::InvalidJsonRpcRequest(e.to_string());
+            return Ok(json_response(serde_json::to_value(ErrorHandler::handle_error(error, None)).unwrap(), pretty));
+        }
+    };
+
+    match mcp_server.handle_message(request).await {
+        Ok(Some(response)) => Ok(json_response(serde_json::to_value(response).unwrap(), pretty)),
+        // Notifications never receive a response
+        Ok(None) => Ok(StatusCode::NO_CONTENT.into_response()),
         Err(e) => {
             tracing::error!("MCP request failed: {}", e);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
@@ -65,13 +147,52 @@ async fn handle_mcp_request(
     }
 }
 
-async fn handle_health() -> Result<Json<serde_json::Value>, StatusCode> {
+/// Serialize a JSON body, pretty-printed for humans debugging with curl when requested
+fn json_response(body: Value, pretty: bool) -> Response {
+    if !pretty {
+        return Json(body).into_response();
+    }
+    let text = serde_json::to_string_pretty(&body).unwrap_or_else(|_| body.to_string());
+    ([(header::CONTENT_TYPE, "application/json")], text).into_response()
+}
+
+/// Plain REST view of `tools/list` for clients without a JSON-RPC envelope
+async fn handle_tools(State(mcp_server): State<Arc<MCPServer>>) -> Json<Value> {
+    Json(json!({ "tools": mcp_server.tool_definitions() }))
+}
+
+/// Build the CORS layer, allowing any origin only when configured as `*`
+fn cors_layer(allowed_origins: &[String]) -> Result<CorsLayer> {
+    let cors = CorsLayer::new().allow_methods(Any).allow_headers(Any);
+
+    if allowed_origins.iter().any(|origin| origin == "*") {
+        return Ok(cors.allow_origin(Any));
+    }
+
+    let origins = allowed_origins.iter()
+        .map(|origin| origin.parse::<HeaderValue>()
+            .map_err(|e| anyhow::anyhow!("Invalid origin in ALLOWED_ORIGINS '{}': {}", origin, e)))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(cors.allow_origin(AllowOrigin::list(origins)))
+}
+
+async fn handle_health(
+    State(mcp_server): State<Arc<MCPServer>>,
+    allowed_origins: Vec<String>,
+    rate_limit_per_minute: Option<u32>,
+    key_source: KeySource,
+) -> Result<Json<serde_json::Value>, StatusCode> {
     Ok(Json(json!({
         "status": "healthy",
         "service": "Web3 Wallet MCP Server",
         "version": "1.0.0",
+        "wallet_mode": mcp_server.wallet_mode(),
+        "key_source": key_source,
+        "allowed_origins": allowed_origins,
+        "rate_limit_per_minute": rate_limit_per_minute,
         "endpoints": {
             "mcp": "/mcp",
+            "tools": "/tools",
             "health": "/health"
         }
     })))
@@ -0,0 +1,79 @@
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Circuit breaker state
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CircuitState {
+    /// Requests flow normally
+    Closed,
+    /// Requests are rejected until the cooldown expires
+    Open { until: Instant },
+    /// A trial request is allowed; success closes the circuit, failure reopens it
+    HalfOpen,
+}
+
+/// Lightweight circuit breaker that trips after consecutive failures
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    name: &'static str,
+    state: CircuitState,
+    consecutive_failures: u32,
+    failure_threshold: u32,
+    cooldown: Duration,
+}
+
+impl CircuitBreaker {
+    pub fn new(name: &'static str, failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            name,
+            state: CircuitState::Closed,
+            consecutive_failures: 0,
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+        }
+    }
+
+    /// Current state
+    pub fn state(&self) -> CircuitState {
+        self.state
+    }
+
+    /// Check whether a request may proceed, moving Open to HalfOpen once the cooldown has passed
+    pub fn allow_request(&mut self) -> bool {
+        match self.state {
+            CircuitState::Closed | CircuitState::HalfOpen => true,
+            CircuitState::Open { until } if Instant::now() >= until => {
+                info!(circuit = self.name, "Circuit half-open, allowing trial request");
+                self.state = CircuitState::HalfOpen;
+                true
+            },
+            CircuitState::Open { .. } => false,
+        }
+    }
+
+    /// Record a successful request, closing the circuit
+    pub fn record_success(&mut self) {
+        if self.state != CircuitState::Closed {
+            info!(circuit = self.name, "Circuit closed");
+        }
+        self.state = CircuitState::Closed;
+        self.consecutive_failures = 0;
+    }
+
+    /// Record a failed request, opening the circuit when the threshold is reached
+    pub fn record_failure(&mut self) {
+        self.consecutive_failures += 1;
+
+        let should_open = self.state == CircuitState::HalfOpen
+            || self.consecutive_failures >= self.failure_threshold;
+        if should_open {
+            warn!(
+                circuit = self.name,
+                consecutive_failures = self.consecutive_failures,
+                cooldown_secs = self.cooldown.as_secs(),
+                "Circuit opened"
+            );
+            self.state = CircuitState::Open { until: Instant::now() + self.cooldown };
+        }
+    }
+}
@@ -0,0 +1,401 @@
+use crate::address::ParsedAddress;
+use crate::error::{InputValidator, MCPError, ABSOLUTE_MAX_SLIPPAGE_PERCENT};
+use crate::ethereum::{
+    parse_rpc_urls, DEFAULT_GAS_LIMIT_MULTIPLIER, DEFAULT_GAS_WARN_GWEI, DEFAULT_LOGS_CHUNK_SIZE, DEFAULT_LOGS_MAX_RANGE,
+    DEFAULT_PRICE_CIRCUIT_COOLDOWN_SECS, DEFAULT_PRICE_CIRCUIT_FAILURE_THRESHOLD,
+};
+use crate::idempotency::DEFAULT_IDEMPOTENCY_TTL_SECS;
+use crate::network::Network;
+use crate::request_limit::DEFAULT_MAX_REQUEST_BYTES;
+use crate::tools::{parse_enabled_tools, DEFAULT_SLIPPAGE, DEFAULT_TOOL_TIMEOUT_SECS};
+use crate::types::SwapSource;
+use crate::ws::{DEFAULT_WS_MAX_RECONNECTS, DEFAULT_WS_RECONNECT_DELAY_MS};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::Path;
+use tracing::info;
+
+/// Stand-in endpoint when offline mode runs without `RPC_URL`; it is never contacted
+const OFFLINE_RPC_URL: &str = "http://localhost:8545";
+
+/// Default timeout in seconds for outbound HTTP requests
+pub const DEFAULT_HTTP_TIMEOUT_SECS: u64 = 30;
+
+/// Server configuration. Each setting comes from the `CONFIG_FILE` (TOML, or JSON for
+/// a `.json` path) when present there, then from its environment variable, then a default.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    /// `rpc_url` / `RPC_URL`: required unless `rpc_urls` is set or offline mode is on
+    pub rpc_url: String,
+    /// `rpc_urls` / `RPC_URLS` (comma-separated): failover endpoints tried in order,
+    /// replacing `rpc_url` when set
+    pub rpc_urls: Vec<String>,
+    /// `network` / `NETWORK` (default: mainnet)
+    pub network: Network,
+    /// `timeout_secs` / `HTTP_TIMEOUT_SECS`: per-request timeout for outbound HTTP
+    pub timeout_secs: u64,
+    /// `enabled_tools` / `ENABLED_TOOLS`; None exposes every tool
+    pub enabled_tools: Option<HashSet<String>>,
+    /// `default_tokens` / `DEFAULT_TOKENS`: tokens `get_balance` reports when none are
+    /// requested; empty uses the built-in USDC/USDT/WETH list
+    pub default_tokens: Vec<ParsedAddress>,
+    /// `offline_mode` / `OFFLINE_MODE`: answer every call from the canned mock chain
+    /// instead of the RPC endpoint and price API
+    pub offline_mode: bool,
+    /// `quote_sources` / `QUOTE_SOURCES`: aggregators (`0x`, `1inch`) compared against
+    /// Uniswap in swap_tokens; each needs its API key in the environment
+    pub quote_sources: Vec<SwapSource>,
+    /// `numeric_json` / `NUMERIC_JSON`: write Decimal fields as JSON numbers instead of
+    /// exact strings (default: false)
+    pub numeric_json: bool,
+    /// `ws_max_reconnects` / `WS_MAX_RECONNECTS`: attempts to rebuild a dropped WebSocket
+    /// provider before its requests fail (default: 5)
+    pub ws_max_reconnects: u32,
+    /// `ws_reconnect_delay_ms` / `WS_RECONNECT_DELAY_MS`: delay before the first reconnect
+    /// attempt, doubled for each further one (default: 500)
+    pub ws_reconnect_delay_ms: u64,
+    /// `prefer_known_list` / `PREFER_KNOWN_LIST`: resolve well-known token metadata from
+    /// the built-in list before querying the contract (default: true)
+    pub prefer_known_list: bool,
+    /// `gas_limit_multiplier` / `GAS_LIMIT_MULTIPLIER`: padding applied to `eth_estimateGas`
+    /// results (default: 1.2)
+    pub gas_limit_multiplier: Decimal,
+    /// `gas_warn_gwei` / `GAS_WARN_GWEI`: gas price above which get_gas_price reports
+    /// `is_high` (default: 100)
+    pub gas_warn_gwei: Decimal,
+    /// `logs_max_range` / `GET_LOGS_MAX_RANGE`: widest block range one get_logs request
+    /// may span (default: 100000)
+    pub logs_max_range: u64,
+    /// `logs_chunk_size` / `GET_LOGS_CHUNK_SIZE`: block span of each `eth_getLogs` call
+    /// (default: 2000)
+    pub logs_chunk_size: u64,
+    /// `disable_mainnet_writes` / `DISABLE_MAINNET_WRITES`: refuse to broadcast on
+    /// mainnet (default: false)
+    pub disable_mainnet_writes: bool,
+    /// `confirmation_webhook_url` / `CONFIRMATION_WEBHOOK_URL`: endpoint receiving a POST
+    /// with each confirmed transaction's receipt (default: none)
+    pub confirmation_webhook_url: Option<String>,
+    /// `tool_timeout_secs` / `TOOL_TIMEOUT_SECS`: longest a single tool call may run
+    /// (default: 60)
+    pub tool_timeout_secs: u64,
+    /// `default_slippage_percent` / `DEFAULT_SLIPPAGE_PERCENT`: swap_tokens slippage when
+    /// the call sets none (default: 0.5)
+    pub default_slippage_percent: Decimal,
+    /// `max_slippage_percent` / `MAX_SLIPPAGE_PERCENT`: highest slippage swap_tokens
+    /// accepts, at most 50 (default: 50)
+    pub max_slippage_percent: Decimal,
+    /// `idempotency_ttl_secs` / `IDEMPOTENCY_TTL_SECS`: how long a signing tool result is
+    /// replayed for its `idempotency_key` (default: 3600)
+    pub idempotency_ttl_secs: u64,
+    /// `price_circuit_failure_threshold` / `PRICE_CIRCUIT_FAILURE_THRESHOLD`: consecutive
+    /// price API failures that open the circuit (default: 5)
+    pub price_circuit_failure_threshold: u32,
+    /// `price_circuit_cooldown_secs` / `PRICE_CIRCUIT_COOLDOWN_SECS`: how long an open
+    /// price circuit rejects calls (default: 30)
+    pub price_circuit_cooldown_secs: u64,
+    /// `max_request_bytes` / `MAX_REQUEST_BODY_BYTES`: largest HTTP body or stdio line
+    /// (default: 1048576)
+    pub max_request_bytes: usize,
+    /// `allowed_origins` / `ALLOWED_ORIGINS` (comma-separated): CORS origins of the HTTP
+    /// server, `*` for any (default: none)
+    pub allowed_origins: Vec<String>,
+    /// `pretty_json` / `PRETTY_JSON`: pretty-print /mcp responses unless the request says
+    /// otherwise (default: false)
+    pub pretty_json: bool,
+    /// `rate_limit_per_minute` / `RATE_LIMIT_PER_MINUTE`: requests per client and minute
+    /// on /mcp; 0 disables the limit (default: 0)
+    pub rate_limit_per_minute: u32,
+    /// `trust_client_id_header` / `TRUST_CLIENT_ID_HEADER`: key the rate limit on the
+    /// `X-Client-Id` header instead of the remote IP; only safe behind a proxy that sets
+    /// it (default: false)
+    pub trust_client_id_header: bool,
+}
+
+/// Settings as written in the config file; every field is optional
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FileConfig {
+    pub rpc_url: Option<String>,
+    pub rpc_urls: Option<Vec<String>>,
+    pub network: Option<String>,
+    pub timeout_secs: Option<u64>,
+    pub enabled_tools: Option<Vec<String>>,
+    pub default_tokens: Option<Vec<String>>,
+    pub offline_mode: Option<bool>,
+    pub quote_sources: Option<Vec<String>>,
+    pub numeric_json: Option<bool>,
+    pub ws_max_reconnects: Option<u32>,
+    pub ws_reconnect_delay_ms: Option<u64>,
+    pub prefer_known_list: Option<bool>,
+    pub gas_limit_multiplier: Option<Decimal>,
+    pub gas_warn_gwei: Option<Decimal>,
+    pub logs_max_range: Option<u64>,
+    pub logs_chunk_size: Option<u64>,
+    pub disable_mainnet_writes: Option<bool>,
+    pub confirmation_webhook_url: Option<String>,
+    pub tool_timeout_secs: Option<u64>,
+    pub default_slippage_percent: Option<Decimal>,
+    pub max_slippage_percent: Option<Decimal>,
+    pub idempotency_ttl_secs: Option<u64>,
+    pub price_circuit_failure_threshold: Option<u32>,
+    pub price_circuit_cooldown_secs: Option<u64>,
+    pub max_request_bytes: Option<usize>,
+    pub allowed_origins: Option<Vec<String>>,
+    pub pretty_json: Option<bool>,
+    pub rate_limit_per_minute: Option<u32>,
+    pub trust_client_id_header: Option<bool>,
+}
+
+impl FileConfig {
+    /// Parse config file contents; `.json` paths are JSON, anything else TOML
+    pub fn parse(path: &Path, contents: &str) -> Result<Self, MCPError> {
+        let is_json = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        if is_json {
+            serde_json::from_str(contents)
+                .map_err(|e| MCPError::ConfigurationError(format!("Invalid config file {}: {}", path.display(), e)))
+        } else {
+            toml::from_str(contents)
+                .map_err(|e| MCPError::ConfigurationError(format!("Invalid config file {}: {}", path.display(), e)))
+        }
+    }
+}
+
+impl Config {
+    /// Load `CONFIG_FILE` if set, filling unset fields from the environment and defaults
+    pub fn load() -> Result<Self, MCPError> {
+        let file = match std::env::var("CONFIG_FILE") {
+            Ok(path) if !path.trim().is_empty() => {
+                let path = Path::new(path.trim());
+                let contents = std::fs::read_to_string(path)
+                    .map_err(|e| MCPError::ConfigurationError(format!("Cannot read config file {}: {}", path.display(), e)))?;
+                info!(config_file = %path.display(), "Loading configuration file");
+                FileConfig::parse(path, &contents)?
+            },
+            _ => FileConfig::default(),
+        };
+        Self::resolve(file)
+    }
+
+    /// Configuration from environment variables and defaults only
+    pub fn from_env() -> Result<Self, MCPError> {
+        Self::resolve(FileConfig::default())
+    }
+
+    /// Merge file settings over the environment and defaults, validating the result
+    pub fn resolve(file: FileConfig) -> Result<Self, MCPError> {
+        let env = |key: &str| std::env::var(key).ok().filter(|v| !v.trim().is_empty());
+
+        let offline_mode = file.offline_mode
+            .or_else(|| env("OFFLINE_MODE").map(|v| v.eq_ignore_ascii_case("true")))
+            .unwrap_or(false);
+
+        let rpc_urls = file.rpc_urls
+            .map(|urls| parse_rpc_urls(&urls.join(",")))
+            .or_else(|| env("RPC_URLS").map(|value| parse_rpc_urls(&value)))
+            .unwrap_or_default();
+        let rpc_url = match file.rpc_url.or_else(|| env("RPC_URL")).or_else(|| rpc_urls.first().cloned()) {
+            Some(url) => url,
+            None if offline_mode => OFFLINE_RPC_URL.to_string(),
+            None => return Err(MCPError::ConfigurationError(
+                "RPC_URL is not set; point it (or rpc_url in CONFIG_FILE) at your node or RPC provider".to_string()
+            )),
+        };
+        for url in std::iter::once(&rpc_url).chain(&rpc_urls) {
+            InputValidator::validate_rpc_url(url)?;
+        }
+
+        let network = match file.network.or_else(|| env("NETWORK")) {
+            Some(network) => network.parse()?,
+            None => Network::default(),
+        };
+
+        let timeout_secs = file.timeout_secs
+            .or_else(|| env("HTTP_TIMEOUT_SECS").and_then(|v| v.parse::<u64>().ok()))
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_HTTP_TIMEOUT_SECS);
+
+        let enabled_tools = match file.enabled_tools {
+            Some(tools) => parse_enabled_tools(&tools.join(",")),
+            None => env("ENABLED_TOOLS").and_then(|value| parse_enabled_tools(&value)),
+        };
+
+        let default_tokens = file.default_tokens
+            .or_else(|| env("DEFAULT_TOKENS").map(|value| value.split(',').map(|token| token.trim().to_string()).collect()))
+            .unwrap_or_default()
+            .iter()
+            .filter(|token| !token.is_empty())
+            .map(|token| ParsedAddress::parse(token)
+                .map_err(|e| MCPError::ConfigurationError(format!("default_tokens: {}", e))))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let quote_sources = file.quote_sources
+            .or_else(|| env("QUOTE_SOURCES").map(|value| value.split(',').map(|source| source.trim().to_string()).collect()))
+            .unwrap_or_default()
+            .iter()
+            .filter(|source| !source.is_empty())
+            .map(|source| match source.parse::<SwapSource>() {
+                Ok(source) if source.api_key_env().is_some() => Ok(source),
+                Ok(_) => Err(MCPError::ConfigurationError(format!(
+                    "quote_sources: '{}' is always quoted; expected 0x or 1inch", source
+                ))),
+                Err(e) => Err(MCPError::ConfigurationError(format!("quote_sources: {}", e))),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let numeric_json = file.numeric_json
+            .or_else(|| env("NUMERIC_JSON").map(|v| v.eq_ignore_ascii_case("true")))
+            .unwrap_or(false);
+
+        let ws_max_reconnects = file.ws_max_reconnects
+            .or_else(|| env("WS_MAX_RECONNECTS").and_then(|v| v.parse::<u32>().ok()))
+            .unwrap_or(DEFAULT_WS_MAX_RECONNECTS);
+
+        let ws_reconnect_delay_ms = file.ws_reconnect_delay_ms
+            .or_else(|| env("WS_RECONNECT_DELAY_MS").and_then(|v| v.parse::<u64>().ok()))
+            .unwrap_or(DEFAULT_WS_RECONNECT_DELAY_MS);
+
+        let prefer_known_list = file.prefer_known_list
+            .or_else(|| env("PREFER_KNOWN_LIST").map(|v| !v.eq_ignore_ascii_case("false")))
+            .unwrap_or(true);
+
+        let gas_limit_multiplier = match file.gas_limit_multiplier {
+            Some(multiplier) => InputValidator::validate_gas_multiplier(&multiplier.to_string())
+                .map_err(|e| MCPError::ConfigurationError(format!("gas_limit_multiplier: {}", e)))?,
+            None => match env("GAS_LIMIT_MULTIPLIER") {
+                Some(value) => InputValidator::validate_gas_multiplier(value.trim())
+                    .map_err(|e| MCPError::ConfigurationError(format!("GAS_LIMIT_MULTIPLIER: {}", e)))?,
+                None => DEFAULT_GAS_LIMIT_MULTIPLIER,
+            },
+        };
+
+        let gas_warn_gwei = match file.gas_warn_gwei {
+            Some(gwei) => InputValidator::validate_gwei(&gwei.to_string())
+                .map_err(|e| MCPError::ConfigurationError(format!("gas_warn_gwei: {}", e)))?,
+            None => match env("GAS_WARN_GWEI") {
+                Some(value) => InputValidator::validate_gwei(value.trim())
+                    .map_err(|e| MCPError::ConfigurationError(format!("GAS_WARN_GWEI: {}", e)))?,
+                None => DEFAULT_GAS_WARN_GWEI,
+            },
+        };
+
+        let logs_max_range = file.logs_max_range
+            .or_else(|| env("GET_LOGS_MAX_RANGE").and_then(|v| v.parse::<u64>().ok()))
+            .filter(|blocks| *blocks > 0)
+            .unwrap_or(DEFAULT_LOGS_MAX_RANGE);
+
+        let logs_chunk_size = file.logs_chunk_size
+            .or_else(|| env("GET_LOGS_CHUNK_SIZE").and_then(|v| v.parse::<u64>().ok()))
+            .filter(|blocks| *blocks > 0)
+            .unwrap_or(DEFAULT_LOGS_CHUNK_SIZE);
+
+        let disable_mainnet_writes = file.disable_mainnet_writes
+            .or_else(|| env("DISABLE_MAINNET_WRITES").map(|v| v.eq_ignore_ascii_case("true")))
+            .unwrap_or(false);
+
+        let confirmation_webhook_url = file.confirmation_webhook_url
+            .or_else(|| env("CONFIRMATION_WEBHOOK_URL"))
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty());
+        if let Some(url) = &confirmation_webhook_url {
+            InputValidator::validate_webhook_url(url)?;
+        }
+
+        let tool_timeout_secs = file.tool_timeout_secs
+            .or_else(|| env("TOOL_TIMEOUT_SECS").and_then(|v| v.parse::<u64>().ok()))
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_TOOL_TIMEOUT_SECS);
+
+        let max_slippage_percent = match file.max_slippage_percent {
+            Some(cap) => Some(("max_slippage_percent", cap.to_string())),
+            None => env("MAX_SLIPPAGE_PERCENT").map(|value| ("MAX_SLIPPAGE_PERCENT", value.trim().to_string())),
+        };
+        let max_slippage_percent = match max_slippage_percent {
+            Some((key, value)) => match InputValidator::validate_slippage_with_cap(&value, ABSOLUTE_MAX_SLIPPAGE_PERCENT) {
+                Ok(cap) if cap > Decimal::ZERO => cap,
+                Ok(_) => return Err(MCPError::ConfigurationError(format!("{}: must be above 0", key))),
+                Err(e) => return Err(MCPError::ConfigurationError(format!("{}: {}", key, e))),
+            },
+            None => ABSOLUTE_MAX_SLIPPAGE_PERCENT,
+        };
+
+        let default_slippage_percent = match file.default_slippage_percent {
+            Some(slippage) => InputValidator::validate_slippage_with_cap(&slippage.to_string(), max_slippage_percent)
+                .map_err(|e| MCPError::ConfigurationError(format!("default_slippage_percent: {}", e)))?,
+            None => match env("DEFAULT_SLIPPAGE_PERCENT") {
+                Some(value) => InputValidator::validate_slippage_with_cap(value.trim(), max_slippage_percent)
+                    .map_err(|e| MCPError::ConfigurationError(format!("DEFAULT_SLIPPAGE_PERCENT: {}", e)))?,
+                None => DEFAULT_SLIPPAGE.min(max_slippage_percent),
+            },
+        };
+
+        let idempotency_ttl_secs = file.idempotency_ttl_secs
+            .or_else(|| env("IDEMPOTENCY_TTL_SECS").and_then(|v| v.parse::<u64>().ok()))
+            .unwrap_or(DEFAULT_IDEMPOTENCY_TTL_SECS);
+
+        let price_circuit_failure_threshold = file.price_circuit_failure_threshold
+            .or_else(|| env("PRICE_CIRCUIT_FAILURE_THRESHOLD").and_then(|v| v.parse::<u32>().ok()))
+            .unwrap_or(DEFAULT_PRICE_CIRCUIT_FAILURE_THRESHOLD);
+
+        let price_circuit_cooldown_secs = file.price_circuit_cooldown_secs
+            .or_else(|| env("PRICE_CIRCUIT_COOLDOWN_SECS").and_then(|v| v.parse::<u64>().ok()))
+            .unwrap_or(DEFAULT_PRICE_CIRCUIT_COOLDOWN_SECS);
+
+        let max_request_bytes = file.max_request_bytes
+            .or_else(|| env("MAX_REQUEST_BODY_BYTES").and_then(|v| v.parse::<usize>().ok()))
+            .unwrap_or(DEFAULT_MAX_REQUEST_BYTES);
+
+        let allowed_origins = file.allowed_origins
+            .or_else(|| env("ALLOWED_ORIGINS").map(|value| value.split(',').map(|origin| origin.to_string()).collect()))
+            .unwrap_or_default()
+            .iter()
+            .map(|origin| origin.trim())
+            .filter(|origin| !origin.is_empty())
+            .map(|origin| origin.to_string())
+            .collect();
+
+        let pretty_json = file.pretty_json
+            .or_else(|| env("PRETTY_JSON").map(|v| v.eq_ignore_ascii_case("true")))
+            .unwrap_or(false);
+
+        let rate_limit_per_minute = file.rate_limit_per_minute
+            .or_else(|| env("RATE_LIMIT_PER_MINUTE").and_then(|v| v.parse::<u32>().ok()))
+            .unwrap_or(0);
+
+        let trust_client_id_header = file.trust_client_id_header
+            .or_else(|| env("TRUST_CLIENT_ID_HEADER").map(|v| v.eq_ignore_ascii_case("true")))
+            .unwrap_or(false);
+
+        Ok(Self {
+            rpc_url,
+            rpc_urls,
+            network,
+            timeout_secs,
+            enabled_tools,
+            default_tokens,
+            offline_mode,
+            quote_sources,
+            numeric_json,
+            ws_max_reconnects,
+            ws_reconnect_delay_ms,
+            prefer_known_list,
+            gas_limit_multiplier,
+            gas_warn_gwei,
+            logs_max_range,
+            logs_chunk_size,
+            disable_mainnet_writes,
+            confirmation_webhook_url,
+            tool_timeout_secs,
+            default_slippage_percent,
+            max_slippage_percent,
+            idempotency_ttl_secs,
+            price_circuit_failure_threshold,
+            price_circuit_cooldown_secs,
+            max_request_bytes,
+            allowed_origins,
+            pretty_json,
+            rate_limit_per_minute,
+            trust_client_id_header,
+        })
+    }
+}
@@ -0,0 +1,76 @@
+//! Serde helpers for the `Decimal` fields of tool responses, used via
+//! `#[serde(with = "crate::decimal_json")]`. Values are written as JSON strings so no
+//! precision is lost in consumers that parse numbers as floats; `numeric_json` /
+//! `NUMERIC_JSON` switches them to JSON numbers for consumers that prefer those.
+//! Deserialization accepts either form.
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer, Serializer};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static NUMERIC_JSON: AtomicBool = AtomicBool::new(false);
+
+/// Write Decimal fields as JSON numbers (`true`) or strings (`false`, the default) process-wide
+pub fn set_numeric_json(numeric: bool) {
+    NUMERIC_JSON.store(numeric, Ordering::Relaxed);
+}
+
+/// Whether Decimal fields are currently written as JSON numbers
+pub fn numeric_json() -> bool {
+    NUMERIC_JSON.load(Ordering::Relaxed)
+}
+
+/// Write `value` as a number (rounded to the nearest f64) or as its exact string form
+pub fn serialize_as<S: Serializer>(value: &Decimal, numeric: bool, serializer: S) -> Result<S::Ok, S::Error> {
+    match value.to_f64() {
+        Some(number) if numeric => serializer.serialize_f64(number),
+        _ => serializer.collect_str(value),
+    }
+}
+
+pub fn serialize<S: Serializer>(value: &Decimal, serializer: S) -> Result<S::Ok, S::Error> {
+    serialize_as(value, numeric_json(), serializer)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Decimal, D::Error> {
+    <Decimal as Deserialize>::deserialize(deserializer)
+}
+
+/// The same for `Option<Decimal>`; None is always `null`
+pub mod option {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Option<Decimal>, serializer: S) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => super::serialize(value, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Decimal>, D::Error> {
+        Option::<Decimal>::deserialize(deserializer)
+    }
+}
+
+/// The same for `Vec<Decimal>`
+pub mod vec {
+    use super::*;
+    use serde::ser::SerializeSeq;
+
+    pub fn serialize<S: Serializer>(values: &[Decimal], serializer: S) -> Result<S::Ok, S::Error> {
+        let numeric = numeric_json();
+        let mut seq = serializer.serialize_seq(Some(values.len()))?;
+        for value in values {
+            match value.to_f64() {
+                Some(number) if numeric => seq.serialize_element(&number)?,
+                _ => seq.serialize_element(&value.to_string())?,
+            }
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Decimal>, D::Error> {
+        Vec::<Decimal>::deserialize(deserializer)
+    }
+}
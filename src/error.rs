@@ -1,9 +1,11 @@
 use thiserror::Error;
 use std::collections::HashMap;
 use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
 use std::str::FromStr;
 use serde_json::Value;
 use regex::Regex;
+use ethers::types::{BlockNumber, H256, U256};
 use lazy_static::lazy_static;
 
 #[derive(Error, Debug)]
@@ -53,6 +55,10 @@ pub enum MCPError {
     
     #[error("Transaction failed: {0}")]
     TransactionFailed(String),
+
+    /// The transaction may have reached the node; retrying could broadcast it twice
+    #[error("Transaction outcome unknown: {0}")]
+    TransactionOutcomeUnknown(String),
     
     #[error("Swap simulation failed: {0}")]
     SwapSimulationFailed(String),
@@ -110,7 +116,7 @@ pub enum MCPError {
     Http(#[from] reqwest::Error),
     
     #[error("Ethers error: {0}")]
-    Ethers(#[from] ethers::providers::ProviderError),
+    Ethers(ethers::providers::ProviderError),
     
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
@@ -139,6 +145,7 @@ impl Clone for MCPError {
             MCPError::InvalidContractAbi(msg) => MCPError::InvalidContractAbi(msg.clone()),
             MCPError::InsufficientBalance(msg) => MCPError::InsufficientBalance(msg.clone()),
             MCPError::TransactionFailed(msg) => MCPError::TransactionFailed(msg.clone()),
+            MCPError::TransactionOutcomeUnknown(msg) => MCPError::TransactionOutcomeUnknown(msg.clone()),
             MCPError::GasEstimationFailed(msg) => MCPError::GasEstimationFailed(msg.clone()),
             MCPError::SlippageTooHigh(msg) => MCPError::SlippageTooHigh(msg.clone()),
             MCPError::PriceFetchFailed(msg) => MCPError::PriceFetchFailed(msg.clone()),
@@ -165,6 +172,25 @@ impl Clone for MCPError {
     }
 }
 
+impl From<ethers::providers::ProviderError> for MCPError {
+    /// Transport failures (the endpoint could not be reached, or a WebSocket provider stayed
+    /// down through its reconnect attempts) are `NetworkError`; node responses stay `Ethers`
+    fn from(error: ethers::providers::ProviderError) -> Self {
+        use ethers::providers::ProviderError;
+
+        let unreachable = match &error {
+            ProviderError::JsonRpcClientError(inner) => inner.as_error_response().is_none() && inner.as_serde_error().is_none(),
+            ProviderError::HTTPError(_) => true,
+            _ => false,
+        };
+        if unreachable {
+            MCPError::NetworkError(error.to_string())
+        } else {
+            MCPError::Ethers(error)
+        }
+    }
+}
+
 impl MCPError {
     /// Get error code
     pub fn error_code(&self) -> i32 {
@@ -183,6 +209,7 @@ impl MCPError {
             MCPError::InvalidContractAbi(_) => -32602,
             MCPError::InsufficientBalance(_) => -32603,
             MCPError::TransactionFailed(_) => -32603,
+            MCPError::TransactionOutcomeUnknown(_) => -32603,
             MCPError::GasEstimationFailed(_) => -32603,
             MCPError::SlippageTooHigh(_) => -32603,
             MCPError::PriceFetchFailed(_) => -32603,
@@ -224,6 +251,7 @@ impl MCPError {
             MCPError::InvalidContractAbi(_) => ErrorSeverity::High,
             MCPError::InsufficientBalance(_) => ErrorSeverity::Medium,
             MCPError::TransactionFailed(_) => ErrorSeverity::High,
+            MCPError::TransactionOutcomeUnknown(_) => ErrorSeverity::High,
             MCPError::GasEstimationFailed(_) => ErrorSeverity::High,
             MCPError::SlippageTooHigh(_) => ErrorSeverity::Medium,
             MCPError::PriceFetchFailed(_) => ErrorSeverity::Medium,
@@ -287,9 +315,38 @@ pub enum ErrorSeverity {
     Critical,
 }
 
+/// Maximum number of wallet addresses in a single multi-balance request
+const MAX_ADDRESSES_PER_REQUEST: usize = 100;
+
+/// Longest wait_for_gas timeout, in seconds
+const MAX_GAS_WAIT_SECS: u64 = 3600;
+
+/// Longest wait_for_gas polling interval, in seconds
+const MAX_GAS_POLL_INTERVAL_SECS: u64 = 60;
+
+/// Maximum fractional digits for formatted balances
+const MAX_DECIMALS_DISPLAY: u64 = 18;
+
+/// Most field paths accepted by the `fields` response projection
+const MAX_FIELDS: usize = 50;
+
+/// Upper bound on gas limit padding, to catch typos like 12 for 1.2
+const MAX_GAS_MULTIPLIER: Decimal = dec!(3);
+
+/// eth_feeHistory accepts at most 1024 blocks
+const MAX_FEE_HISTORY_BLOCKS: u64 = 1024;
+
+/// Longest swap deadline accepted, in seconds from now (one day)
+const MAX_SWAP_DEADLINE_SECS: u64 = 86_400;
+
+/// Absolute slippage ceiling; `max_slippage_percent` in `Config` can only tighten it
+pub const ABSOLUTE_MAX_SLIPPAGE_PERCENT: Decimal = dec!(50);
+
 lazy_static! {
     static ref ETH_ADDRESS_REGEX: Regex = Regex::new(r"^0x[a-fA-F0-9]{40}$").unwrap();
     static ref PRIVATE_KEY_REGEX: Regex = Regex::new(r"^(0x)?[a-fA-F0-9]{64}$").unwrap();
+    static ref ENS_NAME_REGEX: Regex = Regex::new(r"^([a-z0-9-]+\.)+[a-z]{2,}$").unwrap();
+    static ref FUNCTION_SIGNATURE_REGEX: Regex = Regex::new(r"^[A-Za-z_$][A-Za-z0-9_$]*\([A-Za-z0-9_,()\[\]]*\)$").unwrap();
 }
 
 /// Input validator
@@ -308,9 +365,28 @@ impl InputValidator {
             ));
         }
         
+        // All-lowercase or all-uppercase input carries no checksum; mixed case must be valid EIP-55
+        let hex_part = &address[2..];
+        let is_mixed_case = hex_part.chars().any(|c| c.is_ascii_lowercase())
+            && hex_part.chars().any(|c| c.is_ascii_uppercase());
+        if is_mixed_case {
+            let parsed = address.parse::<ethers::types::Address>()
+                .map_err(|_| MCPError::InvalidAddress(address.to_string()))?;
+            if ethers::utils::to_checksum(&parsed, None) != address {
+                return Err(MCPError::InvalidAddress(
+                    format!("Invalid EIP-55 checksum: {}", address)
+                ));
+            }
+        }
+        
         Ok(())
     }
     
+    /// Whether the input looks like an ENS name (e.g. `vitalik.eth`) rather than a hex address
+    pub fn is_ens_name(input: &str) -> bool {
+        ENS_NAME_REGEX.is_match(&input.to_lowercase())
+    }
+    
     /// Validate private key
     pub fn validate_private_key(private_key: &str) -> Result<(), MCPError> {
         if private_key.is_empty() {
@@ -354,6 +430,11 @@ impl InputValidator {
     
     /// Validate slippage
     pub fn validate_slippage(slippage: &str) -> Result<Decimal, MCPError> {
+        Self::validate_slippage_with_cap(slippage, ABSOLUTE_MAX_SLIPPAGE_PERCENT)
+    }
+    
+    /// Validate slippage against an explicit ceiling
+    pub fn validate_slippage_with_cap(slippage: &str, max_slippage: Decimal) -> Result<Decimal, MCPError> {
         if slippage.is_empty() {
             return Err(MCPError::InvalidSlippage("Slippage cannot be empty".to_string()));
         }
@@ -369,21 +450,187 @@ impl InputValidator {
             ));
         }
         
-        if slippage_decimal > Decimal::from(50) {
+        if slippage_decimal > max_slippage {
             return Err(MCPError::InvalidSlippage(
-                format!("Slippage too high (max 50%): {}", slippage)
+                format!("Slippage too high (max {}%): {}", max_slippage, slippage)
             ));
         }
         
         Ok(slippage_decimal)
     }
     
+    /// Validate a gas price expressed in gwei
+    pub fn validate_gwei(gwei: &str) -> Result<Decimal, MCPError> {
+        let gwei_decimal = Decimal::from_str(gwei)
+            .map_err(|e| MCPError::InvalidParameterType(
+                format!("Invalid gwei value '{}': {}", gwei, e)
+            ))?;
+        
+        if gwei_decimal <= Decimal::ZERO {
+            return Err(MCPError::InvalidParameterType(
+                format!("Gwei value must be positive: {}", gwei)
+            ));
+        }
+        
+        Ok(gwei_decimal)
+    }
+    
+    /// Validate a gas limit multiplier (between 1 and 3)
+    pub fn validate_gas_multiplier(multiplier: &str) -> Result<Decimal, MCPError> {
+        let multiplier_decimal = Decimal::from_str(multiplier)
+            .map_err(|e| MCPError::InvalidParameterType(
+                format!("Invalid gas multiplier '{}': {}", multiplier, e)
+            ))?;
+        
+        if multiplier_decimal < Decimal::ONE || multiplier_decimal > MAX_GAS_MULTIPLIER {
+            return Err(MCPError::ValidationError(
+                format!("Gas multiplier must be between 1 and {}: {}", MAX_GAS_MULTIPLIER, multiplier)
+            ));
+        }
+        
+        Ok(multiplier_decimal)
+    }
+    
+    /// Validate the optional per-call `gas_multiplier` override
+    fn validate_gas_multiplier_param(args: &Value) -> Result<(), MCPError> {
+        if let Some(multiplier) = args.get("gas_multiplier") {
+            let multiplier_str = multiplier.as_str()
+                .ok_or_else(|| MCPError::InvalidParameterType("gas_multiplier must be a string".to_string()))?;
+            Self::validate_gas_multiplier(multiplier_str)?;
+        }
+        Ok(())
+    }
+    
+    /// Validate block number, accepting "latest", a decimal/0x-hex string, or an integer.
+    /// Returns None for "latest".
+    pub fn validate_block_number(block: &Value) -> Result<Option<u64>, MCPError> {
+        match block {
+            Value::Null => Ok(None),
+            Value::Number(n) => n.as_u64()
+                .map(Some)
+                .ok_or_else(|| MCPError::InvalidParameterType(
+                    format!("Block number must be a non-negative integer: {}", n)
+                )),
+            Value::String(s) if s == "latest" => Ok(None),
+            Value::String(s) => {
+                let parsed = match s.strip_prefix("0x") {
+                    Some(hex_str) => u64::from_str_radix(hex_str, 16),
+                    None => s.parse::<u64>(),
+                };
+                parsed.map(Some).map_err(|_| MCPError::InvalidParameterType(
+                    format!("Invalid block number: {}", s)
+                ))
+            },
+            _ => Err(MCPError::InvalidParameterType(
+                format!("Invalid block number: {}", block)
+            )),
+        }
+    }
+    
+    /// Validate a block reference for point-in-time reads: a block number or one of the
+    /// "latest", "pending", "safe" and "finalized" tags. Returns None for "latest".
+    pub fn validate_block_tag(block: &Value) -> Result<Option<BlockNumber>, MCPError> {
+        match block.as_str() {
+            Some("pending") => Ok(Some(BlockNumber::Pending)),
+            Some("safe") => Ok(Some(BlockNumber::Safe)),
+            Some("finalized") => Ok(Some(BlockNumber::Finalized)),
+            _ => Ok(Self::validate_block_number(block)?.map(|n| BlockNumber::Number(n.into()))),
+        }
+    }
+    
+    /// Validate a storage slot given as a 0x-hex or decimal string
+    pub fn validate_storage_slot(slot: &str) -> Result<U256, MCPError> {
+        Self::parse_uint256(slot).ok_or_else(|| MCPError::InvalidParameterType(
+            format!("Invalid storage slot (expected 0x-hex or decimal): {}", slot)
+        ))
+    }
+    
+    /// Validate an ERC1155 token id (uint256, 0x-hex or decimal)
+    pub fn validate_token_id(token_id: &str) -> Result<U256, MCPError> {
+        Self::parse_uint256(token_id).ok_or_else(|| MCPError::InvalidParameterType(
+            format!("Invalid token_id (expected 0x-hex or decimal uint256): {}", token_id)
+        ))
+    }
+    
+    /// A uint256 written as 0x-hex or decimal
+    fn parse_uint256(value: &str) -> Option<U256> {
+        match value.strip_prefix("0x") {
+            Some(hex_str) if !hex_str.is_empty() && hex_str.len() <= 64 => U256::from_str_radix(hex_str, 16).ok(),
+            Some(_) => None,
+            None => U256::from_dec_str(value).ok(),
+        }
+    }
+    
+    /// Validate a 32-byte 0x-prefixed hex value such as a salt, hash, or topic
+    pub fn validate_bytes32(value: &str, field: &str) -> Result<H256, MCPError> {
+        let is_bytes32 = value.len() == 66
+            && value.starts_with("0x")
+            && value[2..].chars().all(|c| c.is_ascii_hexdigit());
+        if !is_bytes32 {
+            return Err(MCPError::InvalidParameterType(
+                format!("{} must be a 32-byte 0x-prefixed hex string: {}", field, value)
+            ));
+        }
+        H256::from_str(value)
+            .map_err(|e| MCPError::InvalidParameterType(format!("Invalid {}: {}", field, e)))
+    }
+    
+    /// Decode `data` for hashing: UTF-8 text (the default) or hex with an optional `0x` prefix
+    pub fn validate_hash_input(data: &str, encoding: Option<&str>) -> Result<Vec<u8>, MCPError> {
+        match encoding.unwrap_or("utf8") {
+            "utf8" => Ok(data.as_bytes().to_vec()),
+            "hex" => hex::decode(data.strip_prefix("0x").unwrap_or(data))
+                .map_err(|e| MCPError::InvalidParameterType(format!("data is not valid hex: {}", e))),
+            other => Err(MCPError::ValidationError(
+                format!("Invalid encoding '{}': expected utf8 or hex", other)
+            )),
+        }
+    }
+    
+    /// Validate a canonical function signature such as `transfer(address,uint256)` (no spaces or names)
+    pub fn validate_function_signature(signature: &str) -> Result<(), MCPError> {
+        let balanced = signature.matches('(').count() == signature.matches(')').count();
+        if !FUNCTION_SIGNATURE_REGEX.is_match(signature) || !balanced {
+            return Err(MCPError::ValidationError(format!(
+                "Invalid function signature (expected e.g. transfer(address,uint256)): {}", signature
+            )));
+        }
+        Ok(())
+    }
+    
     /// Validate tool call parameters
     pub fn validate_tool_parameters(tool_name: &str, args: &Value) -> Result<(), MCPError> {
         match tool_name {
             "get_balance" => Self::validate_get_balance_params(args),
+            "get_balances_for_addresses" => Self::validate_get_balances_for_addresses_params(args),
+            "get_balance_changes" => Self::validate_get_balance_changes_params(args),
+            "get_all_token_balances" => Self::validate_get_all_token_balances_params(args),
+            "get_portfolio" => Self::validate_get_portfolio_params(args),
             "get_token_price" => Self::validate_get_token_price_params(args),
+            "get_onchain_price" => Self::validate_get_token_price_params(args),
+            "get_eth_price" => Ok(()),
+            "list_networks" => Ok(()),
+            "get_token_metadata" => Self::validate_get_token_price_params(args),
+            "get_erc1155_balance" => Self::validate_get_erc1155_balance_params(args),
             "swap_tokens" => Self::validate_swap_tokens_params(args),
+            "simulate_token_transfer" | "transfer_token" => Self::validate_token_transfer_params(args),
+            "lookup_ens_name" => Self::validate_lookup_ens_name_params(args),
+            "batch_lookup_ens" => Self::validate_addresses_array(args),
+            "get_code" => Self::validate_get_code_params(args),
+            "get_storage_at" => Self::validate_get_storage_at_params(args),
+            "detect_proxy" => Self::validate_get_code_params(args),
+            "get_pending_transactions" => Self::validate_get_code_params(args),
+            "get_fee_history" => Self::validate_get_fee_history_params(args),
+            "get_gas_price" => Ok(()),
+            "wait_for_gas" => Self::validate_wait_for_gas_params(args),
+            "get_logs" => Self::validate_get_logs_params(args),
+            "compute_create2_address" => Self::validate_compute_create2_address_params(args),
+            "validate_address" => Self::validate_validate_address_params(args),
+            "keccak256" => Self::validate_keccak256_params(args),
+            "function_selector" => Self::validate_function_selector_params(args),
+            "send_transaction" => Self::validate_send_transaction_params(args),
+            "replace_transaction" => Self::validate_replace_transaction_params(args),
+            "build_transaction" => Self::validate_build_transaction_params(args),
             _ => Err(MCPError::ValidationError(
                 format!("Unknown tool: {}", tool_name)
             )),
@@ -400,14 +647,182 @@ impl InputValidator {
         
         // Validate optional token_address
         if let Some(token_address) = args.get("token_address") {
-            if let Some(addr) = token_address.as_str() {
+            if let Some(addr) = token_address.as_str().filter(|addr| !crate::address::is_native_eth(addr)) {
                 Self::validate_address(addr)?;
             }
         }
         
+        // Validate optional block_number
+        if let Some(block) = args.get("block_number") {
+            Self::validate_block_tag(block)?;
+        }
+        
+        if let Some(confirmations) = args.get("confirmations") {
+            confirmations.as_u64()
+                .ok_or_else(|| MCPError::InvalidParameterType("confirmations must be a non-negative integer".to_string()))?;
+            if args.get("block_number").is_some() {
+                return Err(MCPError::ValidationError("block_number and confirmations are mutually exclusive".to_string()));
+            }
+        }
+        
+        Self::validate_decimals_display(args)?;
+        Self::validate_fields(args)?;
+        
+        if let Some(include_usd_value) = args.get("include_usd_value") {
+            if !include_usd_value.is_boolean() {
+                return Err(MCPError::InvalidParameterType("include_usd_value must be a boolean".to_string()));
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Validate full token balance listing parameters
+    fn validate_get_all_token_balances_params(args: &Value) -> Result<(), MCPError> {
+        let address = args.get("address")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::MissingParameter("address".to_string()))?;
+        
+        Self::validate_address(address)?;
+        Self::validate_decimals_display(args)?;
+        Self::validate_fields(args)?;
+        
+        if let Some(include_usd_value) = args.get("include_usd_value") {
+            if !include_usd_value.is_boolean() {
+                return Err(MCPError::InvalidParameterType("include_usd_value must be a boolean".to_string()));
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Validate optional balance display precision
+    fn validate_decimals_display(args: &Value) -> Result<(), MCPError> {
+        if let Some(decimals_display) = args.get("decimals_display") {
+            let dp = decimals_display.as_u64()
+                .ok_or_else(|| MCPError::InvalidParameterType("decimals_display must be a non-negative integer".to_string()))?;
+            if dp > MAX_DECIMALS_DISPLAY {
+                return Err(MCPError::ValidationError(
+                    format!("decimals_display too large (max {}): {}", MAX_DECIMALS_DISPLAY, dp)
+                ));
+            }
+        }
+        
         Ok(())
     }
     
+    /// Validate the optional `fields` response projection: a non-empty array of dotted paths
+    pub fn validate_fields(args: &Value) -> Result<Option<Vec<String>>, MCPError> {
+        let Some(fields) = args.get("fields") else {
+            return Ok(None);
+        };
+        let fields = fields.as_array()
+            .filter(|fields| !fields.is_empty())
+            .ok_or_else(|| MCPError::InvalidParameterType("fields must be a non-empty array of strings".to_string()))?;
+        if fields.len() > MAX_FIELDS {
+            return Err(MCPError::ValidationError(format!("fields accepts at most {} entries", MAX_FIELDS)));
+        }
+        fields.iter()
+            .map(|field| field.as_str()
+                .filter(|field| !field.is_empty() && field.split('.').all(|segment| !segment.is_empty()))
+                .map(|field| field.to_string())
+                .ok_or_else(|| MCPError::InvalidParameterType(format!("Invalid field path: {}", field))))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Some)
+    }
+    
+    /// Validate multi-address balance query parameters.
+    /// Individual addresses are validated per entry so one bad address doesn't fail the batch.
+    fn validate_get_balances_for_addresses_params(args: &Value) -> Result<(), MCPError> {
+        Self::validate_addresses_array(args)?;
+        Self::validate_decimals_display(args)?;
+        Self::validate_fields(args)?;
+        
+        // Token addresses apply to every wallet, so they must all be valid
+        Self::validate_token_addresses(args)
+    }
+    
+    /// Validate the `addresses` array of a batch tool: non-empty strings, at most
+    /// MAX_ADDRESSES_PER_REQUEST. Each address itself is checked when it is processed.
+    fn validate_addresses_array(args: &Value) -> Result<(), MCPError> {
+        let addresses = args.get("addresses")
+            .ok_or_else(|| MCPError::MissingParameter("addresses".to_string()))?
+            .as_array()
+            .ok_or_else(|| MCPError::InvalidParameterType("addresses must be an array".to_string()))?;
+        
+        if addresses.is_empty() {
+            return Err(MCPError::ValidationError("addresses cannot be empty".to_string()));
+        }
+        
+        if addresses.len() > MAX_ADDRESSES_PER_REQUEST {
+            return Err(MCPError::ValidationError(
+                format!("Too many addresses (max {}): {}", MAX_ADDRESSES_PER_REQUEST, addresses.len())
+            ));
+        }
+        
+        if addresses.iter().any(|a| !a.is_string()) {
+            return Err(MCPError::InvalidParameterType("addresses must contain strings".to_string()));
+        }
+        Ok(())
+    }
+    
+    /// Validate the optional `token_addresses` array
+    fn validate_token_addresses(args: &Value) -> Result<(), MCPError> {
+        if let Some(tokens) = args.get("token_addresses") {
+            let tokens = tokens.as_array()
+                .ok_or_else(|| MCPError::InvalidParameterType("token_addresses must be an array".to_string()))?;
+            if tokens.len() > MAX_ADDRESSES_PER_REQUEST {
+                return Err(MCPError::ValidationError(
+                    format!("Too many token addresses (max {}): {}", MAX_ADDRESSES_PER_REQUEST, tokens.len())
+                ));
+            }
+            for token in tokens {
+                let token_str = token.as_str()
+                    .ok_or_else(|| MCPError::InvalidParameterType("token_addresses must contain strings".to_string()))?;
+                Self::validate_address(token_str)?;
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Validate balance comparison parameters; numeric blocks must be in order
+    fn validate_get_balance_changes_params(args: &Value) -> Result<(), MCPError> {
+        let address = args.get("address")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::MissingParameter("address".to_string()))?;
+        Self::validate_address(address)?;
+
+        let from_block = Self::validate_block_tag(
+            args.get("from_block").ok_or_else(|| MCPError::MissingParameter("from_block".to_string()))?
+        )?;
+        let to_block = args.get("to_block")
+            .map(Self::validate_block_tag)
+            .transpose()?
+            .flatten();
+        if let (Some(BlockNumber::Number(from)), Some(BlockNumber::Number(to))) = (from_block, to_block) {
+            if from > to {
+                return Err(MCPError::ValidationError(format!(
+                    "from_block ({}) must not be after to_block ({})", from, to
+                )));
+            }
+        }
+
+        Self::validate_token_addresses(args)
+    }
+    
+    /// Validate portfolio parameters
+    fn validate_get_portfolio_params(args: &Value) -> Result<(), MCPError> {
+        let address = args.get("address")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::MissingParameter("address".to_string()))?;
+        
+        Self::validate_address(address)?;
+        Self::validate_decimals_display(args)?;
+        Self::validate_fields(args)?;
+        Self::validate_token_addresses(args)
+    }
+    
     /// Validate price query parameters
     fn validate_get_token_price_params(args: &Value) -> Result<(), MCPError> {
         let token_address = args.get("token_address")
@@ -415,6 +830,12 @@ impl InputValidator {
             .ok_or_else(|| MCPError::MissingParameter("token_address".to_string()))?;
         
         Self::validate_address(token_address)?;
+        
+        if let Some(source) = args.get("source") {
+            let source = source.as_str()
+                .ok_or_else(|| MCPError::InvalidParameterType("source must be a string".to_string()))?;
+            source.parse::<crate::types::PriceSourceSelection>()?;
+        }
         Ok(())
     }
     
@@ -432,8 +853,19 @@ impl InputValidator {
             .and_then(|v| v.as_str())
             .ok_or_else(|| MCPError::MissingParameter("amount".to_string()))?;
         
-        Self::validate_address(from_token)?;
-        Self::validate_address(to_token)?;
+        let from_native = crate::address::is_native_eth(from_token);
+        let to_native = crate::address::is_native_eth(to_token);
+        if !from_native {
+            Self::validate_address(from_token)?;
+        }
+        if !to_native {
+            Self::validate_address(to_token)?;
+        }
+        
+        if (from_native && to_native) || from_token.eq_ignore_ascii_case(to_token) {
+            return Err(MCPError::ValidationError("from_token and to_token must differ".to_string()));
+        }
+        
         Self::validate_amount(amount)?;
         
         // Validate optional slippage
@@ -443,6 +875,363 @@ impl InputValidator {
             }
         }
         
+        if let Some(wallet) = args.get("wallet_address") {
+            let wallet_str = wallet.as_str()
+                .ok_or_else(|| MCPError::InvalidParameterType("wallet_address must be a string".to_string()))?;
+            Self::validate_address(wallet_str)?;
+        }
+        
+        if let Some(deadline) = args.get("deadline_secs") {
+            let secs = deadline.as_u64()
+                .ok_or_else(|| MCPError::InvalidParameterType("deadline_secs must be a positive integer".to_string()))?;
+            if secs == 0 || secs > MAX_SWAP_DEADLINE_SECS {
+                return Err(MCPError::ValidationError(
+                    format!("deadline_secs must be between 1 and {}: {}", MAX_SWAP_DEADLINE_SECS, secs)
+                ));
+            }
+        }
+        
+        if let Some(skip) = args.get("skip_balance_check") {
+            if !skip.is_boolean() {
+                return Err(MCPError::InvalidParameterType("skip_balance_check must be a boolean".to_string()));
+            }
+        }
+        
+        if let Some(currency) = args.get("quote_currency") {
+            let currency = currency.as_str()
+                .ok_or_else(|| MCPError::InvalidParameterType("quote_currency must be a string".to_string()))?;
+            currency.parse::<crate::types::QuoteCurrency>()?;
+        }
+        
+        Self::validate_gas_multiplier_param(args)?;
+        
+        Ok(())
+    }
+    
+    /// Validate ERC20 transfer parameters
+    fn validate_token_transfer_params(args: &Value) -> Result<(), MCPError> {
+        let token_address = args.get("token_address")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::MissingParameter("token_address".to_string()))?;
+        
+        let to = args.get("to")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::MissingParameter("to".to_string()))?;
+        
+        let amount = args.get("amount")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::MissingParameter("amount".to_string()))?;
+        
+        Self::validate_address(token_address)?;
+        Self::validate_address(to)?;
+        Self::validate_amount(amount)?;
+        Self::validate_gas_multiplier_param(args)?;
+        Self::validate_idempotency_key_param(args)?;
+        
+        Ok(())
+    }
+    
+    /// Validate the optional `idempotency_key` of signing tools: 1 to 128 characters
+    fn validate_idempotency_key_param(args: &Value) -> Result<(), MCPError> {
+        if let Some(key) = args.get("idempotency_key") {
+            let key = key.as_str()
+                .ok_or_else(|| MCPError::InvalidParameterType("idempotency_key must be a string".to_string()))?;
+            if key.is_empty() || key.len() > 128 {
+                return Err(MCPError::ValidationError(
+                    "idempotency_key must be between 1 and 128 characters".to_string()
+                ));
+            }
+        }
+        Ok(())
+    }
+    
+    /// Validate ENS reverse lookup parameters
+    fn validate_lookup_ens_name_params(args: &Value) -> Result<(), MCPError> {
+        let address = args.get("address")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::MissingParameter("address".to_string()))?;
+        
+        Self::validate_address(address)?;
+        Ok(())
+    }
+    
+    /// Validate contract code query parameters
+    fn validate_get_code_params(args: &Value) -> Result<(), MCPError> {
+        let address = args.get("address")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::MissingParameter("address".to_string()))?;
+        
+        Self::validate_address(address)?;
+        Ok(())
+    }
+    
+    /// Validate ERC1155 balance query parameters
+    fn validate_get_erc1155_balance_params(args: &Value) -> Result<(), MCPError> {
+        for field in ["contract_address", "owner"] {
+            let address = args.get(field)
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| MCPError::MissingParameter(field.to_string()))?;
+            Self::validate_address(address)?;
+        }
+
+        let token_id = args.get("token_id")
+            .ok_or_else(|| MCPError::MissingParameter("token_id".to_string()))?
+            .as_str()
+            .ok_or_else(|| MCPError::InvalidParameterType("token_id must be a string".to_string()))?;
+        Self::validate_token_id(token_id)?;
+        Ok(())
+    }
+    
+    /// Validate storage read parameters
+    fn validate_get_storage_at_params(args: &Value) -> Result<(), MCPError> {
+        Self::validate_get_code_params(args)?;
+
+        let slot = args.get("slot")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::MissingParameter("slot".to_string()))?;
+        
+        Self::validate_storage_slot(slot)?;
+        Ok(())
+    }
+    
+    /// Validate gas wait parameters; `timeout_secs` is checked against the tool timeout by the handler
+    fn validate_wait_for_gas_params(args: &Value) -> Result<(), MCPError> {
+        let target = args.get("target_gwei")
+            .ok_or_else(|| MCPError::MissingParameter("target_gwei".to_string()))?
+            .as_str()
+            .ok_or_else(|| MCPError::InvalidParameterType("target_gwei must be a string".to_string()))?;
+        Self::validate_gwei(target)?;
+
+        for (field, max) in [("timeout_secs", MAX_GAS_WAIT_SECS), ("poll_interval_secs", MAX_GAS_POLL_INTERVAL_SECS)] {
+            if let Some(value) = args.get(field) {
+                let secs = value.as_u64()
+                    .ok_or_else(|| MCPError::InvalidParameterType(format!("{} must be a positive integer", field)))?;
+                if secs == 0 || secs > max {
+                    return Err(MCPError::ValidationError(format!("{} must be between 1 and {}: {}", field, max, secs)));
+                }
+            }
+        }
+        Ok(())
+    }
+    
+    /// Validate fee history parameters
+    fn validate_get_fee_history_params(args: &Value) -> Result<(), MCPError> {
+        if let Some(block_count) = args.get("block_count") {
+            let count = block_count.as_u64()
+                .ok_or_else(|| MCPError::InvalidParameterType("block_count must be a positive integer".to_string()))?;
+            if count == 0 || count > MAX_FEE_HISTORY_BLOCKS {
+                return Err(MCPError::ValidationError(
+                    format!("block_count must be between 1 and {}: {}", MAX_FEE_HISTORY_BLOCKS, count)
+                ));
+            }
+        }
+        
+        if let Some(percentiles) = args.get("reward_percentiles") {
+            let percentiles = percentiles.as_array()
+                .ok_or_else(|| MCPError::InvalidParameterType("reward_percentiles must be an array".to_string()))?;
+            let mut previous = 0.0;
+            for percentile in percentiles {
+                let value = percentile.as_f64()
+                    .ok_or_else(|| MCPError::InvalidParameterType("reward_percentiles must contain numbers".to_string()))?;
+                // eth_feeHistory requires monotonically increasing values in [0, 100]
+                if !(0.0..=100.0).contains(&value) || value < previous {
+                    return Err(MCPError::ValidationError(
+                        "reward_percentiles must be increasing values between 0 and 100".to_string()
+                    ));
+                }
+                previous = value;
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Validate event log query parameters
+    fn validate_get_logs_params(args: &Value) -> Result<(), MCPError> {
+        if let Some(address) = args.get("address") {
+            let address = address.as_str()
+                .ok_or_else(|| MCPError::InvalidParameterType("address must be a string".to_string()))?;
+            Self::validate_address(address)?;
+        }
+        
+        let from_block = args.get("from_block")
+            .ok_or_else(|| MCPError::MissingParameter("from_block".to_string()))?;
+        if Self::validate_block_number(from_block)?.is_none() {
+            return Err(MCPError::InvalidParameterType("from_block must be a block number".to_string()));
+        }
+        
+        if let Some(to_block) = args.get("to_block") {
+            Self::validate_block_number(to_block)?;
+        }
+        
+        if let Some(topics) = args.get("topics") {
+            let topics = topics.as_array()
+                .ok_or_else(|| MCPError::InvalidParameterType("topics must be an array".to_string()))?;
+            if topics.len() > 4 {
+                return Err(MCPError::ValidationError("topics accepts at most 4 entries".to_string()));
+            }
+            for topic in topics {
+                match topic {
+                    Value::Null => {},
+                    Value::String(s) => {
+                        Self::validate_bytes32(s, "topic")?;
+                    },
+                    _ => return Err(MCPError::InvalidParameterType(
+                        format!("topics entries must be 32-byte hex strings or null: {}", topic)
+                    )),
+                }
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Validate CREATE2 address computation parameters
+    fn validate_compute_create2_address_params(args: &Value) -> Result<(), MCPError> {
+        let deployer = args.get("deployer")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::MissingParameter("deployer".to_string()))?;
+        Self::validate_address(deployer)?;
+        
+        for field in ["salt", "init_code_hash"] {
+            let value = args.get(field)
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| MCPError::MissingParameter(field.to_string()))?;
+            Self::validate_bytes32(value, field)?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Validate address check parameters; the address itself is checked by the tool
+    fn validate_validate_address_params(args: &Value) -> Result<(), MCPError> {
+        args.get("address")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::MissingParameter("address".to_string()))?;
+        Ok(())
+    }
+    
+    /// Validate hashing parameters
+    fn validate_keccak256_params(args: &Value) -> Result<(), MCPError> {
+        let data = args.get("data")
+            .ok_or_else(|| MCPError::MissingParameter("data".to_string()))?
+            .as_str()
+            .ok_or_else(|| MCPError::InvalidParameterType("data must be a string".to_string()))?;
+        let encoding = match args.get("encoding") {
+            Some(encoding) => Some(encoding.as_str()
+                .ok_or_else(|| MCPError::InvalidParameterType("encoding must be a string".to_string()))?),
+            None => None,
+        };
+        Self::validate_hash_input(data, encoding)?;
+        Ok(())
+    }
+    
+    /// Validate selector parameters
+    fn validate_function_selector_params(args: &Value) -> Result<(), MCPError> {
+        let signature = args.get("signature")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::MissingParameter("signature".to_string()))?;
+        Self::validate_function_signature(signature)
+    }
+    
+    /// Validate transaction sending parameters
+    fn validate_send_transaction_params(args: &Value) -> Result<(), MCPError> {
+        let to = args.get("to")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::MissingParameter("to".to_string()))?;
+        
+        let amount = args.get("amount")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::MissingParameter("amount".to_string()))?;
+        
+        Self::validate_address(to)?;
+        Self::validate_amount(amount)?;
+        
+        // Validate optional fee overrides
+        for field in ["max_fee_per_gas", "max_priority_fee_per_gas"] {
+            if let Some(fee) = args.get(field) {
+                let fee_str = fee.as_str()
+                    .ok_or_else(|| MCPError::InvalidParameterType(format!("{} must be a string", field)))?;
+                Self::validate_gwei(fee_str)?;
+            }
+        }
+        
+        for field in ["legacy", "skip_simulation", "allow_self_transfer"] {
+            if let Some(flag) = args.get(field) {
+                if !flag.is_boolean() {
+                    return Err(MCPError::InvalidParameterType(format!("{} must be a boolean", field)));
+                }
+            }
+        }
+        
+        Self::validate_gas_multiplier_param(args)?;
+        Self::validate_idempotency_key_param(args)?;
+        
+        Ok(())
+    }
+    
+    /// Validate transaction replacement parameters
+    fn validate_replace_transaction_params(args: &Value) -> Result<(), MCPError> {
+        let nonce = args.get("nonce")
+            .ok_or_else(|| MCPError::MissingParameter("nonce".to_string()))?;
+        nonce.as_u64()
+            .ok_or_else(|| MCPError::InvalidParameterType("nonce must be a non-negative integer".to_string()))?;
+        
+        if let Some(bump) = args.get("bump_percent") {
+            Self::validate_bump_percent(bump)?;
+        }
+        
+        if let Some(cancel) = args.get("cancel") {
+            if !cancel.is_boolean() {
+                return Err(MCPError::InvalidParameterType("cancel must be a boolean".to_string()));
+            }
+        }
+        
+        Self::validate_idempotency_key_param(args)?;
+        
+        Ok(())
+    }
+    
+    /// Validate a replacement fee bump: a number or decimal string between 10 and 500 percent.
+    /// Nodes reject same-nonce replacements that raise fees by less than 10%.
+    pub fn validate_bump_percent(bump: &Value) -> Result<Decimal, MCPError> {
+        let percent = match bump {
+            Value::String(s) => Decimal::from_str(s).ok(),
+            Value::Number(n) => Decimal::from_str(&n.to_string()).ok(),
+            _ => None,
+        }.ok_or_else(|| MCPError::InvalidParameterType("bump_percent must be a number".to_string()))?;
+        
+        if percent < dec!(10) || percent > dec!(500) {
+            return Err(MCPError::ValidationError(
+                format!("bump_percent must be between 10 and 500, got {}", percent)
+            ));
+        }
+        Ok(percent)
+    }
+    
+    /// Validate unsigned transaction building parameters
+    fn validate_build_transaction_params(args: &Value) -> Result<(), MCPError> {
+        Self::validate_send_transaction_params(args)?;
+        
+        // Validate optional sender override
+        if let Some(from) = args.get("from") {
+            let from_str = from.as_str()
+                .ok_or_else(|| MCPError::InvalidParameterType("from must be a string".to_string()))?;
+            Self::validate_address(from_str)?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Validate the transaction confirmation webhook URL
+    pub fn validate_webhook_url(url: &str) -> Result<(), MCPError> {
+        let parsed = url::Url::parse(url)
+            .map_err(|e| MCPError::ConfigurationError(format!("Invalid webhook URL '{}': {}", url, e)))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(MCPError::ConfigurationError(
+                format!("Webhook URL must use http or https: {}", url)
+            ));
+        }
         Ok(())
     }
     
@@ -452,7 +1241,8 @@ impl InputValidator {
             return Err(MCPError::ConfigurationError("RPC URL cannot be empty".to_string()));
         }
         
-        if !url.starts_with("http://") && !url.starts_with("https://") {
+        let is_websocket = url.starts_with("ws://") || url.starts_with("wss://");
+        if !url.starts_with("http://") && !url.starts_with("https://") && !is_websocket {
             return Err(MCPError::ConfigurationError(
                 format!("Invalid RPC URL format: {}", url)
             ));
@@ -475,15 +1265,13 @@ pub struct ErrorRecovery;
 impl ErrorRecovery {
     /// Check if error is recoverable
     pub fn is_recoverable(error: &MCPError) -> bool {
-        match error {
-            MCPError::NetworkError(_) => true,
-            MCPError::RpcTimeout(_) => true,
-            MCPError::RateLimitExceeded(_) => true,
-            MCPError::ApiRateLimitExceeded(_) => true,
-            MCPError::Http(_) => true,
-            MCPError::Timeout(_) => true,
-            _ => false,
-        }
+        matches!(
+            error,
+            MCPError::NetworkError(_)
+                | MCPError::RpcTimeout(_)
+                | MCPError::RateLimitExceeded(_)
+                | MCPError::Http(_)
+        )
     }
     
     /// Get retry delay time (seconds)
@@ -509,6 +1297,44 @@ impl ErrorRecovery {
             _ => 1,
         }
     }
+    
+    /// Run `operation`, retrying recoverable errors with `retry_delay` backoff up to `max_retries`
+    /// times. Each retry is logged and counted on `ctx`; a success after retrying logs the total attempts.
+    /// No retry is started if its delay would run past `budget`, measured from the first attempt.
+    pub async fn retry<T, F, Fut>(
+        ctx: &mut crate::logging::RequestContext,
+        name: &str,
+        budget: std::time::Duration,
+        mut operation: F,
+    ) -> Result<T, MCPError>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T, MCPError>>,
+    {
+        let started = std::time::Instant::now();
+        let mut retries = 0;
+        loop {
+            match operation().await {
+                Ok(value) => {
+                    if retries > 0 {
+                        crate::logging::log_retry_success(ctx, name, retries + 1);
+                    }
+                    return Ok(value);
+                },
+                Err(e) if Self::is_recoverable(&e)
+                    && retries < Self::max_retries(&e)
+                    && started.elapsed() + std::time::Duration::from_secs(Self::retry_delay(&e, retries)) < budget =>
+                {
+                    let delay_secs = Self::retry_delay(&e, retries);
+                    retries += 1;
+                    crate::logging::log_retry_attempt(ctx, name, retries, Self::max_retries(&e), delay_secs, &e);
+                    ctx.record_retry();
+                    tokio::time::sleep(std::time::Duration::from_secs(delay_secs)).await;
+                },
+                Err(e) => return Err(e),
+            }
+        }
+    }
 }
 
 /// Error handler
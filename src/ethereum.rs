@@ -1,92 +1,606 @@
 use crate::error::MCPError;
-use crate::types::{BalanceInfo, TokenBalance, PriceInfo, SwapSimulation};
+use crate::types::{BalanceChanges, BalanceInfo, TokenBalanceChange, ConfirmationWebhookPayload, MetadataSource, TokenBalance, PriceInfo, PriceSource, PriceSourceSelection, RouteHop, SwapQuote, SwapSimulation, SwapSource, EnsLookup, EnsBatchLookup, ContractCode, Erc1155Balance, DecodedTransfer, StorageSlot, FailedLogChunk, FeeHistoryBlock, FeeHistoryInfo, GasPriceInfo, GasWaitInfo, KeySource, LogEntry, LogsInfo, PendingTransaction, PendingTransactionsInfo, ProxyInfo, ProxyType, MultiBalanceInfo, OnchainPriceInfo, PortfolioInfo, PortfolioToken, ReplacementTransactionInfo, TokenMetadataInfo, TokenStandard, TransactionFeeOptions, TokenTransferInfo, TransactionInfo, TransferSimulation, UnsignedTransaction, WalletMode};
 use crate::error::InputValidator;
+use crate::network::Network;
+use crate::circuit_breaker::CircuitBreaker;
+use crate::address::ParsedAddress;
+use crate::failover::{FailoverTransport, WsReconnect};
+use crate::transport::{AggregatorQuote, AlchemyTransport, RpcTransport};
+use crate::mock::OfflineTransport;
+use crate::config::{Config, FileConfig};
 use ethers::{
-    providers::{Provider, Http, Middleware},
-    types::{Address, U256, NameOrAddress},
+    middleware::{SignerMiddleware, signer::SignerMiddlewareError},
+    providers::{Provider, Middleware, ProviderError, RpcError},
+    signers::{LocalWallet, Signer},
+    types::{
+        transaction::eip2718::TypedTransaction, Address, BlockId, BlockNumber, Bytes, Eip1559TransactionRequest,
+        Filter, Log, NameOrAddress, Transaction, TransactionReceipt, TransactionRequest, H256, U256,
+    },
 };
-use rust_decimal::Decimal;
+use futures::stream::{self, StreamExt};
+use rust_decimal::{Decimal, RoundingStrategy};
 use rust_decimal_macros::dec;
 use rust_decimal::prelude::ToPrimitive;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use tracing::{info, debug, instrument, warn};
 use reqwest;
+use serde::{Deserialize, Serialize};
 use serde_json;
 
+/// Price impact (in percent) above which a swap simulation is flagged
+const HIGH_PRICE_IMPACT_THRESHOLD: Decimal = dec!(5);
+
+/// How long to wait for a broadcast transaction to be mined
+const RECEIPT_TIMEOUT_SECS: u64 = 120;
+
+/// Headroom left before the tool deadline when waiting for a receipt, so the transaction
+/// hash is returned before the tool call is cut off
+const RECEIPT_DEADLINE_MARGIN_MS: u64 = 1000;
+
+/// How long a transaction still pending when its tool call returned is watched in the
+/// background, so the confirmation webhook fires once it is mined
+const CONFIRMATION_WATCH_SECS: u64 = 3600;
+
+tokio::task_local! {
+    /// Deadline of the tool call running on this task; see `with_tool_deadline`
+    static TOOL_DEADLINE: std::time::Instant;
+}
+
+/// Run `future` as a tool call that must finish by `deadline`. Receipt waits inside it end
+/// early enough for a broadcast transaction to be reported as pending with its hash.
+pub async fn with_tool_deadline<F: std::future::Future>(deadline: std::time::Instant, future: F) -> F::Output {
+    TOOL_DEADLINE.scope(deadline, future).await
+}
+
+/// Default number of fractional digits in `balance_formatted`
+pub const DEFAULT_DECIMALS_DISPLAY: u32 = 6;
+
+/// Upper bound on concurrent per-address queries in multi-balance requests
+const MAX_CONCURRENT_BALANCE_QUERIES: usize = 4;
+
+/// Upper bound on concurrent `balanceOf` calls when one address is queried for several tokens
+const MAX_CONCURRENT_TOKEN_BALANCE_QUERIES: usize = 8;
+
+/// Default widest block range a single get_logs request may span
+pub const DEFAULT_LOGS_MAX_RANGE: u64 = 100_000;
+
+/// Default block span of each `eth_getLogs` sub-request
+pub const DEFAULT_LOGS_CHUNK_SIZE: u64 = 2_000;
+
+/// Consecutive price API failures that open its circuit, unless `PRICE_CIRCUIT_FAILURE_THRESHOLD` is set
+pub const DEFAULT_PRICE_CIRCUIT_FAILURE_THRESHOLD: u32 = 5;
+
+/// Seconds an open price circuit rejects calls, unless `PRICE_CIRCUIT_COOLDOWN_SECS` is set
+pub const DEFAULT_PRICE_CIRCUIT_COOLDOWN_SECS: u64 = 30;
+
+/// Upper bound on concurrent ENS reverse lookups in batch_lookup_ens
+const MAX_CONCURRENT_ENS_LOOKUPS: usize = 8;
+
+/// Upper bound on concurrent `eth_getLogs` sub-requests
+const MAX_CONCURRENT_LOG_QUERIES: usize = 4;
+
+/// ERC-165 interface ids
+const ERC721_INTERFACE_ID: [u8; 4] = [0x80, 0xac, 0x58, 0xcd];
+const ERC1155_INTERFACE_ID: [u8; 4] = [0xd9, 0xb6, 0x7a, 0x26];
+
+/// keccak256("Transfer(address,address,uint256)")
+const ERC20_TRANSFER_TOPIC: H256 = H256([
+    0xdd, 0xf2, 0x52, 0xad, 0x1b, 0xe2, 0xc8, 0x9b,
+    0x69, 0xc2, 0xb0, 0x68, 0xfc, 0x37, 0x8d, 0xaa,
+    0x95, 0x2b, 0xa7, 0xf1, 0x63, 0xc4, 0xa1, 0x16,
+    0x28, 0xf5, 0x5a, 0x4d, 0xf5, 0x23, 0xb3, 0xef,
+]);
+
+/// EIP-1967 implementation slot: keccak256("eip1967.proxy.implementation") - 1
+pub const EIP1967_IMPLEMENTATION_SLOT: H256 = H256([
+    0x36, 0x08, 0x94, 0xa1, 0x3b, 0xa1, 0xa3, 0x21,
+    0x06, 0x67, 0xc8, 0x28, 0x49, 0x2d, 0xb9, 0x8d,
+    0xca, 0x3e, 0x20, 0x76, 0xcc, 0x37, 0x35, 0xa9,
+    0x20, 0xa3, 0xca, 0x50, 0x5d, 0x38, 0x2b, 0xbc,
+]);
+
+/// EIP-1967 beacon slot: keccak256("eip1967.proxy.beacon") - 1
+pub const EIP1967_BEACON_SLOT: H256 = H256([
+    0xa3, 0xf0, 0xad, 0x74, 0xe5, 0x42, 0x3a, 0xeb,
+    0xfd, 0x80, 0xd3, 0xef, 0x43, 0x46, 0x57, 0x83,
+    0x35, 0xa9, 0xa7, 0x2a, 0xea, 0xee, 0x59, 0xff,
+    0x6c, 0xb3, 0x58, 0x2b, 0x35, 0x13, 0x3d, 0x50,
+]);
+
+/// EIP-1967 admin slot: keccak256("eip1967.proxy.admin") - 1
+pub const EIP1967_ADMIN_SLOT: H256 = H256([
+    0xb5, 0x31, 0x27, 0x68, 0x4a, 0x56, 0x8b, 0x31,
+    0x73, 0xae, 0x13, 0xb9, 0xf8, 0xa6, 0x01, 0x6e,
+    0x24, 0x3e, 0x63, 0xb6, 0xe8, 0xee, 0x11, 0x78,
+    0xd6, 0xa7, 0x17, 0x85, 0x0b, 0x5d, 0x61, 0x03,
+]);
+
+/// Uniswap V3 fee tiers tried in order when quoting
+const V3_FEE_TIERS: [u32; 3] = [3000, 500, 10000];
+
+/// Mainnet USDC, used to price ETH on-chain when the price API is down
+const USDC_ADDRESS: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+
+/// Stand-in key used when no PRIVATE_KEY is configured; signing tools refuse to use it
+pub const PLACEHOLDER_PRIVATE_KEY: &str = "0x0000000000000000000000000000000000000000000000000000000000000001";
+
+/// Padding applied to `eth_estimateGas` results when setting a gas limit
+pub const DEFAULT_GAS_LIMIT_MULTIPLIER: Decimal = dec!(1.2);
+
+/// `User-Agent` sent on outbound HTTP requests unless `HTTP_USER_AGENT` is set
+const DEFAULT_USER_AGENT: &str = concat!("web3-wallet-mcp/", env!("CARGO_PKG_VERSION"));
+
+/// Receipt polling interval in offline mode, in milliseconds
+const OFFLINE_POLL_INTERVAL_MS: u64 = 50;
+
+const WEI_PER_GWEI: Decimal = dec!(1_000_000_000);
+
+/// Gas price in gwei above which get_gas_price reports `is_high`, unless `GAS_WARN_GWEI` is set
+pub const DEFAULT_GAS_WARN_GWEI: Decimal = dec!(100);
+const WEI_PER_ETH: Decimal = dec!(1_000_000_000_000_000_000);
+
+/// Most fractional digits a Decimal can hold
+const MAX_DECIMAL_SCALE: u32 = 28;
+
+/// Largest `n` for which `10^n` fits in a U256
+const MAX_U256_EXP10: u32 = 77;
+
+/// Transaction ready to sign, with the fee values that were chosen
+struct PreparedTransaction {
+    tx: TypedTransaction,
+    chain_id: u64,
+    max_fee: Option<U256>,
+    max_priority_fee: Option<U256>,
+    gas_price: Option<U256>,
+    gas_estimate: U256,
+    gas_limit: U256,
+}
+
+/// Response of `alchemy_getTokenBalances`
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AlchemyTokenBalances {
+    token_balances: Vec<AlchemyTokenBalance>,
+    page_key: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AlchemyTokenBalance {
+    contract_address: Address,
+    token_balance: Option<String>,
+    error: Option<String>,
+}
+
+/// Response of geth's `txpool_contentFrom`, keyed by nonce
+#[derive(Debug, Serialize, Deserialize)]
+struct TxpoolContentFrom {
+    #[serde(default)]
+    pending: BTreeMap<String, Transaction>,
+    #[serde(default)]
+    queued: BTreeMap<String, Transaction>,
+}
+
+/// Token name, symbol and decimals, with where they came from
+#[derive(Debug, Clone)]
+pub(crate) struct TokenMetadata {
+    pub(crate) name: String,
+    pub(crate) symbol: String,
+    pub(crate) decimals: u8,
+    pub(crate) source: MetadataSource,
+    pub(crate) logo: Option<String>,
+}
+
+/// Response of `alchemy_getTokenMetadata`; fields are null for non-ERC20 contracts
+#[derive(Debug, Serialize, Deserialize)]
+struct AlchemyTokenMetadata {
+    name: Option<String>,
+    symbol: Option<String>,
+    decimals: Option<u8>,
+    logo: Option<String>,
+}
+
 pub struct EthereumClient {
-    provider: Provider<Http>,
+    provider: Provider<FailoverTransport>,
+    wallet: LocalWallet,
+    wallet_configured: bool,
+    network: Network,
+    gas_multiplier: Decimal,
+    /// Gas price above which get_gas_price reports `is_high`, from `GAS_WARN_GWEI`
+    gas_warn_gwei: Decimal,
+    price_circuit: Mutex<CircuitBreaker>,
+    /// Use the built-in token list before querying the contract
+    prefer_known_list: bool,
+    /// Token (name, symbol, decimals) read from contracts; metadata never changes
+    token_metadata: Mutex<HashMap<Address, TokenMetadata>>,
+    /// Token standards detected via ERC-165
+    token_standards: Mutex<HashMap<Address, TokenStandard>>,
+    /// Widest block range accepted by get_logs, from `GET_LOGS_MAX_RANGE`
+    logs_max_range: u64,
+    /// Block span of each `eth_getLogs` sub-request, from `GET_LOGS_CHUNK_SIZE`
+    logs_chunk_size: u64,
+    /// Refuse to broadcast on chain ID 1, from `DISABLE_MAINNET_WRITES`
+    disable_mainnet_writes: bool,
+    /// Notified with the receipt status of broadcast transactions, from `CONFIRMATION_WEBHOOK_URL`
+    confirmation_webhook_url: Option<String>,
+    /// Tokens `get_balance` reports when none are requested; empty uses the built-in list
+    default_tokens: Vec<ParsedAddress>,
+    /// Client shared by the RPC transport and the Alchemy HTTP calls, so connections are pooled
+    http_client: reqwest::Client,
+    /// Quotes, swap gas estimates and prices that bypass the provider
+    rpc_transport: Arc<dyn RpcTransport>,
+    /// Outbound RPC round trips since startup: provider calls and direct Alchemy requests
+    rpc_calls: Arc<AtomicU64>,
+    /// Every call is answered by the canned mock chain, from `OFFLINE_MODE`
+    offline_mode: bool,
+    /// Aggregators from `QUOTE_SOURCES` with their API keys; sources without a key are left out
+    aggregators: Vec<(SwapSource, String)>,
 }
 
 impl EthereumClient {
-    pub async fn new(rpc_url: String, _private_key: String) -> Result<Self, MCPError> {
+    /// Create a client for `rpc_url`, with every other setting from the environment
+    pub async fn new(rpc_url: String, private_key: String) -> Result<Self, MCPError> {
+        let config = Config::resolve(FileConfig { rpc_url: Some(rpc_url), ..FileConfig::default() })?;
+        Self::with_config(&config, private_key).await
+    }
+
+    /// Like `new`, for `network` instead of the configured one
+    pub async fn with_network(rpc_url: String, private_key: String, network: Network) -> Result<Self, MCPError> {
+        let config = Config::resolve(FileConfig { rpc_url: Some(rpc_url), ..FileConfig::default() })?;
+        Self::with_config(&Config { network, ..config }, private_key).await
+    }
+
+    /// Create a client from loaded configuration; `config.rpc_urls` replaces `config.rpc_url` when set
+    pub async fn with_config(config: &Config, private_key: String) -> Result<Self, MCPError> {
+        let network = config.network;
+        let rpc_urls = if config.rpc_urls.is_empty() {
+            vec![config.rpc_url.clone()]
+        } else {
+            config.rpc_urls.clone()
+        };
+
         // Validate configuration
-        InputValidator::validate_config(&rpc_url, &_private_key)?;
+        InputValidator::validate_private_key(&private_key)?;
+        for url in &rpc_urls {
+            InputValidator::validate_rpc_url(url)?;
+        }
         
-        let provider = Provider::<Http>::try_from(rpc_url)
-            .map_err(|e| MCPError::EthereumRpc(e.to_string()))?;
+        let http_client = Self::build_http_client(config.timeout_secs)?;
+        let transport = if config.offline_mode {
+            warn!(network = %network, "OFFLINE_MODE enabled: serving canned mock data, no RPC or price API calls are made");
+            FailoverTransport::offline(network.chain_id())
+        } else {
+            let reconnect = WsReconnect {
+                max_reconnects: config.ws_max_reconnects,
+                base_delay: std::time::Duration::from_millis(config.ws_reconnect_delay_ms),
+            };
+            FailoverTransport::connect(&rpc_urls, http_client.clone(), reconnect).await?
+        };
+        if transport.len() > 1 {
+            info!(endpoint_count = transport.len(), "RPC failover enabled");
+        }
+        let rpc_calls = transport.request_counter();
+        let mut provider = Provider::new(transport);
+        if config.offline_mode {
+            // Mock receipts exist immediately; skip the default 7s polling delay
+            provider.set_interval(std::time::Duration::from_millis(OFFLINE_POLL_INTERVAL_MS));
+        }
+        let rpc_transport: Arc<dyn RpcTransport> = if config.offline_mode {
+            Arc::new(OfflineTransport)
+        } else {
+            Arc::new(AlchemyTransport::new(http_client.clone(), rpc_calls.clone()))
+        };
+
+        let wallet = private_key.parse::<LocalWallet>()
+            .map_err(|e| MCPError::InvalidPrivateKey(e.to_string()))?;
+        let wallet_configured = private_key.trim_start_matches("0x") != PLACEHOLDER_PRIVATE_KEY.trim_start_matches("0x");
+
+        Self::validate_chain_id(&provider, network).await?;
 
-        info!("Ethereum client initialized successfully");
-        Ok(Self { provider })
+        if wallet_configured {
+            info!(
+                network = %network,
+                wallet_address = %format!("0x{:x}", wallet.address()),
+                "Ethereum client initialized successfully (signing enabled)"
+            );
+        } else {
+            info!(network = %network, "Ethereum client initialized in read-only mode (no PRIVATE_KEY)");
+        }
+        let price_circuit = Mutex::new(CircuitBreaker::new(
+            "alchemy_price",
+            config.price_circuit_failure_threshold,
+            std::time::Duration::from_secs(config.price_circuit_cooldown_secs),
+        ));
+        if config.disable_mainnet_writes {
+            info!("Mainnet writes disabled (DISABLE_MAINNET_WRITES=true)");
+        }
+        let aggregators = config.quote_sources.iter()
+            .filter_map(|source| {
+                let key_env = source.api_key_env()?;
+                match std::env::var(key_env) {
+                    Ok(key) if !key.trim().is_empty() => Some((*source, key.trim().to_string())),
+                    _ => {
+                        warn!(source = ?source, key_env, "Quote source omitted: API key not configured");
+                        None
+                    },
+                }
+            })
+            .collect::<Vec<_>>();
+        if !aggregators.is_empty() {
+            info!(sources = ?aggregators.iter().map(|(source, _)| *source).collect::<Vec<_>>(), "Aggregator swap quotes enabled");
+        }
+        Ok(Self {
+            provider,
+            wallet,
+            wallet_configured,
+            network,
+            gas_multiplier: config.gas_limit_multiplier,
+            gas_warn_gwei: config.gas_warn_gwei,
+            price_circuit,
+            prefer_known_list: config.prefer_known_list,
+            token_metadata: Mutex::new(HashMap::new()),
+            token_standards: Mutex::new(HashMap::new()),
+            logs_max_range: config.logs_max_range,
+            logs_chunk_size: config.logs_chunk_size,
+            disable_mainnet_writes: config.disable_mainnet_writes,
+            confirmation_webhook_url: config.confirmation_webhook_url.clone(),
+            default_tokens: config.default_tokens.clone(),
+            rpc_transport,
+            http_client,
+            rpc_calls,
+            offline_mode: config.offline_mode,
+            aggregators,
+        })
+    }
+
+    /// Replace the transport used for direct quote, gas and price calls, e.g. with a mock in tests
+    pub fn with_rpc_transport(mut self, transport: Arc<dyn RpcTransport>) -> Self {
+        self.rpc_transport = transport;
+        self
+    }
+
+    /// Poll for receipts every `interval` instead of the provider default (7s), e.g. against a local node
+    pub fn with_poll_interval(mut self, interval: std::time::Duration) -> Self {
+        self.provider.set_interval(interval);
+        self
+    }
+
+    /// Replace the aggregators (and API keys) swap simulations are compared against
+    pub fn with_aggregators(mut self, aggregators: Vec<(SwapSource, String)>) -> Self {
+        self.aggregators = aggregators;
+        self
+    }
+
+    /// Build the outbound HTTP client with `HTTP_USER_AGENT` (default `web3-wallet-mcp/<version>`),
+    /// the extra headers in `HTTP_HEADERS` and a `timeout_secs` request timeout
+    fn build_http_client(timeout_secs: u64) -> Result<reqwest::Client, MCPError> {
+        let user_agent = std::env::var("HTTP_USER_AGENT")
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_USER_AGENT.to_string());
+        let headers = match std::env::var("HTTP_HEADERS") {
+            Ok(value) => parse_http_headers(&value)?,
+            Err(_) => reqwest::header::HeaderMap::new(),
+        };
+        debug!(user_agent = %user_agent, header_count = headers.len(), timeout_secs = timeout_secs, "Building HTTP client");
+
+        reqwest::Client::builder()
+            .user_agent(user_agent)
+            .default_headers(headers)
+            .timeout(std::time::Duration::from_secs(timeout_secs))
+            .build()
+            .map_err(|e| MCPError::ConfigurationError(format!("Failed to build HTTP client: {}", e)))
+    }
+
+    /// Outbound RPC round trips made since startup, by all requests together; a single
+    /// request's calls are counted with `crate::logging::count_rpc_calls`
+    pub fn rpc_call_count(&self) -> u64 {
+        self.rpc_calls.load(Ordering::Relaxed)
+    }
+
+    /// Whether responses come from the canned mock chain (`OFFLINE_MODE`)
+    pub fn offline_mode(&self) -> bool {
+        self.offline_mode
+    }
+
+    /// Whether a real `PRIVATE_KEY` was configured rather than the placeholder
+    pub fn wallet_configured(&self) -> bool {
+        self.wallet_configured
+    }
+
+    /// `Signing` with a configured key, `ReadOnly` with the placeholder
+    pub fn wallet_mode(&self) -> WalletMode {
+        if self.wallet_configured { WalletMode::Signing } else { WalletMode::ReadOnly }
+    }
+
+    /// Configured network
+    pub fn network(&self) -> Network {
+        self.network
+    }
+
+    /// Address of the server wallet
+    pub fn wallet_address(&self) -> Address {
+        self.wallet.address()
+    }
+
+    /// Address of the server wallet, or WalletNotInitialized in read-only mode where
+    /// the placeholder key's address would give misleading results
+    pub fn require_wallet(&self, operation: &str) -> Result<Address, MCPError> {
+        if !self.wallet_configured {
+            return Err(MCPError::WalletNotInitialized(
+                format!("{} requires PRIVATE_KEY; the server is running in read-only mode", operation)
+            ));
+        }
+        Ok(self.wallet.address())
+    }
+
+    /// Ensure the RPC endpoint serves the configured network.
+    /// Set `ALLOW_CHAIN_MISMATCH=true` to only warn, e.g. for custom networks.
+    async fn validate_chain_id(provider: &Provider<FailoverTransport>, network: Network) -> Result<(), MCPError> {
+        let chain_id = match provider.get_chainid().await {
+            Ok(id) => id.as_u64(),
+            Err(e) => {
+                // Startup should not depend on RPC availability
+                warn!(network = %network, error = %e, "Could not fetch chain ID, skipping network validation");
+                return Ok(());
+            }
+        };
+
+        if chain_id == network.chain_id() {
+            return Ok(());
+        }
+
+        let allow_mismatch = std::env::var("ALLOW_CHAIN_MISMATCH")
+            .map(|v| v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+        if allow_mismatch {
+            warn!(
+                network = %network,
+                expected_chain_id = network.chain_id(),
+                actual_chain_id = chain_id,
+                "RPC chain ID does not match configured network, continuing (ALLOW_CHAIN_MISMATCH=true)"
+            );
+            return Ok(());
+        }
+
+        Err(MCPError::ConfigurationError(format!(
+            "RPC endpoint reports chain ID {} but network '{}' expects {}",
+            chain_id, network, network.chain_id()
+        )))
+    }
+
+    /// USD price from the requested source; `Auto` tries Alchemy first and falls back to a
+    /// degraded on-chain estimate, failing with both errors when neither source answers
+    pub async fn get_token_price(&self, token: ParsedAddress, selection: PriceSourceSelection) -> Result<PriceInfo, MCPError> {
+        match selection {
+            PriceSourceSelection::Alchemy => self.get_alchemy_token_price(token).await,
+            PriceSourceSelection::Onchain => self.get_onchain_token_price(token).await,
+            PriceSourceSelection::Auto => {
+                let alchemy_error = match self.get_alchemy_token_price(token).await {
+                    Ok(price) => return Ok(price),
+                    Err(e) => e,
+                };
+                warn!(token_address = %token, error = %alchemy_error, "Alchemy price unavailable, falling back to on-chain price");
+                match self.get_onchain_token_price(token).await {
+                    Ok(price) => Ok(PriceInfo { degraded: true, ..price }),
+                    Err(onchain_error) => Err(MCPError::PriceFetchFailed(format!(
+                        "Alchemy: {}; on-chain: {}", alchemy_error, onchain_error
+                    ))),
+                }
+            },
+        }
     }
 
-    pub async fn get_token_price(&self, token_address: &str) -> Result<PriceInfo, MCPError> {
+    async fn get_alchemy_token_price(&self, token: ParsedAddress) -> Result<PriceInfo, MCPError> {
         info!(
-            token_address = %token_address,
+            token_address = %token,
             "Fetching token price from Alchemy API"
         );
-        let (_, symbol, _) = self.get_known_token_info(token_address);
-        let price_usd = self.get_price_from_alchemy(token_address).await?;
-        info!(token_address = %token_address, symbol = %symbol, price_usd = %price_usd, "Token price fetched");
-        Ok(PriceInfo { token_address: token_address.to_string(), symbol, price_usd })
+        let token_address = format!("0x{:x}", token.address());
+        let (_, symbol, _) = self.get_known_token_info(&token_address);
+        let price_usd = self.get_price_from_alchemy(&token_address).await?;
+        info!(token_address = %token, symbol = %symbol, price_usd = %price_usd, "Token price fetched");
+        Ok(PriceInfo { token_address: token.checksummed(), symbol, price_usd, source: PriceSource::Alchemy, degraded: false })
+    }
+
+    /// USD price from a Uniswap V3 quote; fails when the ETH price needed for conversion is unavailable
+    async fn get_onchain_token_price(&self, token: ParsedAddress) -> Result<PriceInfo, MCPError> {
+        let OnchainPriceInfo { token_address, symbol, price_usd, .. } = self.get_onchain_price(token).await?;
+        let price_usd = price_usd.ok_or_else(|| MCPError::PriceFetchFailed(
+            format!("On-chain price for {} could not be converted to USD", token)
+        ))?;
+        Ok(PriceInfo { token_address, symbol, price_usd, source: PriceSource::Onchain, degraded: false })
+    }
+
+    /// USD price of the native currency, priced via the network's wrapped token with the
+    /// usual Alchemy-then-on-chain fallback
+    pub async fn get_eth_price(&self) -> Result<PriceInfo, MCPError> {
+        let wrapped_native = ParsedAddress::parse(self.network.weth_address())?;
+        info!(wrapped_native = %wrapped_native, "Fetching native currency price");
+
+        let price = self.get_token_price(wrapped_native, PriceSourceSelection::Auto).await?;
+        info!(price_usd = %price.price_usd, source = ?price.source, "Native currency price fetched");
+        Ok(PriceInfo { symbol: self.network.native_symbol().to_string(), ..price })
+    }
+
+    /// USD price of the native currency, as reported by `get_eth_price`
+    pub async fn get_native_price_usd(&self) -> Result<Decimal, MCPError> {
+        Ok(self.get_eth_price().await?.price_usd)
     }
 
     async fn get_price_from_alchemy(&self, token_address: &str) -> Result<Decimal, MCPError> {
-        let client = reqwest::Client::new();
-        let url = "https://api.g.alchemy.com/prices/v1/JZUYcRpkXq25weYd16Fuu/tokens/by-address";
-        let request_body = serde_json::json!({
-            "addresses": [ { "network": "eth-mainnet", "address": token_address } ]
-        });
-        let response = client
-            .post(url)
-            .header("Content-Type", "application/json")
-            .json(&request_body)
-            .timeout(std::time::Duration::from_secs(10))
-            .send()
-            .await
-            .map_err(|e| MCPError::NetworkError(format!("Failed to call Alchemy API: {}", e)))?;
-        if !response.status().is_success() {
-            return Err(MCPError::PriceFetchFailed(format!("Alchemy API returned status: {}", response.status())));
+        let prices = self.get_prices_from_alchemy(&[token_address]).await?;
+        prices.get(&token_address.to_lowercase())
+            .copied()
+            .ok_or_else(|| MCPError::PriceFetchFailed("No price data found in Alchemy API response".to_string()))
+    }
+
+    /// Fetch USD prices for several tokens in one request, keyed by lowercase address.
+    /// Tokens without a price are absent from the result.
+    async fn get_prices_from_alchemy(&self, token_addresses: &[&str]) -> Result<HashMap<String, Decimal>, MCPError> {
+        let network_slug = self.network.alchemy_price_slug()
+            .ok_or_else(|| MCPError::PriceFetchFailed(
+                format!("Alchemy Price API does not support network '{}'", self.network)
+            ))?;
+
+        // Stop calling out while the price API is known to be failing
+        if !self.price_circuit.lock().unwrap().allow_request() {
+            return Err(MCPError::ApiRateLimitExceeded(
+                "Price API circuit is open after repeated failures, try again later".to_string()
+            ));
         }
-        let json: serde_json::Value = response
-            .json()
-            .await
-            .map_err(|e| MCPError::PriceFetchFailed(format!("Failed to parse Alchemy API response: {}", e)))?;
-        if let Some(data_array) = json.get("data").and_then(|d| d.as_array()) {
-            if let Some(token_data) = data_array.first() {
-                if let Some(prices) = token_data.get("prices").and_then(|p| p.as_array()) {
-                    if let Some(price_info) = prices.first() {
-                        if let Some(price_usd_str) = price_info.get("value").and_then(|v| v.as_str()) {
-                            let price_usd_decimal = price_usd_str
-                                .parse::<Decimal>()
-                                .map_err(|_| MCPError::PriceFetchFailed("Failed to parse price value".to_string()))?;
-                            return Ok(price_usd_decimal);
-                        }
-                    }
-                }
+
+        let result = self.rpc_transport.fetch_prices(network_slug, token_addresses).await;
+
+        let mut circuit = self.price_circuit.lock().unwrap();
+        match &result {
+            Ok(_) => circuit.record_success(),
+            Err(MCPError::PriceFetchFailed(_)) | Err(MCPError::ApiRateLimitExceeded(_)) => circuit.record_failure(),
+            Err(_) => {},
+        }
+
+        result
+    }
+
+    /// Attach USD values to each balance and compute the portfolio total.
+    /// The native currency is priced via the network's wrapped token; unpriced tokens get
+    /// no value and are excluded from the total.
+    async fn apply_usd_values(&self, balance_info: &mut BalanceInfo) -> Result<(), MCPError> {
+        let wrapped_native = self.network.weth_address();
+        let mut addresses: Vec<&str> = balance_info.token_balances.keys().map(|a| a.as_str()).collect();
+        addresses.push(wrapped_native);
+        let prices = self.get_prices_from_alchemy(&addresses).await?;
+
+        let mut total = Decimal::ZERO;
+
+        balance_info.eth_usd_value = prices.get(&wrapped_native.to_lowercase())
+            .map(|price| balance_info.native_balance * price);
+        if let Some(value) = balance_info.eth_usd_value {
+            total += value;
+        }
+
+        for (address, token) in balance_info.token_balances.iter_mut() {
+            token.usd_value = prices.get(&address.to_lowercase())
+                .and_then(|price| Self::token_usd_value(token, *price));
+            if let Some(value) = token.usd_value {
+                total = total.saturating_add(value);
             }
         }
-        Err(MCPError::PriceFetchFailed("No price data found in Alchemy API response".to_string()))
+
+        balance_info.total_usd_value = Some(total);
+        Ok(())
     }
-    
 
-    #[instrument(skip(self), fields(address = %address, token_address = %token_address.unwrap_or("all")))]
-    pub async fn get_balance(&self, address: &str, token_address: Option<&str>) -> Result<BalanceInfo, MCPError> {
-        let addr = address.parse::<Address>()
-            .map_err(|_| MCPError::InvalidAddress(address.to_string()))?;
+    /// Get ETH and token balances. With no `token_addresses`, common tokens are queried on a best-effort basis.
+    #[instrument(skip(self, token_addresses), fields(address = %address, token_count = token_addresses.len()))]
+    pub async fn get_balance(&self, address: ParsedAddress, token_addresses: &[ParsedAddress], block: Option<BlockId>, include_usd_value: bool, decimals_display: u32) -> Result<BalanceInfo, MCPError> {
+        let addr = address.address();
 
         // Get ETH balance
         debug!(address = %address, "Fetching ETH balance");
-        let eth_balance_wei = self.provider.get_balance(addr, None).await?;
+        let eth_balance_wei = self.provider.get_balance(addr, block).await?;
         let eth_balance = Decimal::from(eth_balance_wei.as_u128()) / dec!(1_000_000_000_000_000_000);
         
         info!(
@@ -97,24 +611,45 @@ impl EthereumClient {
         );
 
         let mut token_balances = HashMap::new();
+        let mut scanned_tokens = Vec::new();
 
-        if let Some(token_addr) = token_address {
-            // Get specific token balance
-            info!(address = %address, token_address = %token_addr, "Fetching specific token balance");
-            let token_balance = self.get_token_balance(addr, token_addr).await?;
-            token_balances.insert(token_addr.to_string(), token_balance);
+        if !token_addresses.is_empty() {
+            // Get specific token balances
+            for token in token_addresses {
+                info!(address = %address, token_address = %token, "Fetching specific token balance");
+                let token_balance = self.get_token_balance(addr, token.address(), block, decimals_display).await?;
+                token_balances.insert(token.checksummed(), token_balance);
+                scanned_tokens.push(token.checksummed());
+            }
+        } else if !self.default_tokens.is_empty() {
+            // Configured default tokens
+            info!(address = %address, token_count = self.default_tokens.len(), "Fetching default token balances");
+            for token in &self.default_tokens {
+                match self.get_token_balance(addr, token.address(), block, decimals_display).await {
+                    Ok(balance) => {
+                        token_balances.insert(token.checksummed(), balance);
+                        scanned_tokens.push(token.checksummed());
+                    },
+                    Err(e) => warn!(token_address = %token, error = %e, "Default token balance failed"),
+                }
+            }
         } else {
             // Get common token balances (USDC, USDT, WETH, etc.)
             info!(address = %address, "Fetching common token balances");
             let common_tokens = vec![
-                ("0xA0b86a33E6441b8C4C8C0C4C8C0C4C8C0C4C8C0C", "USDC", "USD Coin", 6),
+                ("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", "USDC", "USD Coin", 6),
                 ("0xdAC17F958D2ee523a2206206994597C13D831ec7", "USDT", "Tether USD", 6),
                 ("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2", "WETH", "Wrapped Ether", 18),
             ];
 
             for (contract_addr, _symbol, _name, _decimals) in common_tokens {
-                if let Ok(balance) = self.get_token_balance(addr, contract_addr).await {
-                    token_balances.insert(contract_addr.to_string(), balance);
+                let Ok(token_address) = contract_addr.parse::<Address>() else { continue };
+                match self.get_token_balance(addr, token_address, block, decimals_display).await {
+                    Ok(balance) => {
+                        token_balances.insert(contract_addr.to_string(), balance);
+                        scanned_tokens.push(contract_addr.to_string());
+                    },
+                    Err(e) => warn!(token_address = %contract_addr, error = %e, "Common token balance failed"),
                 }
             }
         }
@@ -123,33 +658,1337 @@ impl EthereumClient {
             address = %address,
             eth_balance = %eth_balance,
             token_count = token_balances.len(),
+            scanned_count = scanned_tokens.len(),
             "Balance information retrieved successfully"
         );
 
-        Ok(BalanceInfo {
-            address: address.to_string(),
+        let mut balance_info = BalanceInfo {
+            address: Self::checksum(&addr),
+            native_symbol: self.network.native_symbol().to_string(),
+            native_balance: eth_balance,
+            eth_usd_value: None,
+            token_balances,
+            scanned_tokens,
+            total_usd_value: None,
+        };
+
+        if include_usd_value {
+            self.apply_usd_values(&mut balance_info).await?;
+            info!(
+                address = %address,
+                total_usd_value = ?balance_info.total_usd_value,
+                "Portfolio USD value calculated"
+            );
+        }
+
+        Ok(balance_info)
+    }
+
+    /// Balances enriched with prices and USD values in one call. Listed tokens are fetched
+    /// concurrently with ETH and prices; with no `token_addresses` holdings are discovered
+    /// as in `get_all_token_balances` first. Failures are reported per token.
+    #[instrument(skip(self, token_addresses), fields(address = %address, token_count = token_addresses.len()))]
+    pub async fn get_portfolio(&self, address: ParsedAddress, token_addresses: &[ParsedAddress], decimals_display: u32) -> Result<PortfolioInfo, MCPError> {
+        let addr = address.address();
+        let weth = self.network.weth_address().to_lowercase();
+
+        let (eth_balance, token_results, prices) = if token_addresses.is_empty() {
+            let holdings = self.get_all_token_balances(address, false, decimals_display).await?;
+            let mut price_addresses: Vec<String> = holdings.token_balances.keys().map(|a| a.to_lowercase()).collect();
+            price_addresses.push(weth.clone());
+            let price_refs: Vec<&str> = price_addresses.iter().map(|a| a.as_str()).collect();
+            let prices = self.get_prices_from_alchemy(&price_refs).await;
+            let token_results: Vec<_> = holdings.token_balances.into_iter()
+                .map(|(token, balance)| (token, Ok(balance)))
+                .collect();
+            (holdings.native_balance, token_results, prices)
+        } else {
+            let mut price_addresses: Vec<String> = token_addresses.iter().map(|t| format!("0x{:x}", t.address())).collect();
+            price_addresses.push(weth.clone());
+            let price_refs: Vec<&str> = price_addresses.iter().map(|a| a.as_str()).collect();
+
+            let balances = stream::iter(token_addresses.iter().copied())
+                .map(|token| async move {
+                    (token.checksummed(), self.get_token_balance(addr, token.address(), None, decimals_display).await)
+                })
+                .buffered(MAX_CONCURRENT_TOKEN_BALANCE_QUERIES)
+                .collect::<Vec<_>>();
+            let (eth_balance_wei, token_results, prices) = tokio::join!(
+                self.provider.get_balance(addr, None),
+                balances,
+                self.get_prices_from_alchemy(&price_refs),
+            );
+            (Decimal::from(eth_balance_wei?.as_u128()) / WEI_PER_ETH, token_results, prices)
+        };
+
+        let (prices, price_error) = match prices {
+            Ok(prices) => (prices, None),
+            Err(e) => {
+                warn!(error = %e, "Prices unavailable, returning portfolio balances only");
+                (HashMap::new(), Some(e.to_string()))
+            }
+        };
+
+        let eth_price_usd = prices.get(&weth).copied();
+        let eth_usd_value = eth_price_usd.map(|price| eth_balance * price);
+        let mut total_usd_value = eth_usd_value.unwrap_or_default();
+        let mut tokens = Vec::new();
+        let mut errors = HashMap::new();
+
+        for (token, result) in token_results {
+            match result {
+                Ok(mut balance) => {
+                    let price_usd = prices.get(&token.to_lowercase()).copied();
+                    balance.usd_value = price_usd.and_then(|price| Self::token_usd_value(&balance, price));
+                    total_usd_value = total_usd_value.saturating_add(balance.usd_value.unwrap_or_default());
+                    tokens.push(PortfolioToken { balance, price_usd });
+                },
+                Err(e) => {
+                    warn!(token_address = %token, error = %e, "Token balance failed, reporting per token");
+                    errors.insert(token, e.to_string());
+                },
+            }
+        }
+        tokens.sort_by_key(|token| std::cmp::Reverse(token.balance.usd_value));
+
+        info!(
+            address = %address,
+            token_count = tokens.len(),
+            error_count = errors.len(),
+            total_usd_value = %total_usd_value,
+            "Portfolio assembled"
+        );
+
+        Ok(PortfolioInfo {
+            address: Self::checksum(&addr),
             eth_balance,
+            eth_price_usd,
+            eth_usd_value,
+            tokens,
+            total_usd_value,
+            errors,
+            price_error,
+        })
+    }
+
+    /// Latest block number minus `confirmations`, floored at genesis
+    pub async fn confirmed_block_number(&self, confirmations: u64) -> Result<u64, MCPError> {
+        let latest = self.provider.get_block_number().await?.as_u64();
+        Ok(latest.saturating_sub(confirmations))
+    }
+
+    /// Number of the block `block` refers to; None means latest
+    pub async fn resolve_block_number(&self, block: Option<BlockNumber>) -> Result<u64, MCPError> {
+        match block {
+            Some(BlockNumber::Number(number)) => Ok(number.as_u64()),
+            None | Some(BlockNumber::Latest) => Ok(self.provider.get_block_number().await?.as_u64()),
+            Some(tag) => self.provider.get_block(tag).await?
+                .and_then(|block| block.number)
+                .map(|number| number.as_u64())
+                .ok_or_else(|| MCPError::ValidationError(format!("Block {:?} not found", tag))),
+        }
+    }
+
+    /// Native and token balances at `from_block` and `to_block` and their difference. Tags are
+    /// resolved to block numbers first so both reads are pinned; tokens default as in `get_balance`.
+    #[instrument(skip(self, token_addresses), fields(address = %address, token_count = token_addresses.len()))]
+    pub async fn get_balance_changes(&self, address: ParsedAddress, token_addresses: &[ParsedAddress], from_block: Option<BlockNumber>, to_block: Option<BlockNumber>) -> Result<BalanceChanges, MCPError> {
+        let (from_block, to_block) = tokio::try_join!(
+            self.resolve_block_number(from_block),
+            self.resolve_block_number(to_block),
+        )?;
+        if from_block > to_block {
+            return Err(MCPError::ValidationError(format!(
+                "from_block ({}) must not be after to_block ({})", from_block, to_block
+            )));
+        }
+
+        let at = |number: u64| Some(BlockId::Number(BlockNumber::Number(number.into())));
+        let (before, after) = tokio::try_join!(
+            self.get_balance(address, token_addresses, at(from_block), false, DEFAULT_DECIMALS_DISPLAY),
+            self.get_balance(address, token_addresses, at(to_block), false, DEFAULT_DECIMALS_DISPLAY),
+        )?;
+
+        let mut token_changes = HashMap::new();
+        for token in before.scanned_tokens.iter().filter(|token| after.scanned_tokens.contains(token)) {
+            let (balance_before, balance_after) = (before.token_balances.get(token), after.token_balances.get(token));
+            let Some(metadata) = balance_after.or(balance_before) else { continue };
+            let whole = |balance: Option<&TokenBalance>| balance
+                .map(|balance| Self::scale_token_amount(balance.balance, balance.decimals).normalize())
+                .unwrap_or(Decimal::ZERO);
+            let (balance_before, balance_after) = (whole(balance_before), whole(balance_after));
+            token_changes.insert(token.clone(), TokenBalanceChange {
+                symbol: metadata.symbol.clone(),
+                decimals: metadata.decimals,
+                balance_before,
+                balance_after,
+                change: (balance_after - balance_before).normalize(),
+            });
+        }
+
+        info!(from_block, to_block, token_count = token_changes.len(), "Balance changes computed");
+
+        Ok(BalanceChanges {
+            address: before.address,
+            native_symbol: before.native_symbol,
+            from_block,
+            to_block,
+            native_before: before.native_balance.normalize(),
+            native_after: after.native_balance.normalize(),
+            native_change: (after.native_balance - before.native_balance).normalize(),
+            token_changes,
+        })
+    }
+
+    /// Get only the ETH balance, skipping every token lookup
+    #[instrument(skip(self), fields(address = %address))]
+    pub async fn get_native_balance(&self, address: ParsedAddress, block: Option<BlockId>, include_usd_value: bool) -> Result<BalanceInfo, MCPError> {
+        let addr = address.address();
+
+        let eth_balance_wei = self.provider.get_balance(addr, block).await?;
+        let eth_balance = Decimal::from(eth_balance_wei.as_u128()) / WEI_PER_ETH;
+        info!(eth_balance = %eth_balance, "Native balance retrieved");
+
+        let mut balance_info = BalanceInfo {
+            address: Self::checksum(&addr),
+            native_symbol: self.network.native_symbol().to_string(),
+            native_balance: eth_balance,
+            eth_usd_value: None,
+            token_balances: HashMap::new(),
+            scanned_tokens: Vec::new(),
+            total_usd_value: None,
+        };
+
+        if include_usd_value {
+            self.apply_usd_values(&mut balance_info).await?;
+        }
+
+        Ok(balance_info)
+    }
+
+    /// Get ETH and every non-zero ERC20 balance via `alchemy_getTokenBalances`.
+    /// Non-Alchemy RPC endpoints fall back to the common token list used by `get_balance`.
+    #[instrument(skip(self), fields(address = %address))]
+    pub async fn get_all_token_balances(&self, address: ParsedAddress, include_usd_value: bool, decimals_display: u32) -> Result<BalanceInfo, MCPError> {
+        if !self.is_alchemy_rpc() {
+            info!(rpc_host = ?self.provider.as_ref().url().host_str(), "RPC is not Alchemy, falling back to common token balances");
+            return self.get_balance(address, &[], None, include_usd_value, decimals_display).await;
+        }
+
+        let addr = address.address();
+
+        let eth_balance_wei = self.provider.get_balance(addr, None).await?;
+        let eth_balance = Decimal::from(eth_balance_wei.as_u128()) / WEI_PER_ETH;
+
+        let mut token_balances = HashMap::new();
+        let mut scanned_tokens = Vec::new();
+        for (token_address, balance_uint) in self.fetch_alchemy_token_balances(addr).await? {
+            let metadata = self.resolve_token_metadata(token_address, None).await;
+            let token_balance = Self::build_token_balance(token_address, balance_uint, metadata, decimals_display);
+            token_balances.insert(Self::checksum(&token_address), token_balance);
+            scanned_tokens.push(Self::checksum(&token_address));
+        }
+
+        info!(
+            address = %address,
+            eth_balance = %eth_balance,
+            token_count = token_balances.len(),
+            "All token balances retrieved"
+        );
+
+        let mut balance_info = BalanceInfo {
+            address: Self::checksum(&addr),
+            native_symbol: self.network.native_symbol().to_string(),
+            native_balance: eth_balance,
+            eth_usd_value: None,
             token_balances,
+            scanned_tokens,
+            total_usd_value: None,
+        };
+
+        if include_usd_value {
+            self.apply_usd_values(&mut balance_info).await?;
+        }
+
+        Ok(balance_info)
+    }
+
+    /// Whether the configured RPC endpoint supports Alchemy's enhanced APIs
+    fn is_alchemy_rpc(&self) -> bool {
+        self.provider.as_ref().url().host_str()
+            .map(|host| host.ends_with(".alchemy.com"))
+            .unwrap_or(false)
+    }
+
+    /// Page through `alchemy_getTokenBalances`, returning non-zero ERC20 balances
+    async fn fetch_alchemy_token_balances(&self, owner: Address) -> Result<Vec<(Address, U256)>, MCPError> {
+        let mut balances = Vec::new();
+        let mut page_key: Option<String> = None;
+
+        loop {
+            let params = match &page_key {
+                Some(key) => serde_json::json!([owner, "erc20", { "pageKey": key }]),
+                None => serde_json::json!([owner, "erc20"]),
+            };
+            let page: AlchemyTokenBalances = self.provider.request("alchemy_getTokenBalances", params).await?;
+
+            for entry in page.token_balances {
+                if let Some(error) = entry.error {
+                    debug!(token_address = %format!("0x{:x}", entry.contract_address), error = %error, "Skipping token with balance error");
+                    continue;
+                }
+                let balance = entry.token_balance
+                    .as_deref()
+                    .and_then(|hex_str| U256::from_str_radix(hex_str.trim_start_matches("0x"), 16).ok())
+                    .unwrap_or_default();
+                if !balance.is_zero() {
+                    balances.push((entry.contract_address, balance));
+                }
+            }
+
+            match page.page_key {
+                Some(key) => page_key = Some(key),
+                None => break,
+            }
+        }
+
+        debug!(token_count = balances.len(), "Fetched non-zero token balances from Alchemy");
+        Ok(balances)
+    }
+
+    /// Get balances for several wallets concurrently. Failures are reported per address.
+    #[instrument(skip(self, addresses, token_addresses), fields(address_count = addresses.len()))]
+    pub async fn get_balances_for_addresses(&self, addresses: &[String], token_addresses: &[ParsedAddress], decimals_display: u32) -> MultiBalanceInfo {
+        let results: Vec<(String, Result<BalanceInfo, MCPError>)> = stream::iter(addresses.iter().cloned())
+            .map(|address| async move {
+                // Invalid addresses are reported per address rather than failing the whole request
+                let result = match ParsedAddress::parse(&address) {
+                    Ok(parsed) => self.get_balance(parsed, token_addresses, None, false, decimals_display).await,
+                    Err(e) => Err(e),
+                };
+                (address, result)
+            })
+            .buffer_unordered(MAX_CONCURRENT_BALANCE_QUERIES)
+            .collect()
+            .await;
+
+        let mut multi = MultiBalanceInfo {
+            balances: HashMap::new(),
+            errors: HashMap::new(),
+        };
+        for (address, result) in results {
+            match result {
+                Ok(balance_info) => {
+                    multi.balances.insert(address, balance_info);
+                },
+                Err(e) => {
+                    warn!(address = %address, error = %e, "Balance query failed");
+                    multi.errors.insert(address, e.to_string());
+                }
+            }
+        }
+
+        info!(
+            succeeded = multi.balances.len(),
+            failed = multi.errors.len(),
+            "Multi-address balance query completed"
+        );
+
+        multi
+    }
+
+    /// Reverse-resolve an address to its primary ENS name
+    #[instrument(skip(self), fields(address = %address))]
+    pub async fn lookup_ens_name(&self, address: ParsedAddress) -> Result<EnsLookup, MCPError> {
+        let addr = address.address();
+
+        let ens_name = match self.provider.lookup_address(addr).await {
+            Ok(name) => Some(name),
+            // No reverse record, or the record does not resolve back to this address
+            Err(ProviderError::EnsError(_)) | Err(ProviderError::EnsNotOwned(_)) => None,
+            Err(e) => return Err(e.into()),
+        };
+
+        info!(address = %address, ens_name = ?ens_name, "ENS reverse lookup completed");
+
+        Ok(EnsLookup {
+            address: Self::checksum(&addr),
+            ens_name,
+        })
+    }
+
+    /// Reverse-resolve several addresses concurrently with `lookup_ens_name`.
+    /// Failures are reported per address.
+    #[instrument(skip(self, addresses), fields(address_count = addresses.len()))]
+    pub async fn batch_lookup_ens(&self, addresses: &[String]) -> EnsBatchLookup {
+        let results: Vec<(String, Result<EnsLookup, MCPError>)> = stream::iter(addresses.iter().cloned())
+            .map(|address| async move {
+                let result = match ParsedAddress::parse(&address) {
+                    Ok(parsed) => self.lookup_ens_name(parsed).await,
+                    Err(e) => Err(e),
+                };
+                (address, result)
+            })
+            .buffer_unordered(MAX_CONCURRENT_ENS_LOOKUPS)
+            .collect()
+            .await;
+
+        let mut batch = EnsBatchLookup {
+            names: HashMap::new(),
+            errors: HashMap::new(),
+        };
+        for (address, result) in results {
+            match result {
+                Ok(lookup) => {
+                    batch.names.insert(address, lookup.ens_name);
+                },
+                Err(e) => {
+                    warn!(address = %address, error = %e, "ENS reverse lookup failed");
+                    batch.errors.insert(address, e.to_string());
+                }
+            }
+        }
+
+        info!(
+            resolved = batch.names.values().filter(|name| name.is_some()).count(),
+            failed = batch.errors.len(),
+            "Batch ENS lookup completed"
+        );
+
+        batch
+    }
+
+    /// Sign and broadcast an ETH transfer from the server wallet.
+    /// Builds an EIP-1559 transaction unless `fees.legacy` is set.
+    /// Unless `skip_simulation` is set, the transaction is first run via `eth_call` and never broadcast if it reverts.
+    #[instrument(skip(self, fees), fields(to = %to, amount = %amount, legacy = fees.legacy))]
+    pub async fn send_transaction(&self, to: ParsedAddress, amount: Decimal, fees: TransactionFeeOptions, skip_simulation: bool, allow_self_transfer: bool) -> Result<TransactionInfo, MCPError> {
+        let from_addr = self.require_wallet("send_transaction")?;
+        let to_addr = to.address();
+        // Sending to ourselves only burns gas, and is usually an agent mix-up
+        if to_addr == from_addr && !allow_self_transfer {
+            return Err(MCPError::ValidationError(format!(
+                "Refusing to send to the wallet's own address {}; set allow_self_transfer to override",
+                Self::checksum(&from_addr)
+            )));
+        }
+        let value_wei = Self::token_amount_to_raw(amount, 18, "ETH")?;
+
+        let PreparedTransaction { tx, chain_id, max_fee, max_priority_fee, gas_price, gas_estimate, gas_limit } =
+            self.prepare_transaction(from_addr, to_addr, value_wei, None, &fees, !skip_simulation).await?;
+
+        info!(
+            from = %format!("0x{:x}", from_addr),
+            to = %to,
+            value = %amount,
+            gas_limit = %gas_limit,
+            chain_id = chain_id,
+            "Broadcasting transaction"
+        );
+
+        let (tx_hash, receipt, status) = self.broadcast(tx, chain_id).await?;
+
+        Ok(TransactionInfo {
+            tx_hash: format!("0x{:x}", tx_hash),
+            from: Self::checksum(&from_addr),
+            to: Self::checksum(&to_addr),
+            value: amount,
+            transaction_type: if fees.legacy { "legacy" } else { "eip1559" }.to_string(),
+            gas_estimate: gas_estimate.as_u64(),
+            gas_limit: gas_limit.as_u64(),
+            max_fee_per_gas_gwei: max_fee.map(|v| Decimal::from(v.as_u128()) / WEI_PER_GWEI),
+            max_priority_fee_per_gas_gwei: max_priority_fee.map(|v| Decimal::from(v.as_u128()) / WEI_PER_GWEI),
+            gas_price_gwei: gas_price.map(|v| Decimal::from(v.as_u128()) / WEI_PER_GWEI),
+            status: status.to_string(),
+            block_number: receipt.as_ref().and_then(|r| r.block_number).map(|n| n.as_u64()),
+            gas_used: receipt.as_ref().and_then(|r| r.gas_used).map(|g| g.as_u64()),
+        })
+    }
+
+    /// Sign and broadcast an ERC20 transfer from the server wallet.
+    /// The transfer is simulated first and never broadcast if it would revert.
+    #[instrument(skip(self), fields(token_address = %token_address, to = %to, amount = %amount))]
+    pub async fn transfer_token(&self, token_address: ParsedAddress, to: ParsedAddress, amount: Decimal, gas_multiplier: Option<Decimal>) -> Result<TokenTransferInfo, MCPError> {
+        let from_addr = self.require_wallet("transfer_token")?;
+        let token_addr = token_address.address();
+        let to_addr = to.address();
+
+        // Scaling by guessed decimals could send the wrong amount by orders of magnitude
+        let TokenMetadata { symbol, decimals, source, .. } = self.resolve_token_metadata(token_addr, None).await;
+        if source == MetadataSource::Default {
+            return Err(MCPError::InvalidTokenContract(format!(
+                "Could not read decimals() from token {}; refusing to guess the transfer amount", token_address
+            )));
+        }
+        let amount_raw = Self::token_amount_to_raw(amount, decimals, &symbol)?;
+        let data = Self::encode_erc20_transfer(to_addr, amount_raw);
+
+        let fees = TransactionFeeOptions { gas_multiplier, ..Default::default() };
+        let PreparedTransaction { tx, chain_id, gas_limit, .. } = self
+            .prepare_transaction(from_addr, token_addr, U256::zero(), Some(data), &fees, true)
+            .await?;
+
+        info!(
+            from = %format!("0x{:x}", from_addr),
+            to = %to,
+            token_address = %token_address,
+            amount_raw = %amount_raw,
+            gas_limit = %gas_limit,
+            chain_id = chain_id,
+            "Broadcasting token transfer"
+        );
+
+        let (tx_hash, receipt, status) = self.broadcast(tx, chain_id).await?;
+
+        Ok(TokenTransferInfo {
+            tx_hash: format!("0x{:x}", tx_hash),
+            from: Self::checksum(&from_addr),
+            to: Self::checksum(&to_addr),
+            token_address: Self::checksum(&token_addr),
+            symbol,
+            amount,
+            amount_raw: amount_raw.to_string(),
+            status: status.to_string(),
+            block_number: receipt.as_ref().and_then(|r| r.block_number).map(|n| n.as_u64()),
+            gas_used: receipt.as_ref().and_then(|r| r.gas_used).map(|g| g.as_u64()),
+        })
+    }
+
+    /// Resubmit the wallet's transaction at `nonce` with fees raised by at least `bump_percent`.
+    /// Speed-up re-sends the original (read from the mempool); cancel replaces it with a
+    /// zero-value self-send, which goes through regardless of `allow_self_transfer`.
+    #[instrument(skip(self))]
+    pub async fn replace_transaction(&self, nonce: u64, bump_percent: Decimal, cancel: bool) -> Result<ReplacementTransactionInfo, MCPError> {
+        let from_addr = self.require_wallet("replace_transaction")?;
+        let chain_id = self.provider.get_chainid().await?.as_u64();
+
+        let latest_nonce = self.provider
+            .get_transaction_count(from_addr, Some(BlockNumber::Latest.into()))
+            .await?
+            .as_u64();
+        if nonce < latest_nonce {
+            return Err(MCPError::ValidationError(format!(
+                "Nonce {} is already mined; the wallet's next nonce is {}", nonce, latest_nonce
+            )));
+        }
+
+        let original = self.find_mempool_transaction(from_addr, nonce).await;
+        if original.is_none() && !cancel {
+            return Err(MCPError::TransactionFailed(format!(
+                "No pending transaction with nonce {} found in the mempool; speed-up needs txpool access, cancel does not", nonce
+            )));
+        }
+
+        if !cancel && original.as_ref().is_some_and(|tx| tx.to.is_none()) {
+            return Err(MCPError::ValidationError(
+                "Contract deployments cannot be sped up; use cancel instead".to_string()
+            ));
+        }
+
+        // Replacements must outbid the original; never go below the current network fees either
+        let (network_max_fee, network_priority_fee) = self.provider.estimate_eip1559_fees(None).await
+            .map_err(|e| MCPError::GasEstimationFailed(format!("Failed to derive fees from fee history: {}", e)))?;
+        let (max_fee, max_priority_fee) = match original.as_ref() {
+            Some(tx) => {
+                let original_max_fee = tx.max_fee_per_gas.or(tx.gas_price).unwrap_or_default();
+                let original_priority_fee = tx.max_priority_fee_per_gas.or(tx.gas_price).unwrap_or_default();
+                (
+                    Self::bump_fee(original_max_fee, bump_percent).max(network_max_fee),
+                    Self::bump_fee(original_priority_fee, bump_percent).max(network_priority_fee),
+                )
+            },
+            None => (Self::bump_fee(network_max_fee, bump_percent), Self::bump_fee(network_priority_fee, bump_percent)),
+        };
+        let max_fee = max_fee.max(max_priority_fee);
+
+        let (to_addr, value_wei, data, gas_limit) = match original.as_ref() {
+            Some(tx) if !cancel => (tx.to.unwrap_or_default(), tx.value, Some(tx.input.clone()), tx.gas),
+            _ => (from_addr, U256::zero(), None, U256::from(21_000u64)),
+        };
+
+        let mut request = Eip1559TransactionRequest::new()
+            .from(from_addr)
+            .to(to_addr)
+            .value(value_wei)
+            .nonce(nonce)
+            .gas(gas_limit)
+            .max_fee_per_gas(max_fee)
+            .max_priority_fee_per_gas(max_priority_fee)
+            .chain_id(chain_id);
+        request.data = data;
+
+        let mode = if cancel { "cancel" } else { "speed_up" };
+        info!(
+            nonce = nonce,
+            mode = mode,
+            max_fee = %max_fee,
+            max_priority_fee = %max_priority_fee,
+            "Broadcasting replacement transaction"
+        );
+
+        let (tx_hash, receipt, status) = self.broadcast(request.into(), chain_id).await?;
+
+        Ok(ReplacementTransactionInfo {
+            tx_hash: format!("0x{:x}", tx_hash),
+            replaced_tx_hash: original.map(|tx| format!("0x{:x}", tx.hash)),
+            mode: mode.to_string(),
+            nonce,
+            from: Self::checksum(&from_addr),
+            to: Self::checksum(&to_addr),
+            value_wei: value_wei.to_string(),
+            gas_limit: gas_limit.as_u64(),
+            max_fee_per_gas_gwei: Decimal::from(max_fee.as_u128()) / WEI_PER_GWEI,
+            max_priority_fee_per_gas_gwei: Decimal::from(max_priority_fee.as_u128()) / WEI_PER_GWEI,
+            status: status.to_string(),
+            block_number: receipt.as_ref().and_then(|r| r.block_number).map(|n| n.as_u64()),
+            gas_used: receipt.as_ref().and_then(|r| r.gas_used).map(|g| g.as_u64()),
+        })
+    }
+
+    /// The wallet's pending or queued transaction at `nonce`, if the provider exposes txpool_*
+    async fn find_mempool_transaction(&self, from_addr: Address, nonce: u64) -> Option<Transaction> {
+        match self.provider.request::<_, TxpoolContentFrom>("txpool_contentFrom", [from_addr]).await {
+            Ok(content) => content.pending.into_values()
+                .chain(content.queued.into_values())
+                .find(|tx| tx.nonce.as_u64() == nonce),
+            Err(e) => {
+                debug!(error = %e, "txpool_contentFrom unavailable");
+                None
+            },
+        }
+    }
+
+    /// Raise a fee by `bump_percent`, rounding up so the replacement strictly outbids
+    pub fn bump_fee(fee: U256, bump_percent: Decimal) -> U256 {
+        // Basis points keep fractional percentages exact in integer arithmetic
+        let bump_bps = (bump_percent * dec!(100)).ceil().to_u64().unwrap_or(0);
+        let scaled = fee.saturating_mul(U256::from(10_000 + bump_bps));
+        (scaled + U256::from(9_999u64)) / U256::from(10_000u64)
+    }
+
+    /// Refuse broadcasts on mainnet when `disable_mainnet_writes` is set.
+    /// `chain_id` is the one reported by the RPC endpoint, not the configured network.
+    pub fn ensure_writes_allowed(chain_id: u64, disable_mainnet_writes: bool) -> Result<(), MCPError> {
+        if disable_mainnet_writes && chain_id == Network::Mainnet.chain_id() {
+            return Err(MCPError::ConfigurationError(
+                "Broadcasting on mainnet (chain ID 1) is disabled by DISABLE_MAINNET_WRITES; simulation tools remain available".to_string()
+            ));
+        }
+        Ok(())
+    }
+
+    /// How long `broadcast` may wait for a receipt: `RECEIPT_TIMEOUT_SECS`, cut short to end
+    /// before the deadline of the current tool call
+    fn receipt_wait() -> std::time::Duration {
+        let limit = std::time::Duration::from_secs(RECEIPT_TIMEOUT_SECS);
+        TOOL_DEADLINE.try_with(|deadline| {
+            deadline.saturating_duration_since(std::time::Instant::now())
+                .saturating_sub(std::time::Duration::from_millis(RECEIPT_DEADLINE_MARGIN_MS))
+        }).map_or(limit, |remaining| remaining.min(limit))
+    }
+
+    /// Sign and send a prepared transaction, waiting up to `receipt_wait` for the receipt.
+    ///
+    /// Errors before the node answers with a hash are `TransactionFailed` when nothing was
+    /// broadcast (the node rejected it, or it was never sent) and `TransactionOutcomeUnknown`
+    /// when the request may have reached the node. Once a hash is returned the call succeeds:
+    /// failing to fetch the receipt only leaves the transaction `pending`.
+    async fn broadcast(&self, tx: TypedTransaction, chain_id: u64) -> Result<(H256, Option<TransactionReceipt>, &'static str), MCPError> {
+        Self::ensure_writes_allowed(chain_id, self.disable_mainnet_writes)?;
+
+        let signer = SignerMiddleware::new(self.provider.clone(), self.wallet.clone().with_chain_id(chain_id));
+
+        let pending = signer.send_transaction(tx, None).await.map_err(|e| match &e {
+            // A transport failure may hide a node that accepted the transaction
+            SignerMiddlewareError::MiddlewareError(inner) if inner.as_error_response().is_none() => {
+                MCPError::TransactionOutcomeUnknown(e.to_string())
+            },
+            _ => MCPError::TransactionFailed(e.to_string()),
+        })?;
+        let tx_hash = *pending;
+
+        let receipt_wait = Self::receipt_wait();
+        let receipt = match tokio::time::timeout(receipt_wait, pending).await {
+            Ok(Ok(receipt)) => receipt,
+            Ok(Err(e)) => {
+                warn!(tx_hash = %format!("0x{:x}", tx_hash), error = %e, "Failed to fetch receipt for broadcast transaction");
+                None
+            },
+            Err(_) => {
+                warn!(tx_hash = %format!("0x{:x}", tx_hash), wait_ms = receipt_wait.as_millis() as u64, "Timed out waiting for receipt");
+                None
+            }
+        };
+
+        let status = Self::receipt_status(receipt.as_ref());
+
+        info!(tx_hash = %format!("0x{:x}", tx_hash), status = status, "Transaction submitted");
+
+        self.notify_confirmation_webhook(tx_hash, receipt.as_ref());
+
+        Ok((tx_hash, receipt, status))
+    }
+
+    /// `confirmed` or `failed` from a receipt's status, `pending` without a receipt
+    fn receipt_status(receipt: Option<&TransactionReceipt>) -> &'static str {
+        match receipt.and_then(|r| r.status) {
+            Some(s) if s.as_u64() == 1 => "confirmed",
+            Some(_) => "failed",
+            None => "pending",
+        }
+    }
+
+    /// POST the receipt outcome to `CONFIRMATION_WEBHOOK_URL` in the background. Without a
+    /// receipt yet, the transaction is polled for up to `CONFIRMATION_WATCH_SECS` first.
+    /// Delivery failures are logged and never fail the tool call.
+    fn notify_confirmation_webhook(&self, tx_hash: H256, receipt: Option<&TransactionReceipt>) {
+        let Some(url) = self.confirmation_webhook_url.clone() else {
+            return;
+        };
+        if self.offline_mode {
+            debug!(tx_hash = %format!("0x{:x}", tx_hash), "Skipping confirmation webhook in OFFLINE_MODE");
+            return;
+        }
+        let client = self.http_client.clone();
+        let provider = self.provider.clone();
+        let receipt = receipt.cloned();
+
+        tokio::spawn(async move {
+            let receipt = match receipt {
+                Some(receipt) => receipt,
+                None => {
+                    let watch = std::time::Duration::from_secs(CONFIRMATION_WATCH_SECS);
+                    match tokio::time::timeout(watch, Self::poll_receipt(&provider, tx_hash)).await {
+                        Ok(receipt) => receipt,
+                        Err(_) => {
+                            warn!(tx_hash = %format!("0x{:x}", tx_hash), watch_secs = CONFIRMATION_WATCH_SECS, "Transaction still pending, no confirmation webhook sent");
+                            return;
+                        },
+                    }
+                },
+            };
+
+            let payload = ConfirmationWebhookPayload {
+                tx_hash: format!("0x{:x}", tx_hash),
+                status: Self::receipt_status(Some(&receipt)).to_string(),
+                block_number: receipt.block_number.map(|n| n.as_u64()),
+            };
+            let result = client.post(&url).json(&payload).send().await
+                .and_then(|response| response.error_for_status());
+            match result {
+                Ok(response) => debug!(tx_hash = %payload.tx_hash, status = %response.status(), "Confirmation webhook delivered"),
+                Err(e) => warn!(tx_hash = %payload.tx_hash, error = %e, "Confirmation webhook delivery failed"),
+            }
+        });
+    }
+
+    /// Poll at the provider interval until `tx_hash` has a receipt; lookup errors are retried
+    async fn poll_receipt(provider: &Provider<FailoverTransport>, tx_hash: H256) -> TransactionReceipt {
+        loop {
+            tokio::time::sleep(provider.get_interval()).await;
+            match provider.get_transaction_receipt(tx_hash).await {
+                Ok(Some(receipt)) => return receipt,
+                Ok(None) => {},
+                Err(e) => debug!(tx_hash = %format!("0x{:x}", tx_hash), error = %e, "Receipt lookup failed, retrying"),
+            }
+        }
+    }
+
+    /// Build a fully-populated unsigned transaction (nonce, gas, fees, chain ID) without signing it.
+    /// `from` defaults to the server wallet, so external signers can supply their own address.
+    #[instrument(skip(self, fees), fields(to = %to, amount = %amount, legacy = fees.legacy))]
+    pub async fn build_transaction(&self, from: Option<ParsedAddress>, to: ParsedAddress, amount: Decimal, fees: TransactionFeeOptions) -> Result<UnsignedTransaction, MCPError> {
+        let from_addr = match from {
+            Some(from) => from.address(),
+            None => self.require_wallet("build_transaction without 'from'")?,
+        };
+        let to_addr = to.address();
+        let value_wei = Self::token_amount_to_raw(amount, 18, "ETH")?;
+
+        let PreparedTransaction { mut tx, chain_id, max_fee, max_priority_fee, gas_price, gas_estimate, gas_limit } =
+            self.prepare_transaction(from_addr, to_addr, value_wei, None, &fees, false).await?;
+
+        let nonce = self.provider.get_transaction_count(from_addr, Some(BlockNumber::Pending.into())).await?;
+        tx.set_nonce(nonce);
+        tx.set_chain_id(chain_id);
+
+        info!(
+            from = %format!("0x{:x}", from_addr),
+            nonce = %nonce,
+            chain_id = chain_id,
+            "Unsigned transaction built"
+        );
+
+        Ok(UnsignedTransaction {
+            transaction: serde_json::to_value(&tx)?,
+            rlp: format!("0x{}", hex::encode(tx.rlp())),
+            from: Self::checksum(&from_addr),
+            chain_id,
+            nonce: nonce.as_u64(),
+            transaction_type: if fees.legacy { "legacy" } else { "eip1559" }.to_string(),
+            gas_estimate: gas_estimate.as_u64(),
+            gas_limit: gas_limit.as_u64(),
+            max_fee_per_gas_gwei: max_fee.map(|v| Decimal::from(v.as_u128()) / WEI_PER_GWEI),
+            max_priority_fee_per_gas_gwei: max_priority_fee.map(|v| Decimal::from(v.as_u128()) / WEI_PER_GWEI),
+            gas_price_gwei: gas_price.map(|v| Decimal::from(v.as_u128()) / WEI_PER_GWEI),
+        })
+    }
+
+    /// Build a transaction with fees and gas limit filled in, optionally simulating it first
+    async fn prepare_transaction(&self, from_addr: Address, to_addr: Address, value_wei: U256, data: Option<Bytes>, fees: &TransactionFeeOptions, simulate: bool) -> Result<PreparedTransaction, MCPError> {
+        let chain_id = self.provider.get_chainid().await?.as_u64();
+
+        let (mut tx, max_fee, max_priority_fee, gas_price): (TypedTransaction, _, _, _) = if fees.legacy {
+            // Legacy mode uses max_fee_per_gas, when given, as the gas price
+            let gas_price = match fees.max_fee_per_gas {
+                Some(gwei) => Self::decimal_to_wei(gwei, WEI_PER_GWEI)?,
+                None => self.provider.get_gas_price().await
+                    .map_err(|e| MCPError::NetworkError(format!("Failed to get gas price: {}", e)))?,
+            };
+            let mut tx = TransactionRequest::new().from(from_addr).to(to_addr).value(value_wei).gas_price(gas_price);
+            tx.data = data;
+            (tx.into(), None, None, Some(gas_price))
+        } else {
+            let (max_fee, max_priority_fee) = self.resolve_eip1559_fees(fees).await?;
+            let mut tx = Eip1559TransactionRequest::new()
+                .from(from_addr)
+                .to(to_addr)
+                .value(value_wei)
+                .max_fee_per_gas(max_fee)
+                .max_priority_fee_per_gas(max_priority_fee);
+            tx.data = data;
+            (tx.into(), Some(max_fee), Some(max_priority_fee), None)
+        };
+
+        if simulate {
+            self.provider.call(&tx, None).await
+                .map_err(|e| MCPError::TransactionFailed(
+                    format!("Simulation reverted, not broadcasting: {}", Self::revert_reason(&e))
+                ))?;
+            debug!("Pre-send simulation succeeded");
+        }
+
+        let gas_estimate = self.provider.estimate_gas(&tx, None).await
+            .map_err(|e| MCPError::GasEstimationFailed(Self::revert_reason(&e)))?;
+        let gas_limit = self.pad_gas_limit(gas_estimate, fees.gas_multiplier);
+        tx.set_gas(gas_limit);
+
+        Ok(PreparedTransaction { tx, chain_id, max_fee, max_priority_fee, gas_price, gas_estimate, gas_limit })
+    }
+
+    /// Scale a raw gas estimate by the per-call multiplier or the configured default
+    fn pad_gas_limit(&self, gas_estimate: U256, multiplier: Option<Decimal>) -> U256 {
+        let multiplier = multiplier.unwrap_or(self.gas_multiplier);
+        let padded = (Decimal::from(gas_estimate.as_u64()) * multiplier).ceil();
+        U256::from(padded.to_u64().unwrap_or(gas_estimate.as_u64()))
+    }
+
+    /// Resolve EIP-1559 fees, filling any missing override from eth_feeHistory
+    async fn resolve_eip1559_fees(&self, fees: &TransactionFeeOptions) -> Result<(U256, U256), MCPError> {
+        let max_fee_override = fees.max_fee_per_gas.map(|gwei| Self::decimal_to_wei(gwei, WEI_PER_GWEI)).transpose()?;
+        let priority_override = fees.max_priority_fee_per_gas.map(|gwei| Self::decimal_to_wei(gwei, WEI_PER_GWEI)).transpose()?;
+
+        let (max_fee, max_priority_fee) = match (max_fee_override, priority_override) {
+            (Some(max_fee), Some(priority)) => (max_fee, priority),
+            _ => {
+                let (estimated_max_fee, estimated_priority) = self.provider.estimate_eip1559_fees(None).await
+                    .map_err(|e| MCPError::GasEstimationFailed(format!("Failed to derive fees from fee history: {}", e)))?;
+                (
+                    max_fee_override.unwrap_or(estimated_max_fee),
+                    priority_override.unwrap_or(estimated_priority),
+                )
+            }
+        };
+
+        if max_priority_fee > max_fee {
+            return Err(MCPError::ValidationError(
+                "max_priority_fee_per_gas cannot exceed max_fee_per_gas".to_string()
+            ));
+        }
+
+        Ok((max_fee, max_priority_fee))
+    }
+
+    /// Extract a human-readable revert reason from a provider error
+    fn revert_reason(error: &ProviderError) -> String {
+        if let Some(rpc_error) = error.as_error_response() {
+            if let Some(reason) = rpc_error.as_revert_data().and_then(|data| Self::decode_revert_string(&data)) {
+                return reason;
+            }
+            return rpc_error.message.clone();
+        }
+        error.to_string()
+    }
+
+    /// Decode `Error(string)` revert data into its message
+    pub(crate) fn decode_revert_string(data: &[u8]) -> Option<String> {
+        use ethers::abi::{decode, ParamType, Token};
+
+        // Error(string) selector
+        let payload = data.strip_prefix(&[0x08, 0xc3, 0x79, 0xa0])?;
+        match decode(&[ParamType::String], payload).ok()?.pop()? {
+            Token::String(reason) => Some(reason),
+            _ => None,
+        }
+    }
+
+    /// Scale a token amount into raw units, failing instead of truncating when it has
+    /// more fractional digits than the token's decimals
+    pub fn token_amount_to_raw(amount: Decimal, decimals: u8, symbol: &str) -> Result<U256, MCPError> {
+        let fractional_digits = amount.normalize().scale();
+        if fractional_digits > decimals as u32 {
+            return Err(MCPError::InvalidAmount(format!(
+                "{} has {} fractional digits but {} supports {}",
+                amount, fractional_digits, symbol, decimals
+            )));
+        }
+        // The mantissa times 10^(decimals - scale), on U256 so large decimals cannot overflow
+        let normalized = amount.normalize();
+        let out_of_range = || MCPError::InvalidAmount(format!("Amount out of range: {}", amount));
+        let mantissa = u128::try_from(normalized.mantissa()).map_err(|_| out_of_range())?;
+        if mantissa == 0 {
+            return Ok(U256::zero());
+        }
+        Self::checked_exp10(decimals as u32 - fractional_digits)
+            .and_then(|scale| U256::from(mantissa).checked_mul(scale))
+            .ok_or_else(out_of_range)
+    }
+
+    /// Scale a decimal amount into an integer wei value
+    fn decimal_to_wei(amount: Decimal, scale: Decimal) -> Result<U256, MCPError> {
+        amount.checked_mul(scale)
+            .and_then(|wei| wei.trunc().to_u128())
+            .map(U256::from)
+            .ok_or_else(|| MCPError::InvalidAmount(format!("Amount out of range: {}", amount)))
+    }
+
+    /// Current gas price and base fee, flagged `is_high` above `GAS_WARN_GWEI`
+    #[instrument(skip(self))]
+    pub async fn get_gas_price(&self) -> Result<GasPriceInfo, MCPError> {
+        let (gas_price, base_fee) = tokio::try_join!(
+            self.provider.get_gas_price(),
+            self.latest_base_fee(),
+        )?;
+        let gas_price_gwei = Decimal::from(gas_price.as_u128()) / WEI_PER_GWEI;
+        let is_high = gas_price_gwei > self.gas_warn_gwei;
+        if is_high {
+            warn!(gas_price_gwei = %gas_price_gwei, threshold_gwei = %self.gas_warn_gwei, "Gas price is above GAS_WARN_GWEI");
+        }
+
+        Ok(GasPriceInfo {
+            gas_price_gwei,
+            base_fee_gwei: base_fee.map(|fee| Decimal::from(fee.as_u128()) / WEI_PER_GWEI),
+            warn_threshold_gwei: self.gas_warn_gwei,
+            is_high,
+        })
+    }
+
+    /// Base fee of the latest block; None on chains without EIP-1559
+    async fn latest_base_fee(&self) -> Result<Option<U256>, ProviderError> {
+        Ok(self.provider.get_block(BlockNumber::Latest).await?
+            .and_then(|block| block.base_fee_per_gas))
+    }
+
+    /// Poll the base fee every `poll_interval` until it is at most `target_gwei` or `timeout`
+    /// elapses. Chains without EIP-1559 are polled on the gas price instead.
+    #[instrument(skip(self), fields(target_gwei = %target_gwei))]
+    pub async fn wait_for_gas(&self, target_gwei: Decimal, timeout: std::time::Duration, poll_interval: std::time::Duration) -> Result<GasWaitInfo, MCPError> {
+        let started = std::time::Instant::now();
+        let mut checks = 0;
+        loop {
+            let fee = match self.latest_base_fee().await? {
+                Some(base_fee) => base_fee,
+                None => self.provider.get_gas_price().await?,
+            };
+            checks += 1;
+            let base_fee_gwei = Decimal::from(fee.as_u128()) / WEI_PER_GWEI;
+            let reached = base_fee_gwei <= target_gwei;
+            debug!(base_fee_gwei = %base_fee_gwei, checks, "Gas price checked");
+
+            if reached || started.elapsed() + poll_interval > timeout {
+                info!(reached, base_fee_gwei = %base_fee_gwei, checks, "Gas wait finished");
+                return Ok(GasWaitInfo {
+                    reached,
+                    target_gwei,
+                    base_fee_gwei,
+                    waited_secs: started.elapsed().as_secs(),
+                    checks,
+                });
+            }
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+
+    /// Fetch per-block base fees and priority-fee percentiles via eth_feeHistory
+    #[instrument(skip(self))]
+    pub async fn get_fee_history(&self, block_count: u64, reward_percentiles: Vec<f64>) -> Result<FeeHistoryInfo, MCPError> {
+        let history = self.provider
+            .fee_history(block_count, BlockNumber::Latest, &reward_percentiles)
+            .await?;
+
+        let to_gwei = |wei: &U256| Decimal::from(wei.as_u128()) / WEI_PER_GWEI;
+        let oldest_block = history.oldest_block.as_u64();
+
+        let blocks: Vec<FeeHistoryBlock> = history.gas_used_ratio.iter()
+            .enumerate()
+            .map(|(i, gas_used_ratio)| FeeHistoryBlock {
+                block_number: oldest_block + i as u64,
+                base_fee_per_gas_gwei: history.base_fee_per_gas.get(i).map(to_gwei).unwrap_or_default(),
+                gas_used_ratio: *gas_used_ratio,
+                priority_fees_gwei: history.reward.get(i)
+                    .map(|rewards| rewards.iter().map(to_gwei).collect())
+                    .unwrap_or_default(),
+            })
+            .collect();
+
+        // eth_feeHistory returns one extra base fee: the projection for the next block
+        let next_base_fee_per_gas_gwei = history.base_fee_per_gas.get(blocks.len()).map(to_gwei);
+
+        info!(oldest_block = oldest_block, block_count = blocks.len(), "Fee history retrieved");
+
+        Ok(FeeHistoryInfo {
+            oldest_block,
+            reward_percentiles,
+            blocks,
+            next_base_fee_per_gas_gwei,
+        })
+    }
+
+    /// Fetch event logs over a block range, split into `logs_chunk_size` sub-ranges queried
+    /// concurrently. Failed chunks are reported alongside the logs that did arrive.
+    #[instrument(skip(self, topics), fields(address = ?address, from_block = from_block, to_block = ?to_block))]
+    pub async fn get_logs(&self, address: Option<ParsedAddress>, topics: Vec<Option<H256>>, from_block: u64, to_block: Option<u64>) -> Result<LogsInfo, MCPError> {
+        let to_block = match to_block {
+            Some(block) => block,
+            None => self.provider.get_block_number().await?.as_u64(),
+        };
+        if to_block < from_block {
+            return Err(MCPError::ValidationError(
+                format!("to_block {} is before from_block {}", to_block, from_block)
+            ));
+        }
+        let span = (to_block - from_block).saturating_add(1);
+        if span > self.logs_max_range {
+            return Err(MCPError::ValidationError(
+                format!("Block range of {} exceeds the maximum of {} (GET_LOGS_MAX_RANGE)", span, self.logs_max_range)
+            ));
+        }
+
+        let mut filter = Filter::new();
+        if let Some(address) = address {
+            filter = filter.address(address.address());
+        }
+        for (position, topic) in topics.into_iter().enumerate().take(4) {
+            if let Some(topic) = topic {
+                filter.topics[position] = Some(topic.into());
+            }
+        }
+
+        let chunks = split_block_range(from_block, to_block, self.logs_chunk_size);
+        let chunk_count = chunks.len();
+        debug!(chunk_count = chunk_count, chunk_size = self.logs_chunk_size, "Querying logs in chunks");
+
+        // `buffered` keeps chunk order, so the merged logs stay in block order
+        let results: Vec<_> = stream::iter(chunks)
+            .map(|(start, end)| {
+                let chunk_filter = filter.clone().from_block(start).to_block(end);
+                async move { ((start, end), self.provider.get_logs(&chunk_filter).await) }
+            })
+            .buffered(MAX_CONCURRENT_LOG_QUERIES)
+            .collect()
+            .await;
+
+        let mut raw_logs = Vec::new();
+        let mut failed_chunks = Vec::new();
+        for ((start, end), result) in results {
+            match result {
+                Ok(chunk_logs) => raw_logs.extend(chunk_logs),
+                Err(e) => {
+                    warn!(from_block = start, to_block = end, error = %e, "Log chunk failed");
+                    failed_chunks.push(FailedLogChunk { from_block: start, to_block: end, error: e.to_string() });
+                },
+            }
+        }
+
+        // Resolve each token's decimals once, through the metadata cache
+        let mut transfer_tokens: Vec<Address> = raw_logs.iter()
+            .filter(|log| Self::decode_transfer_log(log).is_some())
+            .map(|log| log.address)
+            .collect();
+        transfer_tokens.sort();
+        transfer_tokens.dedup();
+        let mut token_metadata = HashMap::new();
+        for token in transfer_tokens {
+            token_metadata.insert(token, self.resolve_token_metadata(token, None).await);
+        }
+
+        let logs: Vec<LogEntry> = raw_logs.into_iter().map(|log| {
+            let transfer = Self::decode_transfer_log(&log).and_then(|(from, to, value)| {
+                let metadata = token_metadata.get(&log.address)?;
+                Some(DecodedTransfer {
+                    from: Self::checksum(&from),
+                    to: Self::checksum(&to),
+                    value: Self::format_u256_units(value, metadata.decimals, metadata.decimals as u32),
+                    value_raw: value.to_string(),
+                    symbol: metadata.symbol.clone(),
+                    decimals: metadata.decimals,
+                })
+            });
+            LogEntry {
+                address: Self::checksum(&log.address),
+                block_number: log.block_number.map(|n| n.as_u64()),
+                transaction_hash: log.transaction_hash.map(|hash| format!("{:#x}", hash)),
+                log_index: log.log_index.map(|i| i.as_u64()),
+                topics: log.topics.iter().map(|topic| format!("{:#x}", topic)).collect(),
+                data: log.data.to_string(),
+                transfer,
+            }
+        }).collect();
+
+        info!(log_count = logs.len(), failed_chunks = failed_chunks.len(), "Logs retrieved");
+
+        Ok(LogsInfo {
+            from_block,
+            to_block,
+            chunk_size: self.logs_chunk_size,
+            chunk_count,
+            logs,
+            partial: !failed_chunks.is_empty(),
+            failed_chunks,
+        })
+    }
+
+    /// `(from, to, value)` of an ERC20 Transfer log. ERC721 transfers share the signature
+    /// but index the token ID as a fourth topic, so they are left undecoded.
+    pub fn decode_transfer_log(log: &Log) -> Option<(Address, Address, U256)> {
+        if log.topics.len() != 3 || log.topics[0] != ERC20_TRANSFER_TOPIC || log.data.len() != 32 {
+            return None;
+        }
+        Some((
+            Address::from(log.topics[1]),
+            Address::from(log.topics[2]),
+            U256::from_big_endian(&log.data),
+        ))
+    }
+
+    /// Estimate the gas cost of an ERC20 transfer from the server wallet without sending it
+    #[instrument(skip(self), fields(token_address = %token_address, to = %to, amount = %amount))]
+    pub async fn simulate_token_transfer(&self, token_address: ParsedAddress, to: ParsedAddress, amount: Decimal, gas_multiplier: Option<Decimal>) -> Result<TransferSimulation, MCPError> {
+        let from_addr = self.require_wallet("simulate_token_transfer")?;
+        let token_addr = token_address.address();
+        let to_addr = to.address();
+
+        let TokenMetadata { symbol, decimals, .. } = self.resolve_token_metadata(token_addr, None).await;
+        let amount_raw = Self::token_amount_to_raw(amount, decimals, &symbol)?;
+
+        let tx: TypedTransaction = TransactionRequest::new()
+            .from(from_addr)
+            .to(token_addr)
+            .data(Self::encode_erc20_transfer(to_addr, amount_raw))
+            .into();
+
+        let gas_estimate = self.provider.estimate_gas(&tx, None).await
+            .map_err(|e| MCPError::GasEstimationFailed(format!("Transfer would revert: {}", Self::revert_reason(&e))))?;
+        let gas_limit = self.pad_gas_limit(gas_estimate, gas_multiplier).as_u64();
+        let gas_estimate = gas_estimate.as_u64();
+
+        let gas_price = self.provider.get_gas_price().await
+            .map_err(|e| MCPError::NetworkError(format!("Failed to get gas price: {}", e)))?;
+        let gas_price_decimal = Decimal::from(gas_price.as_u128()) / WEI_PER_ETH;
+        let total_cost = Decimal::from(gas_estimate) * gas_price_decimal;
+
+        info!(gas_estimate = gas_estimate, total_cost = %total_cost, "Token transfer simulated");
+
+        Ok(TransferSimulation {
+            token_address: Self::checksum(&token_addr),
+            symbol,
+            from: Self::checksum(&from_addr),
+            to: Self::checksum(&to_addr),
+            amount,
+            amount_raw: amount_raw.to_string(),
+            gas_estimate,
+            gas_limit,
+            gas_price: gas_price_decimal,
+            total_cost,
+        })
+    }
+
+    /// ERC20 transfer(address,uint256) calldata
+    fn encode_erc20_transfer(to: Address, amount_raw: U256) -> Bytes {
+        use ethers::abi::{encode, Token};
+
+        // ERC20 transfer function selector: 0xa9059cbb
+        let mut data = vec![0xa9, 0x05, 0x9c, 0xbb];
+        data.extend_from_slice(&encode(&[Token::Address(to), Token::Uint(amount_raw)]));
+        data.into()
+    }
+
+    /// Fetch deployed bytecode to tell contracts apart from EOAs
+    #[instrument(skip(self), fields(address = %address))]
+    pub async fn get_code(&self, address: ParsedAddress) -> Result<ContractCode, MCPError> {
+        let addr = address.address();
+
+        let code = self.provider.get_code(addr, None).await?;
+
+        info!(address = %address, code_size = code.len(), "Contract code retrieved");
+
+        Ok(ContractCode {
+            address: Self::checksum(&addr),
+            is_contract: !code.is_empty(),
+            code_size: code.len(),
+        })
+    }
+
+    /// Detect an EIP-1967 proxy from its implementation, beacon and admin storage slots.
+    /// A beacon proxy's implementation is read from the beacon's `implementation()`.
+    #[instrument(skip(self), fields(address = %address))]
+    pub async fn detect_proxy(&self, address: ParsedAddress) -> Result<ProxyInfo, MCPError> {
+        let addr = address.address();
+        let (code, implementation_word, beacon_word, admin_word) = tokio::try_join!(
+            self.provider.get_code(addr, None),
+            self.provider.get_storage_at(addr, EIP1967_IMPLEMENTATION_SLOT, None),
+            self.provider.get_storage_at(addr, EIP1967_BEACON_SLOT, None),
+            self.provider.get_storage_at(addr, EIP1967_ADMIN_SLOT, None),
+        )?;
+
+        let beacon = Self::slot_address(implementation_word).is_none()
+            .then(|| Self::slot_address(beacon_word))
+            .flatten();
+        let (proxy_type, implementation) = match (Self::slot_address(implementation_word), beacon) {
+            (Some(implementation), _) => (Some(ProxyType::Eip1967), Some(implementation)),
+            (None, Some(beacon)) => {
+                // implementation() selector: 0x5c60da1b
+                let tx: TypedTransaction = TransactionRequest::new().to(beacon).data(vec![0x5c, 0x60, 0xda, 0x1b]).into();
+                let implementation = match self.provider.call(&tx, None).await {
+                    Ok(result) if result.len() >= 32 => Self::slot_address(H256::from_slice(&result[..32])),
+                    Ok(_) => None,
+                    Err(e) => {
+                        warn!(beacon = %Self::checksum(&beacon), error = %e, "Beacon implementation() call failed");
+                        None
+                    },
+                };
+                (Some(ProxyType::Eip1967Beacon), implementation)
+            },
+            (None, None) => (None, None),
+        };
+
+        info!(proxy_type = ?proxy_type, implementation = ?implementation.map(|a| Self::checksum(&a)), "Proxy detection completed");
+
+        Ok(ProxyInfo {
+            address: Self::checksum(&addr),
+            is_contract: !code.is_empty(),
+            is_proxy: proxy_type.is_some(),
+            proxy_type,
+            implementation: implementation.map(|a| Self::checksum(&a)),
+            beacon: beacon.map(|a| Self::checksum(&a)),
+            admin: Self::slot_address(admin_word).map(|a| Self::checksum(&a)),
+        })
+    }
+
+    /// The address stored in a 32-byte slot word; None when the word is zero or not a
+    /// left-padded address
+    pub fn slot_address(word: H256) -> Option<Address> {
+        let (padding, address) = word.as_bytes().split_at(12);
+        if padding.iter().any(|b| *b != 0) || address.iter().all(|b| *b == 0) {
+            return None;
+        }
+        Some(Address::from_slice(address))
+    }
+
+    /// Compare pending and latest nonces and, when the provider exposes the mempool,
+    /// list the address's pending and queued transactions
+    #[instrument(skip(self), fields(address = %address))]
+    pub async fn get_pending_transactions(&self, address: ParsedAddress) -> Result<PendingTransactionsInfo, MCPError> {
+        let addr = address.address();
+
+        let latest_nonce = self.provider
+            .get_transaction_count(addr, Some(BlockNumber::Latest.into()))
+            .await?
+            .as_u64();
+        let pending_nonce = self.provider
+            .get_transaction_count(addr, Some(BlockNumber::Pending.into()))
+            .await?
+            .as_u64();
+
+        // Most hosted providers do not expose txpool_*; the nonce gap still works without it
+        let (mempool_available, transactions) = match self.provider
+            .request::<_, TxpoolContentFrom>("txpool_contentFrom", [addr])
+            .await
+        {
+            Ok(content) => {
+                let to_pending = |pool: &str, tx: Transaction| PendingTransaction {
+                    hash: format!("{:#x}", tx.hash),
+                    nonce: tx.nonce.as_u64(),
+                    to: tx.to.map(|to| Self::checksum(&to)),
+                    value: tx.value.to_string(),
+                    pool: pool.to_string(),
+                };
+                let mut transactions: Vec<PendingTransaction> = content.pending.into_values()
+                    .map(|tx| to_pending("pending", tx))
+                    .chain(content.queued.into_values().map(|tx| to_pending("queued", tx)))
+                    .collect();
+                transactions.sort_by_key(|tx| tx.nonce);
+                (true, transactions)
+            },
+            Err(e) => {
+                debug!(error = %e, "txpool_contentFrom unavailable");
+                (false, Vec::new())
+            },
+        };
+
+        let pending_count = pending_nonce.saturating_sub(latest_nonce);
+        info!(latest_nonce, pending_nonce, pending_count, mempool_available, "Pending transactions retrieved");
+
+        Ok(PendingTransactionsInfo {
+            address: Self::checksum(&addr),
+            latest_nonce,
+            pending_nonce,
+            pending_count,
+            mempool_available,
+            transactions,
+        })
+    }
+
+    /// Read a raw 32-byte storage slot
+    pub async fn get_storage_at(&self, address: ParsedAddress, slot: U256) -> Result<StorageSlot, MCPError> {
+        let addr = address.address();
+        let mut slot_bytes = [0u8; 32];
+        slot.to_big_endian(&mut slot_bytes);
+        let location = H256::from(slot_bytes);
+
+        let value = self.provider.get_storage_at(addr, location, None).await?;
+
+        info!(address = %address, slot = %format!("{:#x}", location), "Storage slot retrieved");
+
+        Ok(StorageSlot {
+            address: Self::checksum(&addr),
+            slot: format!("{:#x}", location),
+            value: format!("{:#x}", value),
         })
     }
 
-    async fn get_token_balance(&self, wallet_addr: Address, token_addr: &str) -> Result<TokenBalance, MCPError> {
-        let token_address = token_addr.parse::<Address>()
-            .map_err(|_| MCPError::InvalidTokenContract(token_addr.to_string()))?;
+    async fn get_token_balance(&self, wallet_addr: Address, token_address: Address, block: Option<BlockId>, decimals_display: u32) -> Result<TokenBalance, MCPError> {
+        // Fail fast instead of calling balanceOf on an EOA
+        let code = self.provider.get_code(token_address, block).await?;
+        if code.is_empty() {
+            return Err(MCPError::InvalidTokenContract(
+                format!("{} is not a contract", Self::checksum(&token_address))
+            ));
+        }
 
-        // Try to get token info dynamically, fallback to known tokens or defaults
-        let (name, symbol, decimals) = match self.get_token_info(token_address).await {
-            Ok(info) => info,
-            Err(e) => {
-                // If dynamic lookup fails, try known tokens, then use defaults
-                warn!(
-                    token_address = %token_addr,
-                    error = %e,
-                    "Failed to get token info dynamically, trying known tokens"
-                );
-                self.get_known_token_info(token_addr)
-            }
-        };
+        // balanceOf on an NFT contract returns a count, not a token balance
+        match self.detect_token_standard(token_address).await {
+            TokenStandard::Erc20 => {},
+            standard => {
+                return Err(MCPError::ValidationError(format!(
+                    "{} is an {} contract; get_balance only supports ERC20 tokens",
+                    Self::checksum(&token_address),
+                    standard.name()
+                )));
+            },
+        }
+
+        let metadata = self.resolve_token_metadata(token_address, block).await;
 
         // ERC20 balanceOf function selector: 0x70a08231
         let balance_of_selector = [0x70, 0xa0, 0x82, 0x31];
@@ -157,7 +1996,7 @@ impl EthereumClient {
         
         // Properly encode the wallet address (32 bytes, padded)
         let mut wallet_bytes = [0u8; 32];
-        wallet_bytes[12..].copy_from_slice(&wallet_addr.as_bytes());
+        wallet_bytes[12..].copy_from_slice(wallet_addr.as_bytes());
         data.extend_from_slice(&wallet_bytes);
 
         let call_data = ethers::types::Bytes::from(data);
@@ -167,29 +2006,318 @@ impl EthereumClient {
             ..Default::default()
         };
         
-        let result = self.provider.call(&tx.into(), None).await?;
+        let result = self.provider.call(&tx.into(), block).await?;
+        if result.len() != 32 {
+            return Err(MCPError::InvalidTokenContract(format!(
+                "{} did not return an ERC20 balance",
+                Self::checksum(&token_address)
+            )));
+        }
 
         let balance_uint = U256::from(result.as_ref());
-        let balance = Decimal::from(balance_uint.as_u128());
+        Ok(Self::build_token_balance(token_address, balance_uint, metadata, decimals_display))
+    }
+
+    /// Balance of `token_id` held by `owner` in an ERC1155 multi-token contract
+    #[instrument(skip(self), fields(contract_address = %contract_address, owner = %owner))]
+    pub async fn get_erc1155_balance(&self, contract_address: ParsedAddress, owner: ParsedAddress, token_id: U256) -> Result<Erc1155Balance, MCPError> {
+        let contract = contract_address.address();
+        let code = self.provider.get_code(contract, None).await?;
+        if code.is_empty() {
+            return Err(MCPError::InvalidTokenContract(
+                format!("{} is not a contract", Self::checksum(&contract))
+            ));
+        }
+
+        // ERC1155 balanceOf(address,uint256) selector: 0x00fdd58e
+        let mut data = vec![0x00, 0xfd, 0xd5, 0x8e];
+        let mut owner_bytes = [0u8; 32];
+        owner_bytes[12..].copy_from_slice(owner.address().as_bytes());
+        data.extend_from_slice(&owner_bytes);
+        let mut id_bytes = [0u8; 32];
+        token_id.to_big_endian(&mut id_bytes);
+        data.extend_from_slice(&id_bytes);
+
+        let tx: TypedTransaction = TransactionRequest::new().to(contract).data(data).into();
+        let result = self.provider.call(&tx, None).await?;
+        if result.len() != 32 {
+            return Err(MCPError::InvalidTokenContract(format!(
+                "{} did not return an ERC1155 balance",
+                Self::checksum(&contract)
+            )));
+        }
+        let balance = U256::from(result.as_ref());
+
+        info!(token_id = %token_id, balance = %balance, "ERC1155 balance retrieved");
+
+        Ok(Erc1155Balance {
+            contract_address: Self::checksum(&contract),
+            owner: Self::checksum(&owner.address()),
+            token_id: token_id.to_string(),
+            balance: balance.to_string(),
+        })
+    }
+
+    /// Classify a token contract via ERC-165 `supportsInterface`. Contracts that do not
+    /// implement ERC-165 (most ERC20s) are treated as ERC20. Well-known tokens skip the calls.
+    pub async fn detect_token_standard(&self, token_address: Address) -> TokenStandard {
+        if Self::known_token_info(&format!("0x{:x}", token_address)).is_some() {
+            return TokenStandard::Erc20;
+        }
+        if let Some(standard) = self.token_standards.lock().unwrap().get(&token_address) {
+            return *standard;
+        }
+
+        let standard = if self.supports_interface(token_address, ERC721_INTERFACE_ID).await {
+            TokenStandard::Erc721
+        } else if self.supports_interface(token_address, ERC1155_INTERFACE_ID).await {
+            TokenStandard::Erc1155
+        } else {
+            TokenStandard::Erc20
+        };
+        debug!(token_address = %format!("0x{:x}", token_address), standard = ?standard, "Token standard detected");
+
+        self.token_standards.lock().unwrap().insert(token_address, standard);
+        standard
+    }
+
+    /// ERC-165 `supportsInterface(bytes4)`; reverts and malformed results count as unsupported
+    async fn supports_interface(&self, contract: Address, interface_id: [u8; 4]) -> bool {
+        // supportsInterface(bytes4) selector: 0x01ffc9a7
+        let mut data = vec![0x01, 0xff, 0xc9, 0xa7];
+        let mut padded = [0u8; 32];
+        padded[..4].copy_from_slice(&interface_id);
+        data.extend_from_slice(&padded);
 
-        let balance_formatted = balance / Decimal::from(10_u64.pow(decimals as u32));
+        let tx: TypedTransaction = TransactionRequest::new().to(contract).data(data).into();
+        match self.provider.call(&tx, None).await {
+            Ok(result) => result.len() == 32 && U256::from(result.as_ref()) == U256::one(),
+            Err(_) => false,
+        }
+    }
+
+    /// Assemble a TokenBalance from a raw balance and token metadata. Balances beyond
+    /// Decimal's 96-bit range saturate `balance`; `balance_raw` and `balance_formatted` stay exact.
+    pub(crate) fn build_token_balance(token_address: Address, balance_uint: U256, metadata: TokenMetadata, decimals_display: u32) -> TokenBalance {
+        let TokenMetadata { name, symbol, decimals, source, .. } = metadata;
+        let (balance, balance_formatted) = match Self::u256_to_decimal(balance_uint) {
+            Some(balance) => (balance, Self::format_balance(Self::scale_token_amount(balance, decimals), decimals_display)),
+            None => {
+                warn!(
+                    token_address = %format!("0x{:x}", token_address),
+                    balance_raw = %balance_uint,
+                    "Token balance exceeds Decimal range, saturating balance"
+                );
+                (Decimal::MAX, Self::format_u256_units(balance_uint, decimals, decimals_display))
+            },
+        };
 
-        Ok(TokenBalance {
-            contract_address: token_addr.to_string(),
+        TokenBalance {
+            contract_address: Self::checksum(&token_address),
             symbol,
             name,
             decimals,
             balance,
-            balance_formatted: balance_formatted.to_string(),
-        })
+            balance_raw: balance_uint.to_string(),
+            balance_formatted,
+            usd_value: None,
+            metadata_source: source,
+        }
+    }
+
+    /// Exact Decimal for an on-chain integer, or None when it exceeds Decimal's 96-bit range
+    pub fn u256_to_decimal(value: U256) -> Option<Decimal> {
+        value.to_string().parse::<Decimal>().ok()
+    }
+
+    /// `raw / 10^decimals` rounded half-up to `decimals_display` digits, computed on U256 so
+    /// values beyond Decimal's range format exactly; trailing zeros are trimmed
+    pub fn format_u256_units(raw: U256, decimals: u8, decimals_display: u32) -> String {
+        let decimals = decimals as u32;
+        let (value, digits) = if decimals_display < decimals {
+            // A divisor beyond U256 exceeds twice any raw value, so the result rounds to zero
+            let rounded = match Self::checked_exp10(decimals - decimals_display) {
+                Some(divisor) => {
+                    let (quotient, remainder) = raw.div_mod(divisor);
+                    if remainder >= divisor - remainder { quotient.saturating_add(U256::one()) } else { quotient }
+                },
+                None => U256::zero(),
+            };
+            (rounded, decimals_display as usize)
+        } else {
+            (raw, decimals as usize)
+        };
+
+        let text = format!("{:0>width$}", value.to_string(), width = digits + 1);
+        let (whole, fraction) = text.split_at(text.len() - digits);
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.is_empty() { whole.to_string() } else { format!("{}.{}", whole, fraction) }
+    }
+
+    /// USD value of a token balance, or None if it overflows Decimal
+    fn token_usd_value(token: &TokenBalance, price: Decimal) -> Option<Decimal> {
+        Self::scale_token_amount(token.balance, token.decimals).checked_mul(price)
+    }
+
+
+
+    /// Get token (name, symbol, decimals) from the cache or contract, falling back to known tokens or defaults
+    async fn resolve_token_metadata(&self, token_address: Address, block: Option<BlockId>) -> TokenMetadata {
+        let token_addr = format!("0x{:x}", token_address);
+        if let Some(metadata) = self.token_metadata.lock().unwrap().get(&token_address) {
+            return metadata.clone();
+        }
+
+        // Well-known tokens need no RPC round trip
+        if self.prefer_known_list {
+            if let Some((name, symbol, decimals)) = Self::known_token_info(&token_addr) {
+                return TokenMetadata { name, symbol, decimals, source: MetadataSource::KnownList, logo: None };
+            }
+        }
+
+        match self.get_token_info(token_address, block).await {
+            Ok((name, symbol, decimals)) => {
+                let metadata = TokenMetadata { name, symbol, decimals, source: MetadataSource::Onchain, logo: None };
+                self.token_metadata.lock().unwrap().insert(token_address, metadata.clone());
+                metadata
+            },
+            Err(e) => {
+                // If dynamic lookup fails, try known tokens, then use defaults
+                warn!(
+                    token_address = %token_addr,
+                    error = %e,
+                    "Failed to get token info dynamically, trying known tokens"
+                );
+                let source = if Self::known_token_info(&token_addr).is_some() {
+                    MetadataSource::KnownList
+                } else {
+                    MetadataSource::Default
+                };
+                let (name, symbol, decimals) = self.get_known_token_info(&token_addr);
+                TokenMetadata { name, symbol, decimals, source, logo: None }
+            }
+        }
+    }
+
+    /// Token name, symbol, decimals and logo from `alchemy_getTokenMetadata`, cached for later
+    /// balance lookups. Falls back to the regular metadata resolution when Alchemy is unavailable.
+    #[instrument(skip(self), fields(token_address = %token_address))]
+    pub async fn get_token_metadata(&self, token_address: ParsedAddress) -> TokenMetadataInfo {
+        let token_addr = token_address.address();
+
+        let cached_alchemy = self.token_metadata.lock().unwrap()
+            .get(&token_addr)
+            .filter(|metadata| metadata.source == MetadataSource::Alchemy)
+            .cloned();
+        let metadata = match cached_alchemy {
+            Some(metadata) => metadata,
+            None => match self.fetch_alchemy_token_metadata(token_addr).await {
+                Ok(metadata) => {
+                    self.token_metadata.lock().unwrap().insert(token_addr, metadata.clone());
+                    metadata
+                },
+                Err(e) => {
+                    warn!(error = %e, "alchemy_getTokenMetadata unavailable, falling back to on-chain metadata");
+                    self.resolve_token_metadata(token_addr, None).await
+                },
+            },
+        };
+
+        TokenMetadataInfo {
+            token_address: Self::checksum(&token_addr),
+            name: metadata.name,
+            symbol: metadata.symbol,
+            decimals: metadata.decimals,
+            logo: metadata.logo,
+            metadata_source: metadata.source,
+        }
+    }
+
+    /// Call `alchemy_getTokenMetadata`; contracts without ERC20 metadata are an error
+    async fn fetch_alchemy_token_metadata(&self, token_address: Address) -> Result<TokenMetadata, MCPError> {
+        if !self.is_alchemy_rpc() {
+            return Err(MCPError::ConfigurationError("RPC endpoint is not Alchemy".to_string()));
+        }
+
+        let response: AlchemyTokenMetadata = self.provider
+            .request("alchemy_getTokenMetadata", [token_address])
+            .await?;
+        match response {
+            AlchemyTokenMetadata { name: Some(name), symbol: Some(symbol), decimals: Some(decimals), logo } => {
+                Ok(TokenMetadata { name, symbol, decimals, source: MetadataSource::Alchemy, logo })
+            },
+            _ => Err(MCPError::InvalidTokenContract(
+                format!("{} has no ERC20 metadata", Self::checksum(&token_address))
+            )),
+        }
+    }
+
+    /// EIP-55 checksummed form of an address, used for every address in responses
+    pub fn checksum(address: &Address) -> String {
+        ethers::utils::to_checksum(address, None)
+    }
+
+    /// Convert a raw token amount to whole units. Zero-decimal tokens are already in
+    /// whole units and are returned unscaled. Divides in steps of at most 10^28 so any
+    /// `decimals` is safe; digits beyond Decimal's 28-digit scale round away.
+    pub fn scale_token_amount(raw: Decimal, decimals: u8) -> Decimal {
+        let mut value = raw;
+        let mut remaining = decimals as u32;
+        while remaining > 0 {
+            let step = remaining.min(MAX_DECIMAL_SCALE);
+            value /= Decimal::from_i128_with_scale(10i128.pow(step), 0);
+            remaining -= step;
+        }
+        value
+    }
+
+    /// `raw / 10^decimals` computed on U256, keeping at most 28 fractional digits.
+    /// None when the whole part exceeds Decimal's range.
+    pub fn raw_to_decimal(raw: U256, decimals: u8) -> Option<Decimal> {
+        let decimals = decimals as u32;
+        let (whole, fraction) = match Self::checked_exp10(decimals) {
+            Some(divisor) => raw.div_mod(divisor),
+            None => (U256::zero(), raw),
+        };
+        let fraction_digits = decimals.min(MAX_DECIMAL_SCALE);
+        // Below 10^28 after truncation, so it fits a Decimal mantissa
+        let fraction = Self::checked_exp10(decimals - fraction_digits)
+            .map(|divisor| fraction / divisor)
+            .unwrap_or_default();
+        let fraction = Decimal::from_i128_with_scale(fraction.as_u128() as i128, fraction_digits);
+        Self::u256_to_decimal(whole)?.checked_add(fraction)
+    }
+
+    /// `10^exponent`, or None when it exceeds U256
+    pub fn checked_exp10(exponent: u32) -> Option<U256> {
+        (exponent <= MAX_U256_EXP10).then(|| U256::exp10(exponent as usize))
     }
 
+    /// Least output accepted for a quote of `amount_out` at `slippage` percent tolerance
+    pub fn minimum_received(amount_out: Decimal, slippage: Decimal) -> Decimal {
+        amount_out * (dec!(100) - slippage) / dec!(100)
+    }
 
+    /// Round a balance half-up to `decimals_display` fractional digits for display
+    pub fn format_balance(balance: Decimal, decimals_display: u32) -> String {
+        balance
+            .round_dp_with_strategy(decimals_display, RoundingStrategy::MidpointAwayFromZero)
+            .to_string()
+    }
 
     /// Get token info from known tokens or return defaults
     fn get_known_token_info(&self, token_addr: &str) -> (String, String, u8) {
-        match token_addr.to_lowercase().as_str() {
-            "0xa0b86a33e6441b8c4c8c0c4c8c0c4c8c0c4c8c0c" => ("USD Coin".to_string(), "USDC".to_string(), 6),
+        Self::known_token_info(token_addr).unwrap_or_else(|| {
+            // Decimals are a guess here, so formatted amounts may be wrong
+            warn!(token_address = %token_addr, "Unknown token, using default metadata (18 decimals)");
+            ("Token".to_string(), "TOKEN".to_string(), 18)
+        })
+    }
+
+    /// Metadata for well-known mainnet tokens
+    fn known_token_info(token_addr: &str) -> Option<(String, String, u8)> {
+        let info = match token_addr.to_lowercase().as_str() {
+            "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48" => ("USD Coin".to_string(), "USDC".to_string(), 6),
             "0xdac17f958d2ee523a2206206994597c13d831ec7" => ("Tether USD".to_string(), "USDT".to_string(), 6),
             "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2" => ("Wrapped Ether".to_string(), "WETH".to_string(), 18),
             "0x6b175474e89094c44da98b954eedeac495271d0f" => ("Dai Stablecoin".to_string(), "DAI".to_string(), 18),
@@ -199,11 +2327,12 @@ impl EthereumClient {
             "0x7d1afa7b718fb893db30a3abc0cfc608aacfebb0" => ("Polygon".to_string(), "MATIC".to_string(), 18),
             "0x4fabb145d64652a948d72533023f6e7a623c7c53" => ("Binance USD".to_string(), "BUSD".to_string(), 18),
             "0x95ad61b0a150d79219dcf64e1e6cc01f0b64c4ce" => ("Shiba Inu".to_string(), "SHIB".to_string(), 18),
-            _ => ("Token".to_string(), "TOKEN".to_string(), 18), // Default for unknown tokens
-        }
+            _ => return None,
+        };
+        Some(info)
     }
 
-    async fn get_token_info(&self, token_address: Address) -> Result<(String, String, u8), MCPError> {
+    pub(crate) async fn get_token_info(&self, token_address: Address, block: Option<BlockId>) -> Result<(String, String, u8), MCPError> {
         // Get token name
         let name_selector = [0x06, 0xfd, 0xde, 0x03]; // name()
         let name_data = ethers::types::Bytes::from(name_selector);
@@ -213,7 +2342,8 @@ impl EthereumClient {
             ..Default::default()
         };
         
-        let name_result = self.provider.call(&name_tx.into(), None).await?;
+        let name_result = self.provider.call(&name_tx.into(), block).await?;
+        Self::ensure_metadata_response(&name_result, token_address, "name")?;
         let name = self.parse_string_from_bytes(&name_result)?;
 
         // Get token symbol
@@ -225,7 +2355,8 @@ impl EthereumClient {
             ..Default::default()
         };
         
-        let symbol_result = self.provider.call(&symbol_tx.into(), None).await?;
+        let symbol_result = self.provider.call(&symbol_tx.into(), block).await?;
+        Self::ensure_metadata_response(&symbol_result, token_address, "symbol")?;
         let symbol = self.parse_string_from_bytes(&symbol_result)?;
 
         // Get token decimals
@@ -237,13 +2368,37 @@ impl EthereumClient {
             ..Default::default()
         };
         
-        let decimals_result = self.provider.call(&decimals_tx.into(), None).await?;
+        let decimals_result = self.provider.call(&decimals_tx.into(), block).await?;
+        Self::ensure_metadata_response(&decimals_result, token_address, "decimals")?;
+        if decimals_result.len() != 32 {
+            return Err(MCPError::InvalidTokenContract(
+                "decimals() did not return a single uint".to_string()
+            ));
+        }
+        // Amounts are scaled by 10^decimals, which must fit in a U256
         let decimals_uint = U256::from(decimals_result.as_ref());
+        if decimals_uint > U256::from(MAX_U256_EXP10) {
+            return Err(MCPError::InvalidTokenContract(format!(
+                "decimals() returned {}, more than the supported {}", decimals_uint, MAX_U256_EXP10
+            )));
+        }
         let decimals = decimals_uint.as_u32() as u8;
 
         Ok((name, symbol, decimals))
     }
 
+    /// Contracts without ERC20 metadata (and addresses without code) answer `0x`;
+    /// report that explicitly instead of decoding it as garbage or 0 decimals
+    fn ensure_metadata_response(bytes: &[u8], token_address: Address, method: &str) -> Result<(), MCPError> {
+        if bytes.is_empty() {
+            debug!(token_address = %format!("0x{:x}", token_address), method, "Empty metadata response");
+            return Err(MCPError::InvalidTokenContract(
+                "contract does not implement ERC20 metadata".to_string()
+            ));
+        }
+        Ok(())
+    }
+
     /// Parse string from contract call result bytes
     fn parse_string_from_bytes(&self, bytes: &[u8]) -> Result<String, MCPError> {
         if bytes.len() < 32 {
@@ -269,52 +2424,64 @@ impl EthereumClient {
 
     // Price endpoints removed to simplify code; swap simulation uses on-chain reserves only.
     
-    pub async fn simulate_swap(&self, from_token: &str, to_token: &str, amount: Decimal, slippage: Decimal) -> Result<SwapSimulation, MCPError> {
-        info!(
-            from_token = %from_token,
-            to_token = %to_token,
-            amount = %amount,
-            slippage = %slippage,
-            "Starting Uniswap V3 swap simulation (Quoter v1)"
-        );
+    /// Simulate a swap. When `balance_owner` is set, the owner's `from_token` balance must cover `amount`.
+    /// `native` flags (from, to) legs that stand for native ETH; the tokens are then WETH
+    /// and the route shows `ETH` at those ends.
+    #[instrument(
+        skip(self, balance_owner),
+        fields(from_token = %from_token, to_token = %to_token, amount = %amount, slippage = %slippage, fee_tier = tracing::field::Empty)
+    )]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn simulate_swap(&self, from_token: ParsedAddress, to_token: ParsedAddress, amount: Decimal, slippage: Decimal, balance_owner: Option<Address>, gas_multiplier: Option<Decimal>, native: (bool, bool), deadline_secs: u64) -> Result<SwapSimulation, MCPError> {
+        info!("Starting Uniswap swap simulation (V3 Quoter v1 and V2 router)");
 
-        // Validate token addresses
-        let from_addr = from_token.parse::<Address>()
-            .map_err(|_| MCPError::InvalidTokenContract(from_token.to_string()))?;
-        
-        let to_addr = to_token.parse::<Address>()
-            .map_err(|_| MCPError::InvalidTokenContract(to_token.to_string()))?;
+        let from_addr = from_token.address();
+        let to_addr = to_token.address();
+        let (from_native, to_native) = native;
+
+        if let Some(owner) = balance_owner {
+            if from_native {
+                self.ensure_sufficient_eth_balance(owner, amount).await?;
+            } else {
+                self.ensure_sufficient_balance(owner, from_addr, amount).await?;
+            }
+        }
 
         // Get current gas price from the network
         let gas_price = self.provider.get_gas_price().await
             .map_err(|e| MCPError::NetworkError(format!("Failed to get gas price: {}", e)))?;
         let gas_price_decimal = Decimal::from(gas_price.as_u128()) / dec!(1_000_000_000_000_000_000);
 
-        // Get token decimals
-        let (_, _, from_decimals) = self.get_known_token_info(from_token);
-        let (_, _, to_decimals) = self.get_known_token_info(to_token);
+        // Read real decimals (cache, then contract) so unknown tokens are not assumed to have 18
+        let (from_metadata, to_metadata) = tokio::join!(
+            self.resolve_token_metadata(from_addr, None),
+            self.resolve_token_metadata(to_addr, None),
+        );
+        debug!(
+            from_decimals = from_metadata.decimals,
+            from_source = ?from_metadata.source,
+            to_decimals = to_metadata.decimals,
+            to_source = ?to_metadata.source,
+            "Swap token decimals resolved"
+        );
+        let TokenMetadata { symbol: from_symbol, decimals: from_decimals, .. } = from_metadata;
+        let TokenMetadata { symbol: to_symbol, decimals: to_decimals, .. } = to_metadata;
 
-        // Convert amount to wei based on token decimals
-        let amount_wei = (amount * Decimal::from(10u128.pow(from_decimals as u32))).to_u128()
-            .ok_or_else(|| MCPError::InvalidAmount("Amount too large".to_string()))?;
+        // Convert amount to wei based on token decimals, rejecting digits the token cannot represent
+        let amount_wei = Self::token_amount_to_raw(amount, from_decimals, &from_symbol)?;
 
         // Get Uniswap V2 Router address
         let router_address = "0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D".parse::<Address>()
             .map_err(|_| MCPError::ConfigurationError("Invalid router address".to_string()))?;
-        
-        info!(
-            "🔍 DEBUG: Router address: 0x{:x}",
-            router_address
-        );
+        debug!(router_address = %format!("0x{:x}", router_address), "Using Uniswap V2 router for gas estimation");
 
-        // Prefer Uniswap V3 Quoter (quoteExactInputSingle) with fee tier fallbacks, then fallback to V2 reserves
-        let v3_fees: [u32; 3] = [3000, 500, 10000];
-        let mut amount_out_wei_opt: Option<u128> = None;
-        for fee in v3_fees.iter() {
+        // Quote Uniswap V3 (quoteExactInputSingle, with fee tier fallbacks) and V2 (getAmountsOut)
+        let mut v3_quote: Option<(u32, U256)> = None;
+        for fee in V3_FEE_TIERS.iter() {
             match self.v3_quote_exact_input_single(from_addr, to_addr, *fee, amount_wei).await {
-                Ok(v) if v > 0 => {
-                    info!(fee = *fee, amount_out_wei = v, "✅ V3 quoter success");
-                    amount_out_wei_opt = Some(v);
+                Ok(v) if !v.is_zero() => {
+                    debug!(fee = *fee, amount_out_wei = %v, "V3 quoter success");
+                    v3_quote = Some((*fee, v));
                     break;
                 },
                 Ok(_) => {
@@ -326,57 +2493,349 @@ impl EthereumClient {
             }
         }
 
-        let amount_out_wei = amount_out_wei_opt
-            .ok_or_else(|| MCPError::SwapSimulationFailed("Uniswap V3 quoter failed on all fee tiers".to_string()))?;
+        let v2_quote = match self.v2_get_amounts_out(router_address, from_addr, to_addr, amount_wei).await {
+            Ok(v) if !v.is_zero() => {
+                debug!(amount_out_wei = %v, "V2 getAmountsOut success");
+                Some(v)
+            },
+            Ok(_) => {
+                debug!("V2 getAmountsOut returned zero");
+                None
+            },
+            Err(e) => {
+                debug!(error = %e, "V2 getAmountsOut failed");
+                None
+            }
+        };
 
-        let amount_out_decimal = Decimal::from(amount_out_wei) / Decimal::from(10u128.pow(to_decimals as u32));
-        
+        let aggregator_quotes = self.aggregator_quotes(from_addr, to_addr, amount_wei).await;
+
+        // An aggregator only replaces Uniswap when it quotes strictly more
+        let best_aggregator = aggregator_quotes.iter()
+            .fold(None, |best: Option<&(SwapSource, AggregatorQuote)>, quote| match best {
+                Some(best) if best.1.amount_out >= quote.1.amount_out => Some(best),
+                _ => Some(quote),
+            });
+        let (source, fee_tier, amount_out_wei, aggregator_gas) = match (Self::select_best_quote(v3_quote, v2_quote), best_aggregator) {
+            (Some((_, _, uniswap_out)), Some((source, quote))) if quote.amount_out > uniswap_out => (*source, None, quote.amount_out, quote.gas_estimate),
+            (Some((source, fee_tier, uniswap_out)), _) => (source, fee_tier, uniswap_out, None),
+            (None, Some((source, quote))) => (*source, None, quote.amount_out, quote.gas_estimate),
+            (None, None) => return Err(MCPError::SwapSimulationFailed(
+                "Uniswap V3 quoter failed on all fee tiers, V2 getAmountsOut failed and no aggregator returned a quote".to_string()
+            )),
+        };
+        if let Some(fee) = fee_tier {
+            tracing::Span::current().record("fee_tier", fee);
+        }
         info!(
-            "📊 swap quote: amount_out_wei={}, to_decimals={}, amount_out_decimal={}",
-            amount_out_wei, to_decimals, amount_out_decimal
+            source = ?source,
+            v3_amount_out_wei = ?v3_quote.map(|(_, v)| v.to_string()),
+            v2_amount_out_wei = ?v2_quote.map(|v| v.to_string()),
+            aggregator_quotes = aggregator_quotes.len(),
+            "Best swap quote selected"
         );
-        
-        let amount_out_decimal = amount_out_decimal;
 
-        // Apply slippage tolerance
-        let slippage_factor = (dec!(100) - slippage) / dec!(100);
-        let final_amount_out = amount_out_decimal * slippage_factor;
+        let scale_out = |raw: U256| Self::raw_to_decimal(raw, to_decimals).ok_or_else(|| {
+            MCPError::SwapSimulationFailed(format!("Quote of {} raw {} units is out of range", raw, to_symbol))
+        });
+        let quotes = v3_quote
+            .map(|(fee, v)| (SwapSource::UniswapV3, Some(fee), v, None))
+            .into_iter()
+            .chain(v2_quote.map(|v| (SwapSource::UniswapV2, None, v, None)))
+            .chain(aggregator_quotes.iter().map(|(source, quote)| (*source, None, quote.amount_out, quote.gas_estimate)))
+            .map(|(quote_source, fee_tier, raw, gas_estimate)| Ok(SwapQuote {
+                source: quote_source,
+                amount_out: scale_out(raw)?,
+                fee_tier,
+                gas_estimate,
+                best: quote_source == source,
+            }))
+            .collect::<Result<Vec<_>, MCPError>>()?;
+
+        let amount_out_decimal = scale_out(amount_out_wei)?;
+        
+        debug!(
+            amount_out_wei = %amount_out_wei,
+            to_decimals = to_decimals,
+            amount_out_decimal = %amount_out_decimal,
+            "Swap quote received"
+        );
+        
+        let price_impact_percent = self
+            .calculate_price_impact(from_addr, to_addr, fee_tier, router_address, from_decimals, to_decimals, amount, amount_out_decimal)
+            .await;
+        let high_price_impact = price_impact_percent
+            .map(|impact| impact > HIGH_PRICE_IMPACT_THRESHOLD)
+            .unwrap_or(false);
+        if high_price_impact {
+            warn!(price_impact_percent = ?price_impact_percent, "Swap has high price impact");
+        }
 
+        let minimum_received = Self::minimum_received(amount_out_decimal, slippage);
 
         // Estimate gas usage using eth_estimateGas
-        let gas_estimate = self.estimate_swap_gas(from_addr, to_addr, amount_wei, router_address).await?;
+        let deadline = chrono::Utc::now().timestamp() as u64 + deadline_secs;
+        let gas_estimate = match aggregator_gas {
+            Some(gas) => gas,
+            None => self.estimate_swap_gas(from_addr, to_addr, amount_wei, router_address, deadline).await?,
+        };
+        let gas_limit = self.pad_gas_limit(U256::from(gas_estimate), gas_multiplier).as_u64();
         let total_cost = Decimal::from(gas_estimate) * gas_price_decimal;
 
+        let fee_on_transfer_warning = self
+            .detect_fee_on_transfer(from_addr, to_addr, amount_wei, balance_owner.unwrap_or(self.wallet_address()), router_address, deadline)
+            .await;
+        match fee_on_transfer_warning {
+            Some(true) => warn!("Swap quote is unrealizable: token takes a fee on transfer"),
+            Some(false) => {},
+            None => debug!("Fee-on-transfer check inconclusive"),
+        }
+
         let simulation = SwapSimulation {
-            from_token: from_token.to_string(),
-            to_token: to_token.to_string(),
+            from_token: Self::checksum(&from_addr),
+            to_token: Self::checksum(&to_addr),
             amount_in: amount,
-            amount_out: final_amount_out,
+            amount_out: amount_out_decimal,
+            minimum_received,
             gas_estimate,
+            gas_limit,
             gas_price: gas_price_decimal,
             total_cost,
-            route: vec![from_token.to_string(), to_token.to_string()],
+            total_cost_usd: None,
+            route: vec![RouteHop {
+                token_in: Self::checksum(&from_addr),
+                token_out: Self::checksum(&to_addr),
+                token_in_symbol: if from_native { "ETH".to_string() } else { from_symbol },
+                token_out_symbol: if to_native { "ETH".to_string() } else { to_symbol },
+                source,
+                fee_tier,
+            }],
+            route_addresses: vec![
+                if from_native { "ETH".to_string() } else { Self::checksum(&from_addr) },
+                if to_native { "ETH".to_string() } else { Self::checksum(&to_addr) },
+            ],
             slippage_tolerance: slippage,
+            price_impact_percent,
+            high_price_impact,
+            fee_on_transfer_warning,
+            deadline,
+            source,
+            quotes,
         };
 
         info!(
             amount_in = %simulation.amount_in,
             amount_out = %simulation.amount_out,
             gas_estimate = simulation.gas_estimate,
-            "Uniswap V3 swap simulation completed successfully"
+            source = ?simulation.source,
+            "Uniswap swap simulation completed successfully"
         );
 
         Ok(simulation)
     }
 
+    /// Price a token from Uniswap V3 by quoting one unit against WETH, converted to USD with the ETH price
+    #[instrument(skip(self), fields(token_address = %token_address, fee_tier = tracing::field::Empty))]
+    pub async fn get_onchain_price(&self, token_address: ParsedAddress) -> Result<OnchainPriceInfo, MCPError> {
+        let token_addr = token_address.address();
+        let weth_addr = self.network.weth_address().parse::<Address>()
+            .map_err(|_| MCPError::ConfigurationError("Invalid WETH address".to_string()))?;
+
+        let TokenMetadata { symbol, decimals, .. } = self.resolve_token_metadata(token_addr, None).await;
+
+        let (fee_tier, price_weth) = if token_addr == weth_addr {
+            (None, Decimal::ONE)
+        } else {
+            let one_unit = Self::checked_exp10(decimals as u32)
+                .ok_or_else(|| MCPError::PriceFetchFailed(format!("{} has too many decimals to quote: {}", symbol, decimals)))?;
+            let mut quote = None;
+            for fee in V3_FEE_TIERS.iter() {
+                match self.v3_quote_exact_input_single(token_addr, weth_addr, *fee, one_unit).await {
+                    Ok(v) if !v.is_zero() => {
+                        quote = Some((*fee, v));
+                        break;
+                    },
+                    Ok(_) => debug!(fee = *fee, "V3 quoter returned zero"),
+                    Err(e) => debug!(fee = *fee, error = %e, "V3 quoter failed"),
+                }
+            }
+            let (fee, amount_out_wei) = quote
+                .ok_or_else(|| MCPError::PriceFetchFailed(format!("No Uniswap V3 WETH pool quote for {}", token_address)))?;
+            tracing::Span::current().record("fee_tier", fee);
+            let price_weth = Self::raw_to_decimal(amount_out_wei, 18)
+                .ok_or_else(|| MCPError::PriceFetchFailed(format!("WETH quote for {} is out of range: {}", symbol, amount_out_wei)))?;
+            (Some(fee), price_weth)
+        };
+
+        // The on-chain rate stands alone; USD conversion is best-effort
+        let eth_price_usd = match self.get_price_from_alchemy(self.network.weth_address()).await {
+            Ok(price) => Some(price),
+            Err(e) => {
+                warn!(error = %e, "ETH price unavailable from Alchemy, trying the WETH/USDC pool");
+                match self.get_onchain_eth_price_usd(weth_addr).await {
+                    Ok(price) => Some(price),
+                    Err(e) => {
+                        warn!(error = %e, "ETH price unavailable, returning WETH rate only");
+                        None
+                    }
+                }
+            }
+        };
+
+        info!(price_weth = %price_weth, eth_price_usd = ?eth_price_usd, "On-chain price derived");
+
+        Ok(OnchainPriceInfo {
+            token_address: Self::checksum(&token_addr),
+            symbol,
+            fee_tier,
+            price_weth,
+            eth_price_usd,
+            price_usd: eth_price_usd.map(|eth| price_weth * eth),
+        })
+    }
+
+    /// USD price of one WETH from the first Uniswap V3 WETH/USDC pool that quotes it
+    async fn get_onchain_eth_price_usd(&self, weth_addr: Address) -> Result<Decimal, MCPError> {
+        let usdc_addr = USDC_ADDRESS.parse::<Address>()
+            .map_err(|_| MCPError::ConfigurationError("Invalid USDC address".to_string()))?;
+        for fee in V3_FEE_TIERS.iter() {
+            match self.v3_quote_exact_input_single(weth_addr, usdc_addr, *fee, U256::exp10(18)).await {
+                Ok(v) if !v.is_zero() => {
+                    return Self::raw_to_decimal(v, 6)
+                        .ok_or_else(|| MCPError::PriceFetchFailed(format!("WETH/USDC quote is out of range: {}", v)));
+                },
+                Ok(_) => debug!(fee = *fee, "V3 quoter returned zero"),
+                Err(e) => debug!(fee = *fee, error = %e, "V3 quoter failed"),
+            }
+        }
+        Err(MCPError::PriceFetchFailed("No Uniswap V3 WETH/USDC pool quote".to_string()))
+    }
+
+    /// Fail with InsufficientBalance when `owner` holds less than `amount` of `token`
+    async fn ensure_sufficient_balance(&self, owner: Address, token: Address, amount: Decimal) -> Result<(), MCPError> {
+        let token_balance = self.get_token_balance(owner, token, None, DEFAULT_DECIMALS_DISPLAY).await?;
+        let available = Self::scale_token_amount(token_balance.balance, token_balance.decimals);
+
+        if amount > available {
+            return Err(MCPError::InsufficientBalance(format!(
+                "0x{:x} holds {} {} but the swap needs {}",
+                owner, available, token_balance.symbol, amount
+            )));
+        }
+
+        debug!(owner = %format!("0x{:x}", owner), available = %available, amount = %amount, "Balance check passed");
+        Ok(())
+    }
+
+    /// Like ensure_sufficient_balance, for a native ETH input
+    async fn ensure_sufficient_eth_balance(&self, owner: Address, amount: Decimal) -> Result<(), MCPError> {
+        let balance_wei = self.provider.get_balance(owner, None).await?;
+        let available = Decimal::from(balance_wei.as_u128()) / WEI_PER_ETH;
+
+        if amount > available {
+            return Err(MCPError::InsufficientBalance(format!(
+                "0x{:x} holds {} ETH but the swap needs {}",
+                owner, available, amount
+            )));
+        }
+
+        debug!(owner = %format!("0x{:x}", owner), available = %available, amount = %amount, "ETH balance check passed");
+        Ok(())
+    }
+
+    /// Quote the swap on every configured aggregator concurrently; failing sources are logged and left out
+    async fn aggregator_quotes(&self, from_addr: Address, to_addr: Address, amount_wei: U256) -> Vec<(SwapSource, AggregatorQuote)> {
+        let chain_id = self.network.chain_id();
+        let quotes = futures::future::join_all(self.aggregators.iter().map(|(source, api_key)| async move {
+            match self.rpc_transport.aggregator_quote(*source, api_key, chain_id, from_addr, to_addr, amount_wei).await {
+                Ok(quote) if !quote.amount_out.is_zero() => {
+                    debug!(source = ?source, amount_out_wei = %quote.amount_out, gas_estimate = ?quote.gas_estimate, "Aggregator quote success");
+                    Some((*source, quote))
+                },
+                Ok(_) => {
+                    debug!(source = ?source, "Aggregator quote returned zero");
+                    None
+                },
+                Err(e) => {
+                    warn!(source = ?source, error = %e, "Aggregator quote failed, omitting source");
+                    None
+                },
+            }
+        })).await;
+        quotes.into_iter().flatten().collect()
+    }
+
+    /// Pick the larger of the V3 `(fee, amount_out)` and V2 `amount_out` quotes; V3 wins ties.
+    /// The fee tier is only set when V3 wins.
+    pub fn select_best_quote(v3_quote: Option<(u32, U256)>, v2_quote: Option<U256>) -> Option<(SwapSource, Option<u32>, U256)> {
+        match (v3_quote, v2_quote) {
+            (Some((_, v3)), Some(v2)) if v2 > v3 => Some((SwapSource::UniswapV2, None, v2)),
+            (Some((fee, v3)), _) => Some((SwapSource::UniswapV3, Some(fee), v3)),
+            (None, Some(v2)) => Some((SwapSource::UniswapV2, None, v2)),
+            (None, None) => None,
+        }
+    }
+
+    /// Compare the execution price against a 1-unit reference quote from the same pool:
+    /// the V3 fee tier when `fee` is set, otherwise the V2 router.
+    /// Returns None when the reference quote is unavailable.
+    #[allow(clippy::too_many_arguments)]
+    async fn calculate_price_impact(
+        &self,
+        from_addr: Address,
+        to_addr: Address,
+        fee: Option<u32>,
+        router_address: Address,
+        from_decimals: u8,
+        to_decimals: u8,
+        amount_in: Decimal,
+        amount_out: Decimal,
+    ) -> Option<Decimal> {
+        let Some(one_unit_wei) = Self::checked_exp10(from_decimals as u32) else {
+            warn!(from_decimals, "Token has too many decimals for a reference quote, skipping price impact");
+            return None;
+        };
+        let reference_quote = match fee {
+            Some(fee) => self.v3_quote_exact_input_single(from_addr, to_addr, fee, one_unit_wei).await,
+            None => self.v2_get_amounts_out(router_address, from_addr, to_addr, one_unit_wei).await,
+        };
+        let reference_out_wei = match reference_quote {
+            Ok(v) if !v.is_zero() => v,
+            Ok(_) => {
+                warn!(fee = ?fee, "Reference quote returned zero, skipping price impact");
+                return None;
+            },
+            Err(e) => {
+                warn!(fee = ?fee, error = %e, "Reference quote failed, skipping price impact");
+                return None;
+            }
+        };
+
+        let Some(reference_price) = Self::raw_to_decimal(reference_out_wei, to_decimals).filter(|price| !price.is_zero()) else {
+            warn!(reference_out_wei = %reference_out_wei, "Reference quote out of range, skipping price impact");
+            return None;
+        };
+        let execution_price = amount_out.checked_div(amount_in)?;
+        let impact = ((reference_price - execution_price) / reference_price * dec!(100)).max(Decimal::ZERO);
+
+        debug!(
+            reference_price = %reference_price,
+            execution_price = %execution_price,
+            price_impact_percent = %impact,
+            "Price impact calculated"
+        );
+
+        Some(impact.round_dp(4))
+    }
+
     /// Uniswap V3 Quoter v1: quoteExactInputSingle(address,address,uint24,uint256,uint160) → uint256 amountOut
     async fn v3_quote_exact_input_single(
         &self,
         token_in: Address,
         token_out: Address,
         fee: u32,
-        amount_in_wei: u128,
-    ) -> Result<u128, MCPError> {
+        amount_in_wei: U256,
+    ) -> Result<U256, MCPError> {
         use ethers::abi::{encode, Token};
 
         // Quoter v1 mainnet
@@ -392,78 +2851,97 @@ impl EthereumClient {
             Token::Address(token_in),
             Token::Address(token_out),
             Token::Uint(U256::from(fee as u64)),
-            Token::Uint(U256::from(amount_in_wei)),
+            Token::Uint(amount_in_wei),
             Token::Uint(U256::zero()), // sqrtPriceLimitX96 = 0 (no limit)
         ];
         let mut data = selector;
         data.extend_from_slice(&encode(&params));
 
-        let bytes = self.call_alchemy_eth_call(quoter, data).await?;
+        let bytes = self.rpc_transport.eth_call(quoter, data, None).await?;
         if bytes.len() < 32 { return Err(MCPError::SwapSimulationFailed("Invalid V3 quoter response".to_string())); }
 
-        // Parse single uint256 return (take last 16 bytes for u128)
-        let b = &bytes[bytes.len()-16..bytes.len()];
-        let mut arr = [0u8;16];
-        arr.copy_from_slice(b);
-        let amount_out = u128::from_be_bytes(arr);
-        Ok(amount_out)
+        // Single uint256 return
+        Ok(U256::from_big_endian(&bytes[..32]))
     }
 
+    /// Uniswap V2 router: getAmountsOut(uint256,address[]) → uint256[] amounts; returns the last amount
+    async fn v2_get_amounts_out(
+        &self,
+        router_address: Address,
+        token_in: Address,
+        token_out: Address,
+        amount_in_wei: U256,
+    ) -> Result<U256, MCPError> {
+        use ethers::abi::{decode, encode, ParamType, Token};
+
+        let mut data = ethers::utils::keccak256("getAmountsOut(uint256,address[])".as_bytes())[0..4].to_vec();
+        data.extend_from_slice(&encode(&[
+            Token::Uint(amount_in_wei),
+            Token::Array(vec![Token::Address(token_in), Token::Address(token_out)]),
+        ]));
 
-    async fn call_alchemy_eth_call(&self, to: Address, data: Vec<u8>) -> Result<Vec<u8>, MCPError> {
-        use serde_json::json;
-        
-        let client = reqwest::Client::new();
-        let url = "https://eth-mainnet.g.alchemy.com/v2/JZUYcRpkXq25weYd16Fuu";
-        
-        let request_body = json!({
-            "jsonrpc": "2.0",
-            "method": "eth_call",
-            "params": [
-                {
-                    "to": format!("0x{:x}", to),
-                    "data": format!("0x{}", hex::encode(&data))
-                },
-                "latest"
-            ],
-            "id": 1
-        });
-        
-        let response = client
-            .post(url)
-            .header("Content-Type", "application/json")
-            .json(&request_body)
-            .send()
-            .await
-            .map_err(|e| MCPError::NetworkError(format!("Failed to call Alchemy API: {}", e)))?;
-        
-        let response_text = response.text().await
-            .map_err(|e| MCPError::NetworkError(format!("Failed to read response: {}", e)))?;
-        
-        let response_json: serde_json::Value = serde_json::from_str(&response_text)
-            .map_err(|e| MCPError::NetworkError(format!("Failed to parse response: {}", e)))?;
-        
-        if let Some(result) = response_json.get("result") {
-            if let Some(result_str) = result.as_str() {
-                if result_str.starts_with("0x") {
-                    let hex_data = &result_str[2..];
-                    let bytes = hex::decode(hex_data)
-                        .map_err(|e| MCPError::NetworkError(format!("Failed to decode hex: {}", e)))?;
-                    return Ok(bytes);
-                }
-            }
+        let bytes = self.rpc_transport.eth_call(router_address, data, None).await?;
+        let amounts = decode(&[ParamType::Array(Box::new(ParamType::Uint(256)))], &bytes)
+            .map_err(|e| MCPError::SwapSimulationFailed(format!("Invalid V2 getAmountsOut response: {}", e)))?;
+
+        amounts.into_iter()
+            .next()
+            .and_then(|token| token.into_array())
+            .and_then(|amounts| amounts.last().cloned())
+            .and_then(|token| token.into_uint())
+            .ok_or_else(|| MCPError::SwapSimulationFailed("Empty V2 getAmountsOut response".to_string()))
+    }
+
+    /// Flag fee-on-transfer tokens: the pair receives less than amountIn, so swapExactTokensForTokens
+    /// reverts while the SupportingFeeOnTransferTokens variant goes through. None when the check is
+    /// inconclusive: both revert (typically the sender lacks the balance or allowance) or a simulation fails.
+    async fn detect_fee_on_transfer(&self, from_token: Address, to_token: Address, amount_in: U256, sender: Address, router_address: Address, deadline: u64) -> Option<bool> {
+        let standard = self.router_swap_succeeds(
+            "swapExactTokensForTokens(uint256,uint256,address[],address,uint256)",
+            from_token, to_token, amount_in, U256::zero(), sender, router_address, deadline,
+        ).await?;
+        if standard {
+            return Some(false);
         }
-        
-        if let Some(error) = response_json.get("error") {
-            return Err(MCPError::SwapSimulationFailed(format!("Alchemy API error: {}", error)));
+
+        let supporting = self.router_swap_succeeds(
+            "swapExactTokensForTokensSupportingFeeOnTransferTokens(uint256,uint256,address[],address,uint256)",
+            from_token, to_token, amount_in, U256::zero(), sender, router_address, deadline,
+        ).await?;
+        debug!(standard, supporting, "Fee-on-transfer check");
+        supporting.then_some(true)
+    }
+
+    /// Whether a Uniswap V2 router swap with the given signature passes eth_estimateGas;
+    /// None when the estimate fails for another reason than a revert
+    #[allow(clippy::too_many_arguments)]
+    async fn router_swap_succeeds(&self, signature: &str, from_token: Address, to_token: Address, amount_in: U256, amount_out_min: U256, sender: Address, router_address: Address, deadline: u64) -> Option<bool> {
+        use ethers::abi::{encode, Token};
+
+        let mut data = ethers::utils::keccak256(signature.as_bytes())[0..4].to_vec();
+        data.extend_from_slice(&encode(&[
+            Token::Uint(amount_in),
+            Token::Uint(amount_out_min),
+            Token::Array(vec![Token::Address(from_token), Token::Address(to_token)]),
+            Token::Address(sender),
+            Token::Uint(deadline.into()),
+        ]));
+
+        match self.rpc_transport.estimate_gas(sender, router_address, data).await {
+            Ok(_) => Some(true),
+            Err(MCPError::GasEstimationFailed(error)) => {
+                debug!(signature, error = %error, "Router swap simulation reverted");
+                Some(false)
+            },
+            Err(e) => {
+                debug!(signature, error = %e, "Router swap simulation failed");
+                None
+            },
         }
-        
-        Err(MCPError::SwapSimulationFailed("No result in Alchemy response".to_string()))
     }
 
-    async fn estimate_swap_gas(&self, from_token: Address, to_token: Address, amount_in: u128, router_address: Address) -> Result<u64, MCPError> {
+    async fn estimate_swap_gas(&self, from_token: Address, to_token: Address, amount_in: U256, router_address: Address, deadline: u64) -> Result<u64, MCPError> {
         use ethers::abi::{encode, Token};
-        use serde_json::json;
         
         // Build swapExactTokensForTokens transaction data
         let function_selector = "swapExactTokensForTokens(uint256,uint256,address[],address,uint256)";
@@ -474,12 +2952,11 @@ impl EthereumClient {
         // - amountOutMin: 0 (we're just estimating gas)
         // - path: [from_token, to_token]
         // - to: wallet address (use a dummy address for estimation)
-        // - deadline: current timestamp + 1 hour
+        // - deadline: unix timestamp after which the router rejects the swap
         let wallet_address = "0x0000000000000000000000000000000000000001".parse::<Address>().unwrap();
-        let deadline = chrono::Utc::now().timestamp() as u64 + 3600; // 1 hour from now
         
         let params = vec![
-            Token::Uint(amount_in.into()),
+            Token::Uint(amount_in),
             Token::Uint(0u64.into()), // amountOutMin = 0 for estimation
             Token::Array(vec![Token::Address(from_token), Token::Address(to_token)]),
             Token::Address(wallet_address),
@@ -490,65 +2967,101 @@ impl EthereumClient {
         let mut data = selector;
         data.extend_from_slice(&encoded_params);
         
-        // Call eth_estimateGas
-        let client = reqwest::Client::new();
-        let url = "https://eth-mainnet.g.alchemy.com/v2/JZUYcRpkXq25weYd16Fuu";
-        
-        let request_body = json!({
-            "jsonrpc": "2.0",
-            "method": "eth_estimateGas",
-            "params": [
-                {
-                    "to": format!("0x{:x}", router_address),
-                    "data": format!("0x{}", hex::encode(&data)),
-                    "from": format!("0x{:x}", wallet_address)
-                }
-            ],
-            "id": 1
-        });
-        
-        let response = client
-            .post(url)
-            .header("Content-Type", "application/json")
-            .json(&request_body)
-            .send()
-            .await
-            .map_err(|e| MCPError::NetworkError(format!("Failed to estimate gas: {}", e)))?;
-        
-        let response_text = response.text().await
-            .map_err(|e| MCPError::NetworkError(format!("Failed to read gas estimation response: {}", e)))?;
-        
-        let response_json: serde_json::Value = serde_json::from_str(&response_text)
-            .map_err(|e| MCPError::NetworkError(format!("Failed to parse gas estimation response: {}", e)))?;
-        
-        if let Some(result) = response_json.get("result") {
-            if let Some(result_str) = result.as_str() {
-                if result_str.starts_with("0x") {
-                    let hex_data = &result_str[2..];
-                    let gas_u64 = u64::from_str_radix(&hex_data, 16)
-                        .map_err(|e| MCPError::NetworkError(format!("Failed to parse gas estimate: {}", e)))?;
-                    
-                    info!(
-                        gas_estimate = gas_u64,
-                        "Gas estimation completed via eth_estimateGas"
-                    );
-                    
-                    return Ok(gas_u64);
-                }
-            }
+        match self.rpc_transport.estimate_gas(wallet_address, router_address, data).await {
+            Ok(gas_u64) => {
+                info!(
+                    gas_estimate = gas_u64,
+                    "Gas estimation completed via eth_estimateGas"
+                );
+                Ok(gas_u64)
+            },
+            Err(MCPError::GasEstimationFailed(error)) => {
+                warn!(
+                    error = %error,
+                    "Gas estimation failed, using fallback estimate"
+                );
+                // Fallback to typical gas estimate if eth_estimateGas fails
+                Ok(200000u64)
+            },
+            Err(e) => Err(e),
         }
-        
-        if let Some(error) = response_json.get("error") {
-            warn!(
-                error = ?error,
-                "Gas estimation failed, using fallback estimate"
-            );
-            // Fallback to typical gas estimate if eth_estimateGas fails
-            return Ok(200000u64);
+    }
+
+
+}
+
+/// Split an inclusive block range into consecutive sub-ranges of at most `chunk_size` blocks
+pub fn split_block_range(from_block: u64, to_block: u64, chunk_size: u64) -> Vec<(u64, u64)> {
+    let chunk_size = chunk_size.max(1);
+    let mut chunks = Vec::new();
+    let mut start = from_block;
+    while start <= to_block {
+        let end = start.saturating_add(chunk_size - 1).min(to_block);
+        chunks.push((start, end));
+        if end == u64::MAX {
+            break;
         }
-        
-        Err(MCPError::SwapSimulationFailed("No result in gas estimation response".to_string()))
+        start = end + 1;
+    }
+    chunks
+}
+
+/// Parse `HTTP_HEADERS` (comma-separated `Name=Value` pairs) into a header map
+pub fn parse_http_headers(value: &str) -> Result<reqwest::header::HeaderMap, MCPError> {
+    use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+    let mut headers = HeaderMap::new();
+    for pair in value.split(',').map(|pair| pair.trim()).filter(|pair| !pair.is_empty()) {
+        let (name, header_value) = pair.split_once('=')
+            .ok_or_else(|| MCPError::ConfigurationError(format!("HTTP_HEADERS entry '{}' must be Name=Value", pair)))?;
+        let name = HeaderName::from_bytes(name.trim().as_bytes())
+            .map_err(|e| MCPError::ConfigurationError(format!("HTTP_HEADERS: invalid header name '{}': {}", name.trim(), e)))?;
+        let header_value = HeaderValue::from_str(header_value.trim())
+            .map_err(|e| MCPError::ConfigurationError(format!("HTTP_HEADERS: invalid value for '{}': {}", name, e)))?;
+        headers.insert(name, header_value);
     }
+    Ok(headers)
+}
+
+/// The wallet key from the environment: `PRIVATE_KEY`, else the JSON keystore at
+/// `KEYSTORE_PATH` decrypted with `KEYSTORE_PASSWORD`. When neither is set the placeholder
+/// key is returned and the client runs in read-only mode with signing tools disabled.
+pub fn private_key_from_env() -> Result<(String, KeySource), MCPError> {
+    let var = |name: &str| std::env::var(name).ok();
+    resolve_private_key(var("PRIVATE_KEY"), var("KEYSTORE_PATH"), var("KEYSTORE_PASSWORD"))
+}
+
+/// `private_key_from_env` with the variables passed in
+pub fn resolve_private_key(
+    private_key: Option<String>,
+    keystore_path: Option<String>,
+    keystore_password: Option<String>,
+) -> Result<(String, KeySource), MCPError> {
+    let non_empty = |value: Option<String>| value.filter(|value| !value.trim().is_empty());
 
+    if let Some(key) = non_empty(private_key) {
+        if key.trim_start_matches("0x") != PLACEHOLDER_PRIVATE_KEY.trim_start_matches("0x") {
+            return Ok((key, KeySource::Env));
+        }
+    } else if let Some(path) = non_empty(keystore_path) {
+        let password = keystore_password.ok_or_else(|| {
+            MCPError::ConfigurationError("KEYSTORE_PATH is set but KEYSTORE_PASSWORD is not".to_string())
+        })?;
+        let wallet = LocalWallet::decrypt_keystore(&path, password)
+            .map_err(|e| MCPError::ConfigurationError(format!("Failed to decrypt keystore {}: {}", path, e)))?;
+        info!(keystore = %path, address = ?wallet.address(), "Loaded wallet key from keystore");
+        return Ok((format!("0x{}", hex::encode(wallet.signer().to_bytes())), KeySource::Keystore));
+    }
+
+    warn!("PRIVATE_KEY not set, starting in read-only mode: signing tools are disabled");
+    Ok((PLACEHOLDER_PRIVATE_KEY.to_string(), KeySource::None))
+}
 
+/// Split `RPC_URLS` into endpoints, keeping their order
+pub fn parse_rpc_urls(value: &str) -> Vec<String> {
+    value.split(',')
+        .map(|url| url.trim())
+        .filter(|url| !url.is_empty())
+        .map(|url| url.to_string())
+        .collect()
 }
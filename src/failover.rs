@@ -0,0 +1,207 @@
+use crate::error::MCPError;
+use crate::ws::ReconnectingWs;
+use async_trait::async_trait;
+use ethers::providers::{Http, HttpClientError, JsonRpcClient, JsonRpcError, ProviderError, RpcError};
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+use url::Url;
+
+/// URL reported by an offline transport; it is never contacted
+const OFFLINE_URL: &str = "http://offline.invalid/";
+
+/// Reconnect policy for `ws://` / `wss://` endpoints
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WsReconnect {
+    pub max_reconnects: u32,
+    /// Delay before the first attempt; doubled for each further one
+    pub base_delay: Duration,
+}
+
+/// One RPC endpoint
+#[derive(Debug, Clone)]
+enum Endpoint {
+    Http(Http),
+    Ws(Arc<ReconnectingWs>),
+}
+
+impl Endpoint {
+    fn url(&self) -> &Url {
+        match self {
+            Endpoint::Http(http) => http.url(),
+            Endpoint::Ws(ws) => ws.url(),
+        }
+    }
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, ProviderError>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        match self {
+            Endpoint::Http(http) => http.request(method, params).await.map_err(Into::into),
+            Endpoint::Ws(ws) => ws.request(method, params).await.map_err(Into::into),
+        }
+    }
+}
+
+/// Transport over an ordered list of HTTP and WebSocket RPC endpoints.
+///
+/// Requests go to the last endpoint that answered; transport failures (connection
+/// errors, timeouts, unparseable responses, WebSocket reconnects exhausted) move on to
+/// the next one. JSON-RPC error responses are returned as-is since another node would
+/// answer the same way.
+#[derive(Debug, Clone)]
+pub struct FailoverTransport {
+    transports: Arc<Vec<Endpoint>>,
+    current: Arc<AtomicUsize>,
+    /// Round trips attempted, failed-over attempts included
+    request_count: Arc<AtomicU64>,
+    /// In offline mode, the chain ID of the mock chain that answers instead of any endpoint
+    offline_chain_id: Option<u64>,
+}
+
+impl FailoverTransport {
+    /// HTTP(S) endpoints only; use `connect` when `urls` may include WebSocket endpoints
+    pub fn new(urls: &[String]) -> Result<Self, MCPError> {
+        Self::with_client(urls, reqwest::Client::new())
+    }
+
+    /// Like `new`, sending every request through `client` so endpoints share its
+    /// connection pool, timeout and headers
+    pub fn with_client(urls: &[String], client: reqwest::Client) -> Result<Self, MCPError> {
+        let transports = urls.iter()
+            .map(|url| Self::http_endpoint(url, &client))
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_endpoints(transports)
+    }
+
+    /// Like `with_client`, also accepting `ws://` / `wss://` endpoints, which are connected
+    /// here and rebuilt with `reconnect` when they drop
+    pub async fn connect(urls: &[String], client: reqwest::Client, reconnect: WsReconnect) -> Result<Self, MCPError> {
+        let mut transports = Vec::with_capacity(urls.len());
+        for url in urls {
+            let endpoint = if is_websocket_url(url) {
+                Endpoint::Ws(Arc::new(ReconnectingWs::connect(url, reconnect.max_reconnects, reconnect.base_delay).await?))
+            } else {
+                Self::http_endpoint(url, &client)?
+            };
+            transports.push(endpoint);
+        }
+        Self::from_endpoints(transports)
+    }
+
+    fn http_endpoint(url: &str, client: &reqwest::Client) -> Result<Endpoint, MCPError> {
+        if is_websocket_url(url) {
+            return Err(MCPError::ConfigurationError(format!("WebSocket RPC URL needs FailoverTransport::connect: {}", url)));
+        }
+        Url::parse(url)
+            .map(|url| Endpoint::Http(Http::new_with_client(url, client.clone())))
+            .map_err(|e| MCPError::ConfigurationError(format!("Invalid RPC URL: {}", e)))
+    }
+
+    fn from_endpoints(transports: Vec<Endpoint>) -> Result<Self, MCPError> {
+        if transports.is_empty() {
+            return Err(MCPError::ConfigurationError("At least one RPC URL is required".to_string()));
+        }
+
+        Ok(Self {
+            transports: Arc::new(transports),
+            current: Arc::new(AtomicUsize::new(0)),
+            request_count: Arc::new(AtomicU64::new(0)),
+            offline_chain_id: None,
+        })
+    }
+
+    /// Transport answering every request from `crate::mock` as chain `chain_id`; nothing is sent
+    pub fn offline(chain_id: u64) -> Self {
+        let placeholder = Url::parse(OFFLINE_URL).expect("valid placeholder URL");
+        Self {
+            transports: Arc::new(vec![Endpoint::Http(Http::new(placeholder))]),
+            current: Arc::new(AtomicUsize::new(0)),
+            request_count: Arc::new(AtomicU64::new(0)),
+            offline_chain_id: Some(chain_id),
+        }
+    }
+
+    /// Endpoint requests are currently sent to
+    pub fn url(&self) -> &Url {
+        self.transports[self.current.load(Ordering::Relaxed)].url()
+    }
+
+    /// Counter of round trips made through this transport, shared with its clones
+    pub fn request_counter(&self) -> Arc<AtomicU64> {
+        self.request_count.clone()
+    }
+
+    /// Number of configured endpoints
+    pub fn len(&self) -> usize {
+        self.transports.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transports.is_empty()
+    }
+}
+
+/// Whether `url` is a `ws://` or `wss://` endpoint
+pub fn is_websocket_url(url: &str) -> bool {
+    url.starts_with("ws://") || url.starts_with("wss://")
+}
+
+/// Whether another endpoint might succeed where this one failed
+fn is_transport_failure(error: &ProviderError) -> bool {
+    error.as_error_response().is_none()
+}
+
+#[async_trait]
+impl JsonRpcClient for FailoverTransport {
+    type Error = ProviderError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        if let Some(chain_id) = self.offline_chain_id {
+            crate::logging::record_rpc_call(&self.request_count);
+            let params = serde_json::to_value(&params)
+                .map_err(|err| HttpClientError::SerdeJson { err, text: format!("{:?}", params) })?;
+            return match crate::mock::rpc_response(method, &params, chain_id) {
+                Ok(result) => serde_json::from_value(result.clone())
+                    .map_err(|err| HttpClientError::SerdeJson { err, text: result.to_string() }.into()),
+                Err(message) => Err(HttpClientError::JsonRpcError(JsonRpcError { code: -32000, message, data: None }).into()),
+            };
+        }
+
+        let start = self.current.load(Ordering::Relaxed);
+        let count = self.transports.len();
+        let mut last_error = None;
+
+        for offset in 0..count {
+            let index = (start + offset) % count;
+            let transport = &self.transports[index];
+            crate::logging::record_rpc_call(&self.request_count);
+
+            match transport.request(method, &params).await {
+                Ok(result) => {
+                    if index != start {
+                        info!(rpc_host = ?transport.url().host_str(), method, "Failed over to RPC endpoint");
+                        self.current.store(index, Ordering::Relaxed);
+                    }
+                    return Ok(result);
+                },
+                Err(e) if is_transport_failure(&e) => {
+                    warn!(rpc_host = ?transport.url().host_str(), method, error = %e, "RPC endpoint failed");
+                    last_error = Some(e);
+                },
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(last_error.expect("at least one transport"))
+    }
+}
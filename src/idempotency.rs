@@ -0,0 +1,78 @@
+use crate::error::MCPError;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+/// Default lifetime of a cached result, in seconds
+pub const DEFAULT_IDEMPOTENCY_TTL_SECS: u64 = 3600;
+
+enum Entry {
+    /// A call with this key is running, or ended without knowing whether it broadcast
+    InFlight { started_at: Instant },
+    Completed { result: Value, stored_at: Instant },
+}
+
+/// Outcome of reserving an idempotency key
+#[derive(Debug, PartialEq)]
+pub enum Reservation {
+    /// First use of the key; the caller should run the tool and then `complete` or `release`
+    New,
+    /// The key already completed; this is the stored result
+    Replay(Value),
+}
+
+/// Results of signing tool calls keyed by (tool, client-supplied `idempotency_key`), so a
+/// client retrying after a timeout gets the original tx hash instead of a second broadcast
+pub struct IdempotencyCache {
+    entries: Mutex<HashMap<(String, String), Entry>>,
+    ttl: Duration,
+}
+
+impl IdempotencyCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self { entries: Mutex::new(HashMap::new()), ttl }
+    }
+
+    /// Claim `key` for `tool`. A key whose call is still running (or timed out) is rejected,
+    /// since its transaction may already be on the way.
+    pub fn reserve(&self, tool: &str, key: &str) -> Result<Reservation, MCPError> {
+        let mut entries = self.entries.lock().unwrap();
+        let ttl = self.ttl;
+        entries.retain(|_, entry| match entry {
+            Entry::InFlight { started_at } => started_at.elapsed() < ttl,
+            Entry::Completed { stored_at, .. } => stored_at.elapsed() < ttl,
+        });
+
+        let cache_key = (tool.to_string(), key.to_string());
+        match entries.get(&cache_key) {
+            Some(Entry::Completed { result, .. }) => {
+                info!(tool_name = %tool, idempotency_key = %key, "Replaying cached result for idempotency key");
+                Ok(Reservation::Replay(result.clone()))
+            },
+            Some(Entry::InFlight { .. }) => Err(MCPError::ValidationError(format!(
+                "A {} call with idempotency_key '{}' is in progress or ended with an unknown outcome; \
+                 check get_pending_transactions before retrying with a new key",
+                tool, key
+            ))),
+            None => {
+                entries.insert(cache_key, Entry::InFlight { started_at: Instant::now() });
+                Ok(Reservation::New)
+            },
+        }
+    }
+
+    /// Store the successful result for `key`
+    pub fn complete(&self, tool: &str, key: &str, result: &Value) {
+        self.entries.lock().unwrap().insert(
+            (tool.to_string(), key.to_string()),
+            Entry::Completed { result: result.clone(), stored_at: Instant::now() },
+        );
+    }
+
+    /// Forget `key` after a failure that broadcast nothing, so the client may retry with it
+    pub fn release(&self, tool: &str, key: &str) {
+        self.entries.lock().unwrap().remove(&(tool.to_string(), key.to_string()));
+    }
+}
@@ -4,6 +4,18 @@ pub mod ethereum;
 pub mod types;
 pub mod error;
 pub mod logging;
+pub mod network;
+pub mod circuit_breaker;
+pub mod address;
+pub mod rate_limit;
+pub mod request_limit;
+pub mod failover;
+pub mod ws;
+pub mod transport;
+pub mod config;
+pub mod idempotency;
+pub mod decimal_json;
+pub mod mock;
 
 #[cfg(test)]
 mod tests;
@@ -1,32 +1,116 @@
 use tracing::{info, warn, error, debug};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 use uuid::Uuid;
+use std::cell::Cell;
 use std::collections::HashMap;
+use std::future::Future;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing_appender::rolling::RollingFileAppender;
+
+tokio::task_local! {
+    /// Outbound RPC round trips made by the request being served on this task
+    static REQUEST_RPC_CALLS: Cell<u64>;
+}
+
+/// Run `future` with its own RPC call count, returning its output and the round trips it
+/// made. Requests served concurrently each count only their own calls.
+pub async fn count_rpc_calls<F: Future>(future: F) -> (F::Output, u64) {
+    REQUEST_RPC_CALLS.scope(Cell::new(0), async {
+        let output = future.await;
+        (output, REQUEST_RPC_CALLS.with(Cell::get))
+    }).await
+}
+
+/// Record one outbound RPC round trip in `total` and against the current request, if any
+pub fn record_rpc_call(total: &AtomicU64) {
+    total.fetch_add(1, Ordering::Relaxed);
+    let _ = REQUEST_RPC_CALLS.try_with(|calls| calls.set(calls.get() + 1));
+}
+
+/// Final result of a request, recorded for log-based SLO tracking
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestOutcome {
+    Success,
+    /// Failed with the given JSON-RPC error code
+    Failure { error_code: i32 },
+}
+
+impl RequestOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RequestOutcome::Success => "success",
+            RequestOutcome::Failure { .. } => "failure",
+        }
+    }
+
+    pub fn error_code(&self) -> Option<i32> {
+        match self {
+            RequestOutcome::Success => None,
+            RequestOutcome::Failure { error_code } => Some(*error_code),
+        }
+    }
+}
 
 /// Request context containing request ID and other metadata
 #[derive(Debug, Clone)]
 pub struct RequestContext {
+    /// Internal trace id, unique per request
     pub request_id: String,
+    /// JSON-RPC `id` sent by the client, for correlating with client-side logs
+    pub client_request_id: Option<String>,
     pub method: String,
     pub start_time: std::time::Instant,
     pub metadata: HashMap<String, String>,
+    /// Set once the request has been handled
+    pub outcome: Option<RequestOutcome>,
+    /// Attempts beyond the first made while serving the request
+    pub retry_count: u32,
+    /// Outbound RPC round trips made while serving the request
+    pub rpc_calls: u64,
 }
 
 impl RequestContext {
     pub fn new(method: String) -> Self {
         Self {
             request_id: Uuid::new_v4().to_string(),
+            client_request_id: None,
             method,
             start_time: std::time::Instant::now(),
             metadata: HashMap::new(),
+            outcome: None,
+            retry_count: 0,
+            rpc_calls: 0,
         }
     }
 
+    /// Attach the client's JSON-RPC id; null ids (notifications) are left unset
+    pub fn with_client_request_id(mut self, id: &serde_json::Value) -> Self {
+        self.client_request_id = match id {
+            serde_json::Value::Null => None,
+            serde_json::Value::String(s) => Some(s.clone()),
+            other => Some(other.to_string()),
+        };
+        self
+    }
+
     pub fn with_metadata(mut self, key: String, value: String) -> Self {
         self.metadata.insert(key, value);
         self
     }
 
+    pub fn record_outcome(&mut self, outcome: RequestOutcome) {
+        self.outcome = Some(outcome);
+    }
+
+    pub fn record_retry(&mut self) {
+        self.retry_count += 1;
+    }
+
+    pub fn record_rpc_calls(&mut self, rpc_calls: u64) {
+        self.rpc_calls = rpc_calls;
+    }
+
     pub fn duration(&self) -> std::time::Duration {
         self.start_time.elapsed()
     }
@@ -38,66 +122,125 @@ pub fn init_logging() -> anyhow::Result<()> {
     let env_filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new("info"));
 
+    // Choose log format based on environment variable
+    let use_json = std::env::var("LOG_FORMAT").unwrap_or_default() == "json";
+
     // Create JSON format logger
-    let json_layer = tracing_subscriber::fmt::layer()
-        .json()
-        .with_current_span(true)
-        .with_span_list(true);
+    let json_layer = use_json.then(|| {
+        tracing_subscriber::fmt::layer()
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .with_writer(console_writer)
+    });
 
     // Create human-readable format logger (for development)
-    let human_layer = tracing_subscriber::fmt::layer()
-        .with_target(true)
-        .with_thread_ids(true)
-        .with_thread_names(true)
-        .with_file(true)
-        .with_line_number(true);
+    let human_layer = (!use_json).then(|| {
+        tracing_subscriber::fmt::layer()
+            .with_target(true)
+            .with_thread_ids(true)
+            .with_thread_names(true)
+            .with_file(true)
+            .with_line_number(true)
+            .with_writer(console_writer)
+    });
 
-    // Choose log format based on environment variable
-    let use_json = std::env::var("LOG_FORMAT").unwrap_or_default() == "json";
+    // Optionally persist logs to a daily-rolling file
+    let file_appender = match std::env::var("LOG_FILE") {
+        Ok(path) if !path.is_empty() => Some(file_appender(&path)?),
+        _ => None,
+    };
+    let (json_file_layer, human_file_layer) = match file_appender {
+        Some(appender) if use_json => (
+            Some(tracing_subscriber::fmt::layer()
+                .json()
+                .with_current_span(true)
+                .with_span_list(true)
+                .with_writer(appender)),
+            None,
+        ),
+        Some(appender) => (
+            None,
+            Some(tracing_subscriber::fmt::layer()
+                .with_target(true)
+                .with_ansi(false)
+                .with_writer(appender)),
+        ),
+        None => (None, None),
+    };
 
-    if use_json {
-        tracing_subscriber::registry()
-            .with(env_filter)
-            .with(json_layer)
-            .init();
-    } else {
-        tracing_subscriber::registry()
-            .with(env_filter)
-            .with(human_layer)
-            .init();
-    }
+    tracing_subscriber::registry()
+        .with(env_filter)
+        .with(json_layer)
+        .with(human_layer)
+        .with(json_file_layer)
+        .with(human_file_layer)
+        .init();
 
-    info!("Logging initialized successfully");
+    info!(log_file = ?std::env::var("LOG_FILE").ok(), "Logging initialized successfully");
     Ok(())
 }
 
+/// Writer for console log output.
+///
+/// Always stderr: stdout is reserved for the JSON-RPC stream when running over
+/// the stdio transport, and interleaved log lines would break MCP hosts.
+pub(crate) fn console_writer() -> std::io::Stderr {
+    std::io::stderr()
+}
+
+/// Build a daily-rolling file appender from a log file path
+fn file_appender(path: &str) -> anyhow::Result<RollingFileAppender> {
+    let path = Path::new(path);
+    let directory = path.parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let file_name = path.file_name()
+        .ok_or_else(|| anyhow::anyhow!("LOG_FILE must include a file name: {}", path.display()))?;
+
+    Ok(tracing_appender::rolling::daily(directory, file_name))
+}
+
 /// Log request start
 pub fn log_request_start(ctx: &RequestContext) {
     info!(
         request_id = %ctx.request_id,
+        client_request_id = ctx.client_request_id.as_deref().unwrap_or("none"),
         method = %ctx.method,
         "Request started"
     );
 }
 
-/// Log request completion
-pub fn log_request_complete(ctx: &RequestContext, success: bool) {
+/// Log request completion with the recorded outcome; a missing outcome is logged as `unknown`
+pub fn log_request_complete(ctx: &RequestContext) {
     let duration = ctx.duration();
+    let success = ctx.outcome == Some(RequestOutcome::Success);
+    let outcome = ctx.outcome.map(|outcome| outcome.as_str()).unwrap_or("unknown");
+    let error_code = ctx.outcome.and_then(|outcome| outcome.error_code());
     
     if success {
         info!(
             request_id = %ctx.request_id,
+            client_request_id = ctx.client_request_id.as_deref().unwrap_or("none"),
             method = %ctx.method,
             duration_ms = duration.as_millis(),
             success = success,
+            outcome = outcome,
+            retry_count = ctx.retry_count,
+            rpc_calls = ctx.rpc_calls,
             "Request completed successfully"
         );
     } else {
         tracing::warn!(
             request_id = %ctx.request_id,
+            client_request_id = ctx.client_request_id.as_deref().unwrap_or("none"),
             method = %ctx.method,
             duration_ms = duration.as_millis(),
             success = success,
+            outcome = outcome,
+            error_code = ?error_code,
+            retry_count = ctx.retry_count,
+            rpc_calls = ctx.rpc_calls,
             "Request completed with errors"
         );
     }
@@ -134,6 +277,29 @@ pub fn log_tool_result(ctx: &RequestContext, tool_name: &str, success: bool, dur
     }
 }
 
+/// Log a failed attempt that is about to be retried after `delay_secs`
+pub fn log_retry_attempt(ctx: &RequestContext, operation: &str, attempt: u32, max_retries: u32, delay_secs: u64, error: &dyn std::error::Error) {
+    warn!(
+        request_id = %ctx.request_id,
+        operation = %operation,
+        attempt = attempt,
+        max_retries = max_retries,
+        delay_secs = delay_secs,
+        error = %error,
+        "Retrying after transient failure"
+    );
+}
+
+/// Log an operation that succeeded after one or more retries
+pub fn log_retry_success(ctx: &RequestContext, operation: &str, total_attempts: u32) {
+    info!(
+        request_id = %ctx.request_id,
+        operation = %operation,
+        total_attempts = total_attempts,
+        "Operation succeeded after retrying"
+    );
+}
+
 /// Log Ethereum operation
 pub fn log_ethereum_operation(ctx: &RequestContext, operation: &str, details: &str) {
     debug!(
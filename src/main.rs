@@ -1,6 +1,7 @@
 use anyhow::Result;
-use std::env;
 use tracing::info;
+use web3_wallet::config::Config;
+use web3_wallet::ethereum::private_key_from_env;
 use web3_wallet::mcp_server::MCPServer;
 use web3_wallet::logging::init_logging;
 
@@ -11,19 +12,19 @@ async fn main() -> Result<()> {
 
     info!("Starting Web3 Wallet MCP Server");
 
-    // Get configuration from environment
-    let rpc_url = "https://eth-mainnet.g.alchemy.com/v2/JZUYcRpkXq25weYd16Fuu".to_string();
+    // Get configuration from CONFIG_FILE and the environment
+    let config = Config::load()?;
     
-    let private_key = env::var("PRIVATE_KEY")
-        .expect("PRIVATE_KEY environment variable is required");
+    let (private_key, _) = private_key_from_env()?;
 
     info!(
-        rpc_url = %rpc_url,
+        rpc_url = %config.rpc_url,
+        network = %config.network,
         "Connecting to Ethereum RPC"
     );
 
     // Create and start the MCP server
-    let server = MCPServer::new(rpc_url, private_key).await?;
+    let server = MCPServer::from_config(&config, private_key).await?;
     
     info!("MCP Server initialized successfully");
     
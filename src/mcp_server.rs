@@ -1,51 +1,169 @@
 use crate::error::MCPError;
-use crate::error::ErrorHandler;
-use crate::types::{MCPRequest, MCPResponse, MCPErrorResponse, ToolCall};
+use crate::error::{ErrorHandler, ErrorRecovery};
+use crate::types::{MCPRequest, MCPResponse, MCPErrorResponse, ToolCall, WalletMode};
 use crate::ethereum::EthereumClient;
-use crate::tools::ToolHandler;
-use crate::logging::{RequestContext, log_request_start, log_request_complete, log_error};
+use crate::config::{Config, FileConfig};
+use crate::tools::{ToolHandler, SIGNING_TOOLS};
+use crate::logging::{RequestContext, RequestOutcome, count_rpc_calls, log_request_start, log_request_complete, log_error};
+use crate::request_limit::{BoundedLine, DEFAULT_MAX_REQUEST_BYTES, read_bounded_line, request_too_large};
 use serde_json::{Value, json};
-use tracing::{info, error, debug, instrument};
+use tokio::io::{AsyncBufRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tracing::{info, warn, error, debug, instrument};
+
+/// Read-only `(uri, name, description)` resources served by `resources/list` and `resources/read`
+const RESOURCES: [(&str, &str, &str); 3] = [
+    ("wallet://address", "Wallet address", "Address of the server wallet and whether PRIVATE_KEY is configured"),
+    ("wallet://network", "Network", "Configured network and its chain ID"),
+    ("wallet://config", "Server configuration", "Network, default slippage and the enabled tools"),
+];
+
+/// Most requests accepted in one JSON-RPC batch
+pub const MAX_BATCH_SIZE: usize = 100;
 
 pub struct MCPServer {
     tool_handler: ToolHandler,
+    /// Longest stdio request line, from `MAX_REQUEST_BODY_BYTES`
+    max_line_bytes: usize,
 }
 
 impl MCPServer {
     pub async fn new(rpc_url: String, private_key: String) -> Result<Self, MCPError> {
-        let ethereum_client = EthereumClient::new(rpc_url, private_key).await?;
-        let tool_handler = ToolHandler::new(ethereum_client);
+        let config = Config::resolve(FileConfig { rpc_url: Some(rpc_url), ..FileConfig::default() })?;
+        Self::from_config(&config, private_key).await
+    }
+
+    /// Create a server from configuration loaded with `Config::load`
+    pub async fn from_config(config: &Config, private_key: String) -> Result<Self, MCPError> {
+        let ethereum_client = EthereumClient::with_config(config, private_key).await?;
+        let tool_handler = ToolHandler::with_config(ethereum_client, config)?;
         
-        Ok(Self { tool_handler })
+        Ok(Self { tool_handler, max_line_bytes: config.max_request_bytes })
     }
 
+    /// Create a server around an existing tool handler, e.g. one with a mock transport in tests
+    pub fn with_tool_handler(tool_handler: ToolHandler) -> Self {
+        Self { tool_handler, max_line_bytes: DEFAULT_MAX_REQUEST_BYTES }
+    }
+
+    /// Serve newline-delimited JSON-RPC over stdin/stdout until stdin closes
     pub async fn run(&self) -> Result<(), MCPError> {
-        info!("MCP Server is running and ready to accept requests");
-        
-        // In a real implementation, this would listen on a socket or HTTP endpoint
-        // For now, we'll just keep the server running
+        let max_line_bytes = self.max_line_bytes;
+        info!(max_line_bytes = max_line_bytes, "MCP Server is running and ready to accept requests on stdio");
+
+        self.serve_lines(BufReader::new(tokio::io::stdin()), tokio::io::stdout(), max_line_bytes).await
+    }
+
+    /// Answer one JSON-RPC message (a request or a batch) per input line, writing one
+    /// response line each. Lines over `max_line_bytes` are discarded and answered with
+    /// an invalid request error.
+    pub async fn serve_lines<R, W>(&self, mut reader: R, mut writer: W, max_line_bytes: usize) -> Result<(), MCPError>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
         loop {
-            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+            let response = match read_bounded_line(&mut reader, max_line_bytes).await? {
+                BoundedLine::Eof => return Ok(()),
+                BoundedLine::TooLong => {
+                    warn!(max_line_bytes = max_line_bytes, "Stdio request line exceeds limit");
+                    Some(serde_json::to_value(ErrorHandler::handle_error(request_too_large(max_line_bytes), None))?)
+                },
+                BoundedLine::Line(line) => self.handle_line(&line).await?,
+            };
+
+            if let Some(response) = response {
+                let mut text = serde_json::to_vec(&response)?;
+                text.push(b'\n');
+                writer.write_all(&text).await?;
+                writer.flush().await?;
+            }
+        }
+    }
+
+    /// Dispatch one stdio line; `None` when nothing should be written back
+    async fn handle_line(&self, line: &[u8]) -> Result<Option<Value>, MCPError> {
+        if line.trim_ascii().is_empty() {
+            return Ok(None);
         }
+
+        let payload = match serde_json::from_slice::<Value>(line) {
+            Ok(payload) => payload,
+            Err(e) => {
+                let error = MCPError::InvalidJsonRpcRequest(format!("Invalid JSON: {}", e));
+                return Ok(Some(serde_json::to_value(ErrorHandler::handle_error(error, None))?));
+            }
+        };
+
+        // A JSON array is a batch; anything else is a single request
+        if let Value::Array(batch) = payload {
+            return Ok(match self.handle_batch(batch).await {
+                Ok(responses) if responses.is_empty() => None,
+                Ok(responses) => Some(serde_json::to_value(responses)?),
+                Err(e) => {
+                    warn!("MCP batch rejected: {}", e);
+                    Some(serde_json::to_value(ErrorHandler::handle_error(e, None))?)
+                }
+            });
+        }
+
+        let request = match serde_json::from_value::<MCPRequest>(payload) {
+            Ok(request) => request,
+            Err(e) => {
+                let error = MCPError::InvalidJsonRpcRequest(e.to_string());
+                return Ok(Some(serde_json::to_value(ErrorHandler::handle_error(error, None))?));
+            }
+        };
+
+        let id = request.id.clone();
+        Ok(match self.handle_message(request).await {
+            Ok(response) => response.map(serde_json::to_value).transpose()?,
+            Err(e) => {
+                error!("MCP request failed: {}", e);
+                let mut response = ErrorHandler::handle_error(e, None);
+                response.id = id;
+                Some(serde_json::to_value(response)?)
+            }
+        })
     }
 
-    #[instrument(skip(self), fields(request_id = %request.request_context.as_ref().map(|ctx| ctx.request_id.as_str()).unwrap_or("unknown")))]
+    #[instrument(skip(self), fields(request_id = %request.request_context.as_ref().map(|ctx| ctx.request_id.as_str()).unwrap_or("unknown"), client_request_id = %request.id))]
     pub async fn handle_request(&self, mut request: MCPRequest) -> Result<MCPResponse, MCPError> {
         // Create request context
-        let ctx = request.request_context.take().unwrap_or_else(|| {
+        let mut ctx = request.request_context.take().unwrap_or_else(|| {
             RequestContext::new(request.method.clone())
         });
+        if ctx.client_request_id.is_none() {
+            ctx = ctx.with_client_request_id(&request.id);
+        }
         
         log_request_start(&ctx);
+        let client_request_id = request.id.clone();
         
-        let result = match request.method.as_str() {
+        let (result, rpc_calls) = count_rpc_calls(async { match request.method.as_str() {
             "tools/list" => {
                 debug!(request_id = %ctx.request_id, "Handling tools/list request");
                 self.handle_tools_list(request.id, &ctx).await
             },
             "tools/call" => {
                 debug!(request_id = %ctx.request_id, "Handling tools/call request");
-                self.handle_tools_call(request.id, request.params, &ctx).await
+                self.handle_tools_call(request.id, request.params, &mut ctx).await
+            },
+            "resources/list" => {
+                debug!(request_id = %ctx.request_id, "Handling resources/list request");
+                Ok(self.handle_resources_list(request.id))
+            },
+            "resources/read" => {
+                debug!(request_id = %ctx.request_id, "Handling resources/read request");
+                self.handle_resources_read(request.id, request.params)
+            },
+            "ping" => {
+                debug!(request_id = %ctx.request_id, "Handling ping request");
+                Ok(MCPResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id: request.id,
+                    result: Some(json!({})),
+                    error: None,
+                })
             },
             _ => {
                 let error_msg = format!("Method not found: {}", request.method);
@@ -63,28 +181,165 @@ impl MCPServer {
                     error: Some(error),
                 })
             }
-        };
+        }}).await;
+
+        ctx.record_rpc_calls(rpc_calls);
 
         // Log request completion
         match &result {
             Ok(response) => {
-                let success = response.error.is_none();
-                log_request_complete(&ctx, success);
+                ctx.record_outcome(match &response.error {
+                    None => RequestOutcome::Success,
+                    Some(error) => RequestOutcome::Failure { error_code: error.code },
+                });
+                log_request_complete(&ctx);
             },
             Err(e) => {
                 log_error(&ctx, e, "Request processing failed");
-                log_request_complete(&ctx, false);
+                ctx.record_outcome(RequestOutcome::Failure { error_code: e.error_code() });
+                log_request_complete(&ctx);
                 
-                // Use error handler to generate better error response
-                return Ok(ErrorHandler::handle_error(e.clone(), Some(&ctx.request_id)));
+                // Use error handler to generate better error response; the internal request id
+                // stays in the error data for log correlation, the client gets its own id back
+                let mut response = ErrorHandler::handle_error(e.clone(), Some(&ctx.request_id));
+                response.id = client_request_id;
+                return Ok(response);
             }
         }
 
         result
     }
 
+    /// Handle a single JSON-RPC message, returning None for notifications.
+    /// `notifications/*` methods are acknowledged without dispatch; other notifications are executed and their response dropped.
+    pub async fn handle_message(&self, request: MCPRequest) -> Result<Option<MCPResponse>, MCPError> {
+        if request.method.starts_with("notifications/") {
+            debug!(method = %request.method, "Received client notification");
+            return Ok(None);
+        }
+
+        let is_notification = request.is_notification();
+        let response = self.handle_request(request).await?;
+        Ok(if is_notification { None } else { Some(response) })
+    }
+
+    /// Handle a JSON-RPC batch, returning responses in request order.
+    /// Notifications are executed but produce no response entry.
+    pub async fn handle_batch(&self, batch: Vec<Value>) -> Result<Vec<MCPResponse>, MCPError> {
+        if batch.is_empty() {
+            return Err(MCPError::InvalidJsonRpcRequest("Batch must not be empty".to_string()));
+        }
+        if batch.len() > MAX_BATCH_SIZE {
+            return Err(MCPError::InvalidJsonRpcRequest(format!(
+                "Batch of {} requests exceeds limit of {}", batch.len(), MAX_BATCH_SIZE
+            )));
+        }
+
+        let requests = batch.into_iter()
+            .map(serde_json::from_value::<MCPRequest>)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| MCPError::InvalidJsonRpcRequest(format!("Malformed batch: {}", e)))?;
+
+        info!(batch_size = requests.len(), "Handling JSON-RPC batch");
+
+        let mut responses = Vec::with_capacity(requests.len());
+        for request in requests {
+            if let Some(response) = self.handle_message(request).await? {
+                responses.push(response);
+            }
+        }
+
+        Ok(responses)
+    }
+
     #[instrument(skip(self), fields(request_id = %ctx.request_id))]
     async fn handle_tools_list(&self, id: Value, ctx: &RequestContext) -> Result<MCPResponse, MCPError> {
+        Ok(MCPResponse {
+            jsonrpc: "2.0".to_string(),
+            id,
+            result: Some(json!({
+                "tools": self.tool_definitions()
+            })),
+            error: None,
+        })
+    }
+
+    fn handle_resources_list(&self, id: Value) -> MCPResponse {
+        let resources: Vec<Value> = RESOURCES.iter()
+            .map(|(uri, name, description)| json!({
+                "uri": uri,
+                "name": name,
+                "description": description,
+                "mimeType": "application/json"
+            }))
+            .collect();
+
+        MCPResponse {
+            jsonrpc: "2.0".to_string(),
+            id,
+            result: Some(json!({ "resources": resources })),
+            error: None,
+        }
+    }
+
+    fn handle_resources_read(&self, id: Value, params: Value) -> Result<MCPResponse, MCPError> {
+        let uri = params.get("uri")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::MissingParameter("uri".to_string()))?;
+        let value = self.read_resource(uri)?;
+
+        Ok(MCPResponse {
+            jsonrpc: "2.0".to_string(),
+            id,
+            result: Some(json!({
+                "contents": [
+                    {
+                        "uri": uri,
+                        "mimeType": "application/json",
+                        "text": serde_json::to_string_pretty(&value)?
+                    }
+                ]
+            })),
+            error: None,
+        })
+    }
+
+    /// Whether the server wallet can sign
+    pub fn wallet_mode(&self) -> WalletMode {
+        self.tool_handler.ethereum_client().wallet_mode()
+    }
+
+    /// Current value of a `wallet://` resource
+    pub fn read_resource(&self, uri: &str) -> Result<Value, MCPError> {
+        let client = self.tool_handler.ethereum_client();
+        let network = client.network();
+
+        match uri {
+            "wallet://address" => Ok(json!({
+                "address": ethers::utils::to_checksum(&client.wallet_address(), None),
+                "configured": client.wallet_configured()
+            })),
+            "wallet://network" => Ok(json!({
+                "network": network.name(),
+                "chain_id": network.chain_id()
+            })),
+            "wallet://config" => {
+                let enabled_tools: Vec<Value> = self.tool_definitions().into_iter()
+                    .filter_map(|tool| tool.get("name").cloned())
+                    .collect();
+                Ok(json!({
+                    "network": network.name(),
+                    "chain_id": network.chain_id(),
+                    "default_slippage_percent": self.tool_handler.default_slippage(),
+                    "enabled_tools": enabled_tools
+                }))
+            },
+            _ => Err(MCPError::ValidationError(format!("Unknown resource: {}", uri))),
+        }
+    }
+
+    /// Definitions of the enabled tools, shared by `tools/list` and the HTTP `/tools` route
+    pub fn tool_definitions(&self) -> Vec<Value> {
         let tools = json!([
             {
                 "name": "get_balance",
@@ -99,6 +354,138 @@ impl MCPServer {
                         "token_address": {
                             "type": "string",
                             "description": "Optional token contract address"
+                        },
+                        "block_number": {
+                            "type": "string",
+                            "description": "Optional block number (decimal or 0x-hex) or tag (latest, pending, safe, finalized) to query at (default: latest)"
+                        },
+                        "confirmations": {
+                            "type": "integer",
+                            "description": "Read this many blocks behind latest for reorg safety (default: 0); cannot be combined with block_number"
+                        },
+                        "include_usd_value": {
+                            "type": "boolean",
+                            "description": "Include USD values per token and a portfolio total (default: false)"
+                        },
+                        "decimals_display": {
+                            "type": "integer",
+                            "description": "Fractional digits in balance_formatted, rounded half-up (default: 6)"
+                        },
+                        "fields": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Return only these dotted field paths, e.g. [\"native_balance\", \"token_balances.symbol\"] (default: full response)"
+                        }
+                    },
+                    "required": ["address"]
+                }
+            },
+            {
+                "name": "get_balances_for_addresses",
+                "description": "Get ETH and ERC20 token balances for several wallet addresses at once",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "addresses": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Wallet addresses to query (max 100)"
+                        },
+                        "token_addresses": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Optional token contract addresses (default: common tokens)"
+                        },
+                        "decimals_display": {
+                            "type": "integer",
+                            "description": "Fractional digits in balance_formatted, rounded half-up (default: 6)"
+                        },
+                        "fields": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Return only these dotted field paths, e.g. [\"native_balance\", \"token_balances.symbol\"] (default: full response)"
+                        }
+                    },
+                    "required": ["addresses"]
+                }
+            },
+            {
+                "name": "get_balance_changes",
+                "description": "Compare a wallet's ETH and token balances at two blocks and return the change in each",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "address": {
+                            "type": "string",
+                            "description": "Wallet address to query"
+                        },
+                        "from_block": {
+                            "type": "string",
+                            "description": "Earlier block number (decimal or 0x-hex) or tag (latest, safe, finalized)"
+                        },
+                        "to_block": {
+                            "type": "string",
+                            "description": "Later block number or tag (default: latest)"
+                        },
+                        "token_addresses": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Token contract addresses to compare (default: the get_balance token set)"
+                        }
+                    },
+                    "required": ["address", "from_block"]
+                }
+            },
+            {
+                "name": "get_all_token_balances",
+                "description": "Get ETH and every non-zero ERC20 balance for a wallet address (Alchemy enhanced API)",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "address": {
+                            "type": "string",
+                            "description": "Wallet address to query"
+                        },
+                        "include_usd_value": {
+                            "type": "boolean",
+                            "description": "Include USD values per token and a portfolio total (default: false)"
+                        },
+                        "decimals_display": {
+                            "type": "integer",
+                            "description": "Fractional digits in balance_formatted, rounded half-up (default: 6)"
+                        },
+                        "fields": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Return only these dotted field paths, e.g. [\"native_balance\", \"token_balances.symbol\"] (default: full response)"
+                        }
+                    },
+                    "required": ["address"]
+                }
+            },
+            {
+                "name": "get_portfolio",
+                "description": "Get a wallet's ETH and token balances with current USD prices, per-token values and a total, in one call",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "address": {
+                            "type": "string",
+                            "description": "Wallet address to query"
+                        },
+                        "token_addresses": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Token contract addresses to include (default: every non-zero holding)"
+                        },
+                        "decimals_display": {
+                            "type": "integer",
+                            "description": "Fractional digits in balance_formatted, rounded half-up (default: 6)"
+                        },
+                        "fields": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Return only these dotted field paths, e.g. [\"native_balance\", \"token_balances.symbol\"] (default: full response)"
                         }
                     },
                     "required": ["address"]
@@ -107,6 +494,48 @@ impl MCPServer {
             {
                 "name": "get_token_price",
                 "description": "Get current token price in USD and ETH",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "token_address": {
+                            "type": "string",
+                            "description": "Token contract address"
+                        },
+                        "source": {
+                            "type": "string",
+                            "enum": ["alchemy", "onchain", "auto"],
+                            "description": "Price source: alchemy, onchain (Uniswap V3), or auto (default; Alchemy, falling back to a degraded on-chain estimate)"
+                        }
+                    },
+                    "required": ["token_address"]
+                }
+            },
+            {
+                "name": "get_onchain_price",
+                "description": "Get a token's price from Uniswap V3 by quoting one unit against WETH, with a USD estimate",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "token_address": {
+                            "type": "string",
+                            "description": "Token contract address"
+                        }
+                    },
+                    "required": ["token_address"]
+                }
+            },
+            {
+                "name": "get_eth_price",
+                "description": "Get the current USD price of ETH (priced via WETH, no token address needed)",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {},
+                    "required": []
+                }
+            },
+            {
+                "name": "get_token_metadata",
+                "description": "Get a token's name, symbol, decimals and logo URL (Alchemy, falling back to on-chain data)",
                 "inputSchema": {
                     "type": "object",
                     "properties": {
@@ -118,19 +547,41 @@ impl MCPServer {
                     "required": ["token_address"]
                 }
             },
+            {
+                "name": "get_erc1155_balance",
+                "description": "Get the balance of one token id in an ERC1155 multi-token contract",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "contract_address": {
+                            "type": "string",
+                            "description": "ERC1155 contract address"
+                        },
+                        "owner": {
+                            "type": "string",
+                            "description": "Address whose balance to read"
+                        },
+                        "token_id": {
+                            "type": "string",
+                            "description": "Token id as 0x-hex or decimal string"
+                        }
+                    },
+                    "required": ["contract_address", "owner", "token_id"]
+                }
+            },
             {
                 "name": "swap_tokens",
-                "description": "Simulate a token swap on Uniswap",
+                "description": "Simulate a token swap on Uniswap, compared against any configured aggregators (0x, 1inch)",
                 "inputSchema": {
                     "type": "object",
                     "properties": {
                         "from_token": {
                             "type": "string",
-                            "description": "Source token contract address"
+                            "description": "Source token contract address, or ETH for native ETH"
                         },
                         "to_token": {
                             "type": "string",
-                            "description": "Destination token contract address"
+                            "description": "Destination token contract address, or ETH for native ETH"
                         },
                         "amount": {
                             "type": "string",
@@ -138,30 +589,474 @@ impl MCPServer {
                         },
                         "slippage_tolerance": {
                             "type": "string",
-                            "description": "Slippage tolerance percentage (default: 0.5)"
+                            "description": format!(
+                                "Slippage tolerance percentage (default: {}, max: {})",
+                                self.tool_handler.default_slippage(),
+                                self.tool_handler.max_slippage()
+                            )
+                        },
+                        "wallet_address": {
+                            "type": "string",
+                            "description": "Wallet whose from_token balance must cover the amount (default: server wallet)"
+                        },
+                        "deadline_secs": {
+                            "type": "integer",
+                            "description": "Seconds from now until the router rejects the swap (default: 1200, max: 86400)"
+                        },
+                        "skip_balance_check": {
+                            "type": "boolean",
+                            "description": "Skip the balance check for pure price discovery (default: false)"
+                        },
+                        "quote_currency": {
+                            "type": "string",
+                            "enum": ["eth", "usd"],
+                            "description": "Currency of the gas cost; usd adds total_cost_usd (default: eth)"
+                        },
+                        "gas_multiplier": {
+                            "type": "string",
+                            "description": "Optional gas limit padding over the raw estimate, e.g. \"1.5\" (default: GAS_LIMIT_MULTIPLIER or 1.2)"
                         }
                     },
                     "required": ["from_token", "to_token", "amount"]
                 }
+            },
+            {
+                "name": "simulate_token_transfer",
+                "description": "Estimate the gas cost of an ERC20 transfer from the server wallet without sending it",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "token_address": {
+                            "type": "string",
+                            "description": "Token contract address"
+                        },
+                        "to": {
+                            "type": "string",
+                            "description": "Recipient address"
+                        },
+                        "amount": {
+                            "type": "string",
+                            "description": "Amount of tokens to transfer (as decimal string)"
+                        },
+                        "gas_multiplier": {
+                            "type": "string",
+                            "description": "Optional gas limit padding over the raw estimate, e.g. \"1.5\" (default: GAS_LIMIT_MULTIPLIER or 1.2)"
+                        }
+                    },
+                    "required": ["token_address", "to", "amount"]
+                }
+            },
+            {
+                "name": "transfer_token",
+                "description": "Sign and broadcast an ERC20 transfer from the server wallet (simulated first, requires PRIVATE_KEY)",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "token_address": {
+                            "type": "string",
+                            "description": "Token contract address"
+                        },
+                        "to": {
+                            "type": "string",
+                            "description": "Recipient address"
+                        },
+                        "amount": {
+                            "type": "string",
+                            "description": "Amount of tokens to transfer (as decimal string)"
+                        },
+                        "gas_multiplier": {
+                            "type": "string",
+                            "description": "Optional gas limit padding over the raw estimate, e.g. \"1.5\" (default: GAS_LIMIT_MULTIPLIER or 1.2)"
+                        },
+                        "idempotency_key": {
+                            "type": "string",
+                            "description": "Optional client key (1-128 chars); repeating a successful call with the same key returns the cached result instead of broadcasting again"
+                        }
+                    },
+                    "required": ["token_address", "to", "amount"]
+                }
+            },
+            {
+                "name": "lookup_ens_name",
+                "description": "Reverse-resolve a wallet address to its primary ENS name",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "address": {
+                            "type": "string",
+                            "description": "Wallet address to look up"
+                        }
+                    },
+                    "required": ["address"]
+                }
+            },
+            {
+                "name": "batch_lookup_ens",
+                "description": "Reverse-resolve several wallet addresses to their primary ENS names in one call",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "addresses": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Wallet addresses to look up (max 100)"
+                        }
+                    },
+                    "required": ["addresses"]
+                }
+            },
+            {
+                "name": "get_code",
+                "description": "Check whether an address is a contract and return its bytecode size",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "address": {
+                            "type": "string",
+                            "description": "Address to inspect"
+                        }
+                    },
+                    "required": ["address"]
+                }
+            },
+            {
+                "name": "get_pending_transactions",
+                "description": "Show transactions from an address that are broadcast but not yet mined (nonce gap, plus mempool contents when the provider exposes them)",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "address": {
+                            "type": "string",
+                            "description": "Sender address"
+                        }
+                    },
+                    "required": ["address"]
+                }
+            },
+            {
+                "name": "get_storage_at",
+                "description": "Read a raw 32-byte storage slot of a contract",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "address": {
+                            "type": "string",
+                            "description": "Contract address"
+                        },
+                        "slot": {
+                            "type": "string",
+                            "description": "Storage slot as 0x-hex or decimal string"
+                        }
+                    },
+                    "required": ["address", "slot"]
+                }
+            },
+            {
+                "name": "detect_proxy",
+                "description": "Check whether a contract is an EIP-1967 proxy and return the implementation it delegates to",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "address": {
+                            "type": "string",
+                            "description": "Contract address"
+                        }
+                    },
+                    "required": ["address"]
+                }
+            },
+            {
+                "name": "get_fee_history",
+                "description": "Get recent per-block base fees and priority-fee percentiles for gas trend analysis",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "block_count": {
+                            "type": "integer",
+                            "description": "Number of recent blocks (default: 10, max: 1024)"
+                        },
+                        "reward_percentiles": {
+                            "type": "array",
+                            "items": { "type": "number" },
+                            "description": "Optional increasing priority-fee percentiles between 0 and 100, e.g. [25, 50, 75]"
+                        }
+                    }
+                }
+            },
+            {
+                "name": "get_gas_price",
+                "description": "Get the current gas price and base fee, with is_high set above the GAS_WARN_GWEI threshold",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {}
+                }
+            },
+            {
+                "name": "wait_for_gas",
+                "description": "Wait until the base fee drops to a target gwei or a timeout passes, to avoid transacting during gas spikes",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "target_gwei": {
+                            "type": "string",
+                            "description": "Base fee in gwei to wait for, e.g. \"30\""
+                        },
+                        "timeout_secs": {
+                            "type": "integer",
+                            "description": "Seconds to wait at most; must be below TOOL_TIMEOUT_SECS (default: 30)"
+                        },
+                        "poll_interval_secs": {
+                            "type": "integer",
+                            "description": "Seconds between checks (default: 12, max: 60)"
+                        }
+                    },
+                    "required": ["target_gwei"]
+                }
+            },
+            {
+                "name": "get_logs",
+                "description": "Fetch event logs over a block range, automatically split into chunks the provider accepts",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "address": {
+                            "type": "string",
+                            "description": "Optional contract address emitting the logs"
+                        },
+                        "from_block": {
+                            "type": "string",
+                            "description": "First block (decimal or 0x-hex)"
+                        },
+                        "to_block": {
+                            "type": "string",
+                            "description": "Last block (decimal or 0x-hex, default: latest)"
+                        },
+                        "topics": {
+                            "type": "array",
+                            "items": { "type": ["string", "null"] },
+                            "description": "Optional topic filters by position (up to 4); null matches any value"
+                        }
+                    },
+                    "required": ["from_block"]
+                }
+            },
+            {
+                "name": "compute_create2_address",
+                "description": "Compute the deterministic address of a contract deployed with CREATE2 (no RPC call)",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "deployer": {
+                            "type": "string",
+                            "description": "Address of the deploying contract or factory"
+                        },
+                        "salt": {
+                            "type": "string",
+                            "description": "32-byte salt as 0x-prefixed hex"
+                        },
+                        "init_code_hash": {
+                            "type": "string",
+                            "description": "keccak256 of the contract init code, 32-byte 0x-prefixed hex"
+                        }
+                    },
+                    "required": ["deployer", "salt", "init_code_hash"]
+                }
+            },
+            {
+                "name": "validate_address",
+                "description": "Check whether a string is a valid Ethereum address, including its EIP-55 checksum, and whether it is an ENS name (no RPC call)",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "address": {
+                            "type": "string",
+                            "description": "Address or ENS name to check"
+                        }
+                    },
+                    "required": ["address"]
+                }
+            },
+            {
+                "name": "keccak256",
+                "description": "Compute the keccak256 hash of UTF-8 text or hex bytes (no RPC call)",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "data": {
+                            "type": "string",
+                            "description": "Data to hash"
+                        },
+                        "encoding": {
+                            "type": "string",
+                            "enum": ["utf8", "hex"],
+                            "description": "How to read data: utf8 text (default) or hex bytes, 0x prefix optional"
+                        }
+                    },
+                    "required": ["data"]
+                }
+            },
+            {
+                "name": "function_selector",
+                "description": "Compute the 4-byte function selector for a signature such as transfer(address,uint256) (no RPC call)",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "signature": {
+                            "type": "string",
+                            "description": "Canonical function signature: name and parameter types, no spaces or parameter names"
+                        }
+                    },
+                    "required": ["signature"]
+                }
+            },
+            {
+                "name": "list_networks",
+                "description": "List the supported networks with their chain ID and native currency, marking the one this server is configured for as enabled",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {},
+                    "required": []
+                }
+            },
+            {
+                "name": "send_transaction",
+                "description": "Sign and broadcast an ETH transfer from the server wallet (EIP-1559 by default)",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "to": {
+                            "type": "string",
+                            "description": "Recipient address"
+                        },
+                        "amount": {
+                            "type": "string",
+                            "description": "Amount of ETH to send (as decimal string)"
+                        },
+                        "max_fee_per_gas": {
+                            "type": "string",
+                            "description": "Optional max fee per gas in gwei (default: derived from eth_feeHistory)"
+                        },
+                        "max_priority_fee_per_gas": {
+                            "type": "string",
+                            "description": "Optional max priority fee per gas in gwei (default: derived from eth_feeHistory)"
+                        },
+                        "legacy": {
+                            "type": "boolean",
+                            "description": "Send a legacy gas-price transaction for chains without EIP-1559 (default: false)"
+                        },
+                        "gas_multiplier": {
+                            "type": "string",
+                            "description": "Optional gas limit padding over the raw estimate, e.g. \"1.5\" (default: GAS_LIMIT_MULTIPLIER or 1.2)"
+                        },
+                        "skip_simulation": {
+                            "type": "boolean",
+                            "description": "Broadcast without first simulating via eth_call (default: false)"
+                        },
+                        "allow_self_transfer": {
+                            "type": "boolean",
+                            "description": "Allow sending to the wallet's own address (default: false)"
+                        },
+                        "idempotency_key": {
+                            "type": "string",
+                            "description": "Optional client key (1-128 chars); repeating a successful call with the same key returns the cached result instead of broadcasting again"
+                        }
+                    },
+                    "required": ["to", "amount"]
+                }
+            },
+            {
+                "name": "replace_transaction",
+                "description": "Speed up or cancel a stuck wallet transaction by resubmitting at the same nonce with higher fees",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "nonce": {
+                            "type": "integer",
+                            "description": "Nonce of the pending transaction to replace"
+                        },
+                        "bump_percent": {
+                            "type": "number",
+                            "description": "Fee increase over the original transaction, between 10 and 500 (default: 10)"
+                        },
+                        "cancel": {
+                            "type": "boolean",
+                            "description": "Replace with a zero-value self-send instead of resubmitting the original (default: false)"
+                        },
+                        "idempotency_key": {
+                            "type": "string",
+                            "description": "Optional client key (1-128 chars); repeating a successful call with the same key returns the cached result instead of broadcasting again"
+                        }
+                    },
+                    "required": ["nonce"]
+                }
+            },
+            {
+                "name": "build_transaction",
+                "description": "Build an unsigned ETH transfer (nonce, gas, fees, chainId) as JSON and RLP hex without signing or broadcasting",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "from": {
+                            "type": "string",
+                            "description": "Optional sender address (default: server wallet)"
+                        },
+                        "to": {
+                            "type": "string",
+                            "description": "Recipient address"
+                        },
+                        "amount": {
+                            "type": "string",
+                            "description": "Amount of ETH to send (as decimal string)"
+                        },
+                        "max_fee_per_gas": {
+                            "type": "string",
+                            "description": "Optional max fee per gas in gwei (default: derived from eth_feeHistory)"
+                        },
+                        "max_priority_fee_per_gas": {
+                            "type": "string",
+                            "description": "Optional max priority fee per gas in gwei (default: derived from eth_feeHistory)"
+                        },
+                        "legacy": {
+                            "type": "boolean",
+                            "description": "Send a legacy gas-price transaction for chains without EIP-1559 (default: false)"
+                        },
+                        "gas_multiplier": {
+                            "type": "string",
+                            "description": "Optional gas limit padding over the raw estimate, e.g. \"1.5\" (default: GAS_LIMIT_MULTIPLIER or 1.2)"
+                        }
+                    },
+                    "required": ["to", "amount"]
+                }
             }
         ]);
-
-        Ok(MCPResponse {
-            jsonrpc: "2.0".to_string(),
-            id,
-            result: Some(json!({
-                "tools": tools
-            })),
-            error: None,
-        })
+        tools.as_array()
+            .into_iter()
+            .flatten()
+            .filter(|tool| tool["name"].as_str().is_some_and(|name| self.tool_handler.is_tool_enabled(name)))
+            .cloned()
+            .collect()
     }
 
     #[instrument(skip(self), fields(request_id = %ctx.request_id))]
-    async fn handle_tools_call(&self, id: Value, params: Value, ctx: &RequestContext) -> Result<MCPResponse, MCPError> {
+    async fn handle_tools_call(&self, id: Value, params: Value, ctx: &mut RequestContext) -> Result<MCPResponse, MCPError> {
         let tool_call = serde_json::from_value::<ToolCall>(params)
             .map_err(|e| MCPError::JsonRpc(format!("Invalid tool call parameters: {}", e)))?;
 
-        match self.tool_handler.handle_tool_call(tool_call).await {
+        // Read-only tools are retried on transient RPC and price API failures; signing
+        // tools run once, as a retry could broadcast a second transaction. Retries stop
+        // within one tool timeout so a failing call cannot hold the stdio loop for minutes.
+        let result = if SIGNING_TOOLS.contains(&tool_call.name.as_str()) {
+            self.tool_handler.handle_tool_call(tool_call).await
+        } else {
+            let ToolCall { name, arguments } = tool_call;
+            let budget = self.tool_handler.tool_timeout();
+            ErrorRecovery::retry(ctx, &name, budget, || self.tool_handler.handle_tool_call(ToolCall {
+                name: name.clone(),
+                arguments: arguments.clone(),
+            })).await
+        };
+
+        match result {
             Ok(result) => {
                 if result.is_error {
                     let error = MCPErrorResponse {
@@ -179,9 +1074,7 @@ impl MCPServer {
                     Ok(MCPResponse {
                         jsonrpc: "2.0".to_string(),
                         id,
-                        result: Some(json!({
-                            "content": result.content
-                        })),
+                        result: Some(tool_result_body(result.content)),
                         error: None,
                     })
                 }
@@ -205,3 +1098,19 @@ impl MCPServer {
         }
     }
 }
+
+/// Wrap a tool's output in MCP content blocks: the pretty-printed JSON as a text block
+/// for hosts that only render text, plus the raw value as `structuredContent`
+pub(crate) fn tool_result_body(content: Value) -> Value {
+    let text = serde_json::to_string_pretty(&content).unwrap_or_else(|_| content.to_string());
+    json!({
+        "content": [
+            {
+                "type": "text",
+                "text": text
+            }
+        ],
+        "structuredContent": content,
+        "isError": false
+    })
+}
@@ -0,0 +1,317 @@
+//! Deterministic canned chain data served instead of any RPC or HTTP call when
+//! `OFFLINE_MODE=true`, so agents and CI can exercise every tool without a node.
+
+use crate::error::MCPError;
+use crate::network::Network;
+use crate::transport::{AggregatorQuote, RpcTransport};
+use crate::types::SwapSource;
+use async_trait::async_trait;
+use ethers::abi::{decode, encode, ParamType, Token};
+use ethers::types::{Address, BlockId, U256};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Block number every mock read is answered at
+pub const MOCK_BLOCK_NUMBER: u64 = 19_000_000;
+
+/// Native balance of every address: 1.5 ETH
+pub const MOCK_NATIVE_BALANCE_WEI: u128 = 1_500_000_000_000_000_000;
+
+/// Balance of every address in each mock token, in whole tokens
+pub const MOCK_TOKEN_BALANCE: u64 = 1_000;
+
+/// Balance of every address for each ERC1155 token id
+pub const MOCK_ERC1155_BALANCE: u64 = 5;
+
+/// Gas price and EIP-1559 base fee: 20 gwei
+pub const MOCK_GAS_PRICE_WEI: u64 = 20_000_000_000;
+
+/// Priority fee reward reported by eth_feeHistory: 1 gwei
+pub const MOCK_PRIORITY_FEE_WEI: u64 = 1_000_000_000;
+
+/// Result of every eth_estimateGas
+pub const MOCK_GAS_ESTIMATE: u64 = 150_000;
+
+/// USD price of the native currency and its wrapped token
+pub const MOCK_NATIVE_PRICE_USD: Decimal = dec!(3000);
+
+/// Tokens the mock chain knows: (address, name, symbol, decimals, USD price).
+/// Other addresses have no code, like EOAs. Each network's wrapped native token is added.
+const MOCK_TOKENS: [(&str, &str, &str, u8, Decimal); 4] = [
+    ("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48", "USD Coin", "USDC", 6, dec!(1)),
+    ("0xdac17f958d2ee523a2206206994597c13d831ec7", "Tether USD", "USDT", 6, dec!(1)),
+    ("0x6b175474e89094c44da98b954eedeac495271d0f", "Dai Stablecoin", "DAI", 18, dec!(1)),
+    ("0x2260fac5e5542a773aa44fbcfedf7c193bc2c599", "Wrapped BTC", "WBTC", 8, dec!(60000)),
+];
+
+/// A token on the mock chain
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockToken {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub price_usd: Decimal,
+}
+
+/// The mock token at `address`, if any
+pub fn mock_token(address: Address) -> Option<MockToken> {
+    let address_str = format!("0x{:x}", address);
+    if let Some((_, name, symbol, decimals, price_usd)) = MOCK_TOKENS.iter().find(|(token, ..)| *token == address_str) {
+        return Some(MockToken { name: name.to_string(), symbol: symbol.to_string(), decimals: *decimals, price_usd: *price_usd });
+    }
+    Network::ALL.iter()
+        .find(|network| network.weth_address().eq_ignore_ascii_case(&address_str))
+        .map(|network| MockToken {
+            name: format!("Wrapped {}", network.native_symbol()),
+            symbol: format!("W{}", network.native_symbol()),
+            decimals: 18,
+            price_usd: MOCK_NATIVE_PRICE_USD,
+        })
+}
+
+fn hex_u256(value: U256) -> Value {
+    json!(format!("{:#x}", value))
+}
+
+fn hex_bytes(bytes: &[u8]) -> Value {
+    json!(format!("0x{}", hex::encode(bytes)))
+}
+
+/// Parse a `0x` address or hex data parameter
+fn param_bytes(value: Option<&Value>) -> Vec<u8> {
+    value.and_then(|v| v.as_str())
+        .and_then(|s| hex::decode(s.trim_start_matches("0x")).ok())
+        .unwrap_or_default()
+}
+
+fn param_address(value: Option<&Value>) -> Address {
+    value.and_then(|v| v.as_str())
+        .and_then(|s| s.parse().ok())
+        .unwrap_or_default()
+}
+
+/// Parse a quantity given as a 0x-hex string or a JSON number
+fn param_u64(value: Option<&Value>) -> Option<u64> {
+    match value? {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => u64::from_str_radix(s.trim_start_matches("0x"), 16).ok(),
+        _ => None,
+    }
+}
+
+fn selector(signature: &str) -> [u8; 4] {
+    let hash = ethers::utils::keccak256(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// `amount_in` of `token_in` converted to `token_out` at mock prices, less `fee_ppm` parts per million
+fn mock_quote(token_in: Address, token_out: Address, amount_in: U256, fee_ppm: u64) -> Result<U256, String> {
+    let (token_in, token_out) = match (mock_token(token_in), mock_token(token_out)) {
+        (Some(token_in), Some(token_out)) => (token_in, token_out),
+        _ => return Err("execution reverted: no mock pool for pair".to_string()),
+    };
+    // Prices as integer micro-dollars keep the arithmetic in U256
+    let micro_usd = |price: Decimal| U256::from((price * dec!(1_000_000)).trunc().to_string().parse::<u128>().unwrap_or(0));
+    let amount_out = amount_in
+        .saturating_mul(micro_usd(token_in.price_usd))
+        .saturating_mul(U256::exp10(token_out.decimals as usize))
+        / micro_usd(token_out.price_usd).max(U256::one())
+        / U256::exp10(token_in.decimals as usize);
+    Ok(amount_out * U256::from(1_000_000 - fee_ppm) / U256::from(1_000_000u64))
+}
+
+/// Return data for an eth_call on the mock chain; an Err is a revert message
+pub fn eth_call(to: Address, data: &[u8]) -> Result<Vec<u8>, String> {
+    if data.len() < 4 {
+        return Ok(Vec::new());
+    }
+    let (function, args) = data.split_at(4);
+    let token = mock_token(to);
+
+    let encoded = if function == selector("balanceOf(address)") {
+        let decimals = token.map(|t| t.decimals).unwrap_or(18);
+        encode(&[Token::Uint(U256::from(MOCK_TOKEN_BALANCE) * U256::exp10(decimals as usize))])
+    } else if function == selector("balanceOf(address,uint256)") {
+        encode(&[Token::Uint(U256::from(MOCK_ERC1155_BALANCE))])
+    } else if function == selector("decimals()") {
+        encode(&[Token::Uint(U256::from(token.map(|t| t.decimals).unwrap_or(18)))])
+    } else if function == selector("symbol()") {
+        encode(&[Token::String(token.map(|t| t.symbol).unwrap_or_else(|| "MOCK".to_string()))])
+    } else if function == selector("name()") {
+        encode(&[Token::String(token.map(|t| t.name).unwrap_or_else(|| "Mock Token".to_string()))])
+    } else if function == selector("supportsInterface(bytes4)") {
+        encode(&[Token::Bool(false)])
+    } else if function == selector("quoteExactInputSingle(address,address,uint24,uint256,uint160)") {
+        let params = decode(&[ParamType::Address, ParamType::Address, ParamType::Uint(24), ParamType::Uint(256), ParamType::Uint(160)], args)
+            .map_err(|e| e.to_string())?;
+        let (token_in, token_out) = (params[0].clone().into_address().unwrap_or_default(), params[1].clone().into_address().unwrap_or_default());
+        let fee = params[2].clone().into_uint().unwrap_or_default().as_u64();
+        let amount_in = params[3].clone().into_uint().unwrap_or_default();
+        encode(&[Token::Uint(mock_quote(token_in, token_out, amount_in, fee)?)])
+    } else if function == selector("getAmountsOut(uint256,address[])") {
+        let params = decode(&[ParamType::Uint(256), ParamType::Array(Box::new(ParamType::Address))], args)
+            .map_err(|e| e.to_string())?;
+        let amount_in = params[0].clone().into_uint().unwrap_or_default();
+        let path: Vec<Address> = params[1].clone().into_array().unwrap_or_default()
+            .into_iter()
+            .filter_map(|token| token.into_address())
+            .collect();
+        let mut amounts = vec![amount_in];
+        for hop in path.windows(2) {
+            // Uniswap V2 charges 0.3%
+            let previous = *amounts.last().unwrap();
+            amounts.push(mock_quote(hop[0], hop[1], previous, 3_000)?);
+        }
+        encode(&[Token::Array(amounts.into_iter().map(Token::Uint).collect())])
+    } else {
+        // transfer, swaps, ENS registry lookups and anything else: a zero word,
+        // i.e. `false`, zero or the zero address
+        let mut word = vec![0u8; 32];
+        if function == selector("transfer(address,uint256)") {
+            word[31] = 1;
+        }
+        word
+    };
+    Ok(encoded)
+}
+
+/// Canned ERC20 Transfer log of 100 USDC emitted at `block`
+fn mock_transfer_log(block: u64) -> Value {
+    let usdc = MOCK_TOKENS[0].0;
+    let mut value = [0u8; 32];
+    (U256::from(100u64) * U256::exp10(6)).to_big_endian(&mut value);
+    json!({
+        "address": usdc,
+        "topics": [
+            format!("0x{}", hex::encode(ethers::utils::keccak256("Transfer(address,address,uint256)"))),
+            format!("0x{:0>64}", "a"),
+            format!("0x{:0>64}", "b"),
+        ],
+        "data": hex_bytes(&value),
+        "blockNumber": format!("{:#x}", block),
+        "blockHash": format!("0x{:0>64x}", block),
+        "transactionHash": format!("0x{:0>64x}", block),
+        "transactionIndex": "0x0",
+        "logIndex": "0x0",
+        "removed": false
+    })
+}
+
+/// JSON-RPC `result` for `method` on the mock chain, or an error message
+pub fn rpc_response(method: &str, params: &Value, chain_id: u64) -> Result<Value, String> {
+    let param = |index: usize| params.get(index);
+    let zero_hash = format!("0x{:0>64}", "");
+    let block_hash = format!("0x{:0>64x}", MOCK_BLOCK_NUMBER);
+
+    let result = match method {
+        "eth_chainId" => hex_u256(U256::from(chain_id)),
+        "net_version" => json!(chain_id.to_string()),
+        "eth_blockNumber" => hex_u256(U256::from(MOCK_BLOCK_NUMBER)),
+        "eth_getBalance" => hex_u256(U256::from(MOCK_NATIVE_BALANCE_WEI)),
+        "eth_gasPrice" => hex_u256(U256::from(MOCK_GAS_PRICE_WEI)),
+        "eth_maxPriorityFeePerGas" => hex_u256(U256::from(MOCK_PRIORITY_FEE_WEI)),
+        "eth_estimateGas" => hex_u256(U256::from(MOCK_GAS_ESTIMATE)),
+        "eth_getTransactionCount" => hex_u256(U256::zero()),
+        "eth_getStorageAt" => json!(zero_hash),
+        "eth_getCode" => {
+            // Mock tokens carry a stub runtime so they read as contracts
+            if mock_token(param_address(param(0))).is_some() { json!("0x6080604052") } else { json!("0x") }
+        },
+        "eth_call" => {
+            let call = param(0).cloned().unwrap_or_default();
+            let data = param_bytes(call.get("data").or_else(|| call.get("input")));
+            hex_bytes(&eth_call(param_address(call.get("to")), &data)?)
+        },
+        "eth_getBlockByNumber" | "eth_getBlockByHash" => json!({
+            "number": format!("{:#x}", MOCK_BLOCK_NUMBER),
+            "hash": block_hash,
+            "parentHash": zero_hash,
+            "timestamp": "0x65a00000",
+            "gasLimit": "0x1c9c380",
+            "gasUsed": "0xe4e1c0",
+            "baseFeePerGas": format!("{:#x}", MOCK_GAS_PRICE_WEI),
+            "transactions": []
+        }),
+        "eth_feeHistory" => {
+            let block_count = param_u64(param(0)).unwrap_or(1).clamp(1, 1024);
+            let percentiles = param(2).and_then(|v| v.as_array()).map(|p| p.len()).unwrap_or(0);
+            json!({
+                "oldestBlock": format!("{:#x}", MOCK_BLOCK_NUMBER + 1 - block_count),
+                "baseFeePerGas": vec![format!("{:#x}", MOCK_GAS_PRICE_WEI); block_count as usize + 1],
+                "gasUsedRatio": vec![0.5; block_count as usize],
+                "reward": vec![vec![format!("{:#x}", MOCK_PRIORITY_FEE_WEI); percentiles]; block_count as usize]
+            })
+        },
+        "eth_getLogs" => {
+            let from_block = param(0).and_then(|filter| param_u64(filter.get("fromBlock"))).unwrap_or(MOCK_BLOCK_NUMBER);
+            json!([mock_transfer_log(from_block)])
+        },
+        "eth_sendRawTransaction" => {
+            let raw = param_bytes(param(0));
+            json!(format!("0x{}", hex::encode(ethers::utils::keccak256(raw))))
+        },
+        "eth_getTransactionByHash" => json!({
+            "hash": param(0).cloned().unwrap_or_default(),
+            "nonce": "0x0",
+            "blockHash": block_hash,
+            "blockNumber": format!("{:#x}", MOCK_BLOCK_NUMBER),
+            "transactionIndex": "0x0",
+            "from": format!("{:#x}", Address::zero()),
+            "to": format!("{:#x}", Address::zero()),
+            "value": "0x0",
+            "gasPrice": format!("{:#x}", MOCK_GAS_PRICE_WEI),
+            "gas": format!("{:#x}", MOCK_GAS_ESTIMATE),
+            "input": "0x",
+            "v": "0x0",
+            "r": "0x0",
+            "s": "0x0"
+        }),
+        "eth_getTransactionReceipt" => json!({
+            "transactionHash": param(0).cloned().unwrap_or_default(),
+            "transactionIndex": "0x0",
+            "blockHash": block_hash,
+            "blockNumber": format!("{:#x}", MOCK_BLOCK_NUMBER),
+            "from": format!("{:#x}", Address::zero()),
+            "to": format!("{:#x}", Address::zero()),
+            "cumulativeGasUsed": format!("{:#x}", MOCK_GAS_ESTIMATE),
+            "gasUsed": format!("{:#x}", MOCK_GAS_ESTIMATE),
+            "contractAddress": null,
+            "logs": [],
+            "logsBloom": format!("0x{}", "0".repeat(512)),
+            "status": "0x1",
+            "effectiveGasPrice": format!("{:#x}", MOCK_GAS_PRICE_WEI),
+            "type": "0x2"
+        }),
+        _ => return Err(format!("{} is not available in OFFLINE_MODE", method)),
+    };
+    Ok(result)
+}
+
+/// `RpcTransport` answering quotes, gas estimates and prices from the mock chain
+pub struct OfflineTransport;
+
+#[async_trait]
+impl RpcTransport for OfflineTransport {
+    async fn eth_call(&self, to: Address, data: Vec<u8>, _block: Option<BlockId>) -> Result<Vec<u8>, MCPError> {
+        eth_call(to, &data).map_err(MCPError::SwapSimulationFailed)
+    }
+
+    async fn estimate_gas(&self, _from: Address, _to: Address, _data: Vec<u8>) -> Result<u64, MCPError> {
+        Ok(MOCK_GAS_ESTIMATE)
+    }
+
+    async fn fetch_prices(&self, _network_slug: &str, token_addresses: &[&str]) -> Result<HashMap<String, Decimal>, MCPError> {
+        Ok(token_addresses.iter()
+            .filter_map(|address| {
+                let token = mock_token(address.parse().ok()?)?;
+                Some((address.to_lowercase(), token.price_usd))
+            })
+            .collect())
+    }
+
+    async fn aggregator_quote(&self, source: SwapSource, _api_key: &str, _chain_id: u64, _sell_token: Address, _buy_token: Address, _amount: U256) -> Result<AggregatorQuote, MCPError> {
+        Err(MCPError::NetworkError(format!("{:?} quotes are not available in OFFLINE_MODE", source)))
+    }
+}
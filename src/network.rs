@@ -0,0 +1,106 @@
+use crate::error::MCPError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Supported EVM networks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Network {
+    #[default]
+    Mainnet,
+    Sepolia,
+    Polygon,
+    Arbitrum,
+    Optimism,
+    Base,
+}
+
+impl Network {
+    /// Every supported network, in display order
+    pub const ALL: [Network; 6] = [
+        Network::Mainnet,
+        Network::Sepolia,
+        Network::Polygon,
+        Network::Arbitrum,
+        Network::Optimism,
+        Network::Base,
+    ];
+
+    /// Expected chain ID reported by `eth_chainId`
+    pub fn chain_id(&self) -> u64 {
+        match self {
+            Network::Mainnet => 1,
+            Network::Sepolia => 11155111,
+            Network::Polygon => 137,
+            Network::Arbitrum => 42161,
+            Network::Optimism => 10,
+            Network::Base => 8453,
+        }
+    }
+
+    /// Canonical lowercase name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Network::Mainnet => "mainnet",
+            Network::Sepolia => "sepolia",
+            Network::Polygon => "polygon",
+            Network::Arbitrum => "arbitrum",
+            Network::Optimism => "optimism",
+            Network::Base => "base",
+        }
+    }
+
+    /// Symbol of the native currency balances are denominated in
+    pub fn native_symbol(&self) -> &'static str {
+        match self {
+            Network::Polygon => "MATIC",
+            Network::Mainnet | Network::Sepolia | Network::Arbitrum | Network::Optimism | Network::Base => "ETH",
+        }
+    }
+
+    /// Wrapped native token substituted for ETH in swaps (WMATIC on Polygon)
+    pub fn weth_address(&self) -> &'static str {
+        match self {
+            Network::Mainnet => "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+            Network::Sepolia => "0xfFf9976782d46CC05630D1f6eBAb18b2324d6B14",
+            Network::Polygon => "0x0d500B1d8E8eF31E21C99d1Db9A6444d3ADf1270",
+            Network::Arbitrum => "0x82aF49447D8a07e3bd95BD0d56f35241523fBab1",
+            Network::Optimism | Network::Base => "0x4200000000000000000000000000000000000006",
+        }
+    }
+
+    /// Network slug used by the Alchemy Price API, or None when prices are unavailable
+    pub fn alchemy_price_slug(&self) -> Option<&'static str> {
+        match self {
+            Network::Mainnet => Some("eth-mainnet"),
+            Network::Sepolia => None,
+            Network::Polygon => Some("polygon-mainnet"),
+            Network::Arbitrum => Some("arb-mainnet"),
+            Network::Optimism => Some("opt-mainnet"),
+            Network::Base => Some("base-mainnet"),
+        }
+    }
+}
+
+impl FromStr for Network {
+    type Err = MCPError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "mainnet" | "ethereum" | "eth-mainnet" => Ok(Network::Mainnet),
+            "sepolia" | "eth-sepolia" => Ok(Network::Sepolia),
+            "polygon" | "matic" | "polygon-mainnet" => Ok(Network::Polygon),
+            "arbitrum" | "arb-mainnet" => Ok(Network::Arbitrum),
+            "optimism" | "opt-mainnet" => Ok(Network::Optimism),
+            "base" | "base-mainnet" => Ok(Network::Base),
+            _ => Err(MCPError::ConfigurationError(format!("Unsupported network: {}", s))),
+        }
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
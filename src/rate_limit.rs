@@ -0,0 +1,85 @@
+use crate::config::Config;
+use crate::error::MCPError;
+use governor::{DefaultKeyedRateLimiter, Quota, RateLimiter};
+use std::net::IpAddr;
+use std::num::NonZeroU32;
+use tracing::{info, warn};
+
+/// Per-client token bucket protecting the shared upstream RPC quota
+pub struct ClientRateLimiter {
+    limiter: DefaultKeyedRateLimiter<String>,
+    requests_per_minute: u32,
+    /// Key clients on their self-reported id rather than their IP
+    trust_client_id_header: bool,
+}
+
+impl ClientRateLimiter {
+    /// Allow `requests_per_minute` per client, with bursts up to the same amount
+    pub fn new(requests_per_minute: NonZeroU32) -> Self {
+        Self {
+            limiter: RateLimiter::keyed(Quota::per_minute(requests_per_minute)),
+            requests_per_minute: requests_per_minute.get(),
+            trust_client_id_header: false,
+        }
+    }
+
+    /// Key clients on the id they send instead of their remote IP. A client can pick any
+    /// id, so only enable this behind a proxy that sets it.
+    pub fn with_trusted_client_id(mut self, trusted: bool) -> Self {
+        self.trust_client_id_header = trusted;
+        self
+    }
+
+    /// Build from `config.rate_limit_per_minute`; 0 disables rate limiting
+    pub fn from_config(config: &Config) -> Option<Self> {
+        let requests_per_minute = NonZeroU32::new(config.rate_limit_per_minute)?;
+
+        info!(
+            requests_per_minute = requests_per_minute.get(),
+            trust_client_id_header = config.trust_client_id_header,
+            "Per-client rate limiting enabled"
+        );
+        Some(Self::new(requests_per_minute).with_trusted_client_id(config.trust_client_id_header))
+    }
+
+    /// Bucket key for a request: the remote IP, or the client id it sent when that is
+    /// trusted and non-empty
+    pub fn client_key(&self, client_id: Option<&str>, remote_ip: IpAddr) -> String {
+        client_id
+            .filter(|_| self.trust_client_id_header)
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| remote_ip.to_string())
+    }
+
+    /// Configured limit
+    pub fn requests_per_minute(&self) -> u32 {
+        self.requests_per_minute
+    }
+
+    /// Consume one request for `client`, failing once its bucket is empty
+    pub fn check(&self, client: &str) -> Result<(), MCPError> {
+        self.check_n(client, 1)
+    }
+
+    /// Consume `requests` at once for `client`, e.g. every element of a JSON-RPC batch.
+    /// Nothing is consumed when the bucket cannot cover all of them.
+    pub fn check_n(&self, client: &str, requests: u32) -> Result<(), MCPError> {
+        let requests = NonZeroU32::new(requests).unwrap_or(NonZeroU32::MIN);
+        let allowed = matches!(self.limiter.check_key_n(&client.to_string(), requests), Ok(Ok(())));
+        if !allowed {
+            warn!(client = %client, requests = requests.get(), "Client rate limit exceeded");
+            return Err(MCPError::RateLimitExceeded(format!(
+                "client {} exceeded {} requests per minute",
+                client, self.requests_per_minute
+            )));
+        }
+
+        // Drop idle buckets so the key space stays bounded
+        if self.limiter.len() > 10_000 {
+            self.limiter.retain_recent();
+        }
+        Ok(())
+    }
+}
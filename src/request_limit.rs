@@ -0,0 +1,91 @@
+use crate::error::{ErrorHandler, MCPError};
+use axum::{
+    extract::DefaultBodyLimit,
+    http::StatusCode,
+    middleware,
+    response::{IntoResponse, Json, Response},
+    Router,
+};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+use tower_http::limit::RequestBodyLimitLayer;
+
+/// Default maximum size of one request: an HTTP body or a stdio line (1 MB)
+pub const DEFAULT_MAX_REQUEST_BYTES: usize = 1024 * 1024;
+
+/// Error returned for a request over the size limit
+pub fn request_too_large(max_bytes: usize) -> MCPError {
+    MCPError::InvalidJsonRpcRequest(format!("Request body exceeds limit of {} bytes", max_bytes))
+}
+
+/// Reject request bodies over `max_bytes` with HTTP 413 and a JSON-RPC error
+pub fn with_body_limit<S>(router: Router<S>, max_bytes: usize) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    router
+        .layer(DefaultBodyLimit::disable())
+        .layer(RequestBodyLimitLayer::new(max_bytes))
+        .layer(middleware::map_response(move |response: Response| async move {
+            payload_too_large_to_jsonrpc(response, max_bytes)
+        }))
+}
+
+/// Replace the plain-text 413 from the body limit with a JSON-RPC error
+fn payload_too_large_to_jsonrpc(response: Response, max_bytes: usize) -> Response {
+    if response.status() != StatusCode::PAYLOAD_TOO_LARGE {
+        return response;
+    }
+
+    let body = serde_json::to_value(ErrorHandler::handle_error(request_too_large(max_bytes), None)).unwrap();
+    (StatusCode::PAYLOAD_TOO_LARGE, Json(body)).into_response()
+}
+
+/// One line read by `read_bounded_line`
+#[derive(Debug, PartialEq, Eq)]
+pub enum BoundedLine {
+    /// A complete line without its trailing newline
+    Line(Vec<u8>),
+    /// A line over the limit; it was read to its end and discarded
+    TooLong,
+    /// End of input
+    Eof,
+}
+
+/// Read one newline-terminated line, buffering at most `max_bytes` of it.
+///
+/// A longer line is drained without being kept, so one oversized request neither
+/// exhausts memory nor desynchronises the lines that follow it.
+pub async fn read_bounded_line<R>(reader: &mut R, max_bytes: usize) -> std::io::Result<BoundedLine>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut line = Vec::new();
+    let mut too_long = false;
+
+    loop {
+        let available = reader.fill_buf().await?;
+        if available.is_empty() {
+            return Ok(match (too_long, line.is_empty()) {
+                (true, _) => BoundedLine::TooLong,
+                (false, true) => BoundedLine::Eof,
+                (false, false) => BoundedLine::Line(line),
+            });
+        }
+
+        let newline = available.iter().position(|&b| b == b'\n');
+        let content = newline.unwrap_or(available.len());
+        if !too_long {
+            if line.len() + content > max_bytes {
+                too_long = true;
+                line = Vec::new();
+            } else {
+                line.extend_from_slice(&available[..content]);
+            }
+        }
+
+        reader.consume(newline.map_or(content, |i| i + 1));
+        if newline.is_some() {
+            return Ok(if too_long { BoundedLine::TooLong } else { BoundedLine::Line(line) });
+        }
+    }
+}
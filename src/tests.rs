@@ -1,10 +1,27 @@
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod tests {
     use crate::mcp_server::MCPServer;
     use crate::types::MCPRequest;
     use serde_json::json;
     use std::env;
 
+    /// File settings with only an RPC endpoint, so resolving them does not require RPC_URL
+    fn file_config() -> crate::config::FileConfig {
+        crate::config::FileConfig { rpc_url: Some("http://localhost:8545".to_string()), ..Default::default() }
+    }
+
+    /// Configuration answering every call from the canned mock chain
+    fn offline_config() -> crate::config::Config {
+        crate::config::Config::resolve(crate::config::FileConfig { offline_mode: Some(true), ..Default::default() }).unwrap()
+    }
+
+    /// Server over the offline mock chain, so protocol tests need neither a key nor a network
+    async fn offline_server() -> MCPServer {
+        let private_key = crate::ethereum::PLACEHOLDER_PRIVATE_KEY.to_string();
+        MCPServer::from_config(&offline_config(), private_key).await.unwrap()
+    }
+
     #[tokio::test]
     async fn test_mcp_server_initialization() {
         // This test requires environment variables to be set
@@ -50,7 +67,7 @@ mod tests {
         assert!(result.get("tools").is_some());
         
         let tools = result.get("tools").unwrap().as_array().unwrap();
-        assert_eq!(tools.len(), 3);
+        assert_eq!(tools.len(), 31);
         
         // Check that all expected tools are present
         let tool_names: Vec<&str> = tools.iter()
@@ -58,8 +75,35 @@ mod tests {
             .collect();
         
         assert!(tool_names.contains(&"get_balance"));
+        assert!(tool_names.contains(&"get_balances_for_addresses"));
+        assert!(tool_names.contains(&"get_balance_changes"));
+        assert!(tool_names.contains(&"detect_proxy"));
+        assert!(tool_names.contains(&"batch_lookup_ens"));
+        assert!(tool_names.contains(&"get_gas_price"));
+        assert!(tool_names.contains(&"get_erc1155_balance"));
+        assert!(tool_names.contains(&"wait_for_gas"));
         assert!(tool_names.contains(&"get_token_price"));
+        assert!(tool_names.contains(&"get_onchain_price"));
+        assert!(tool_names.contains(&"get_eth_price"));
+        assert!(tool_names.contains(&"list_networks"));
+        assert!(tool_names.contains(&"keccak256"));
+        assert!(tool_names.contains(&"function_selector"));
+        assert!(tool_names.contains(&"get_token_metadata"));
+        assert!(tool_names.contains(&"get_all_token_balances"));
+        assert!(tool_names.contains(&"get_portfolio"));
         assert!(tool_names.contains(&"swap_tokens"));
+        assert!(tool_names.contains(&"simulate_token_transfer"));
+        assert!(tool_names.contains(&"transfer_token"));
+        assert!(tool_names.contains(&"lookup_ens_name"));
+        assert!(tool_names.contains(&"get_code"));
+        assert!(tool_names.contains(&"get_storage_at"));
+        assert!(tool_names.contains(&"get_pending_transactions"));
+        assert!(tool_names.contains(&"get_fee_history"));
+        assert!(tool_names.contains(&"get_logs"));
+        assert!(tool_names.contains(&"compute_create2_address"));
+        assert!(tool_names.contains(&"validate_address"));
+        assert!(tool_names.contains(&"send_transaction"));
+        assert!(tool_names.contains(&"build_transaction"));
     }
 
     #[tokio::test]
@@ -93,13 +137,12 @@ mod tests {
         // The test might fail due to RPC connection issues, which is expected
         match response {
             Ok(resp) => {
-                if resp.result.is_some() {
-                    let result = resp.result.unwrap();
-                    let content = result.get("content").unwrap();
+                if let Some(result) = resp.result {
+                    let content = result.get("structuredContent").unwrap();
                     
                     // Verify the response structure
                     assert!(content.get("address").is_some());
-                    assert!(content.get("eth_balance").is_some());
+                    assert!(content.get("native_balance").is_some());
                     assert!(content.get("token_balances").is_some());
                 }
             },
@@ -130,7 +173,7 @@ mod tests {
             params: json!({
                 "name": "get_token_price",
                 "arguments": {
-                    "token_address": "0xA0b86a33E6441b8C4C8C0C4C8C0C4C8C0C4C8C0C" // USDC
+                    "token_address": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48" // USDC
                 }
             }),
             request_context: None,
@@ -141,7 +184,7 @@ mod tests {
         // This might fail if the API is down, so we just check the structure
         if response.error.is_none() {
             let result = response.result.unwrap();
-            let content = result.get("content").unwrap();
+            let content = result.get("structuredContent").unwrap();
             
             assert!(content.get("token_address").is_some());
             assert!(content.get("price_usd").is_some());
@@ -168,10 +211,11 @@ mod tests {
             params: json!({
                 "name": "swap_tokens",
                 "arguments": {
-                    "from_token": "0xA0b86a33E6441b8C4C8C0C4C8C0C4C8C0C4C8C0C", // USDC
+                    "from_token": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", // USDC
                     "to_token": "0xdAC17F958D2ee523a2206206994597C13D831ec7", // USDT
                     "amount": "100.0",
-                    "slippage_tolerance": "0.5"
+                    "slippage_tolerance": "0.5",
+                    "skip_balance_check": true
                 }
             }),
             request_context: None,
@@ -183,16 +227,18 @@ mod tests {
         // We just want to ensure the code compiles and runs without panicking
         match response {
             Ok(resp) => {
-                if resp.result.is_some() {
-                    let result = resp.result.unwrap();
-                    let content = result.get("content").unwrap();
+                if let Some(result) = resp.result {
+                    let content = result.get("structuredContent").unwrap();
                     
                     // Verify the response structure
                     assert!(content.get("from_token").is_some());
                     assert!(content.get("to_token").is_some());
                     assert!(content.get("amount_in").is_some());
                     assert!(content.get("amount_out").is_some());
+                    assert!(content.get("minimum_received").is_some());
                     assert!(content.get("gas_estimate").is_some());
+                    assert!(content.get("price_impact_percent").is_some());
+                    assert!(content.get("high_price_impact").is_some());
                 }
             },
             Err(_) => {
@@ -259,4 +305,2479 @@ mod tests {
         let response = server.handle_request(request).await.unwrap();
         assert!(response.error.is_some());
     }
+
+    #[tokio::test]
+    async fn test_batch_request() {
+        let server = offline_server().await;
+        
+        let batch = vec![
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/list", "params": {} }),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "ping" }),
+        ];
+
+        let responses = server.handle_batch(batch).await.unwrap();
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0].id, json!(1));
+        assert!(responses[0].result.as_ref().unwrap().get("tools").is_some());
+        assert_eq!(responses[1].id, json!(2));
+        assert!(responses[1].error.is_none());
+
+        // Malformed and empty batches are rejected as a whole
+        assert!(server.handle_batch(vec![json!({ "id": 3 })]).await.is_err());
+        assert!(server.handle_batch(vec![]).await.is_err());
+    }
+
+    #[test]
+    fn test_validate_block_number() {
+        use crate::error::InputValidator;
+
+        assert_eq!(InputValidator::validate_block_number(&json!("latest")).unwrap(), None);
+        assert_eq!(InputValidator::validate_block_number(&json!(17000000)).unwrap(), Some(17_000_000));
+        assert_eq!(InputValidator::validate_block_number(&json!("17000000")).unwrap(), Some(17_000_000));
+        assert_eq!(InputValidator::validate_block_number(&json!("0x10")).unwrap(), Some(16));
+        assert!(InputValidator::validate_block_number(&json!("pending-ish")).is_err());
+        assert!(InputValidator::validate_block_number(&json!(-1)).is_err());
+    }
+
+    #[test]
+    fn test_validate_block_tag() {
+        use crate::error::InputValidator;
+        use ethers::types::BlockNumber;
+
+        assert_eq!(InputValidator::validate_block_tag(&json!("latest")).unwrap(), None);
+        assert_eq!(InputValidator::validate_block_tag(&json!("pending")).unwrap(), Some(BlockNumber::Pending));
+        assert_eq!(InputValidator::validate_block_tag(&json!("safe")).unwrap(), Some(BlockNumber::Safe));
+        assert_eq!(InputValidator::validate_block_tag(&json!("finalized")).unwrap(), Some(BlockNumber::Finalized));
+        assert_eq!(InputValidator::validate_block_tag(&json!("0x10")).unwrap(), Some(BlockNumber::Number(16u64.into())));
+        assert!(InputValidator::validate_block_tag(&json!("earliest-ish")).is_err());
+        // get_logs ranges stay numeric
+        assert!(InputValidator::validate_block_number(&json!("safe")).is_err());
+    }
+
+    #[test]
+    fn test_console_logs_go_to_stderr() {
+        // Logs on stdout would corrupt the stdio JSON-RPC stream
+        let writer = crate::logging::console_writer();
+        assert_eq!(
+            std::any::type_name_of_val(&writer),
+            std::any::type_name::<std::io::Stderr>()
+        );
+    }
+
+    #[test]
+    fn test_network_parsing() {
+        use crate::network::Network;
+
+        assert_eq!("mainnet".parse::<Network>().unwrap(), Network::Mainnet);
+        assert_eq!("Polygon".parse::<Network>().unwrap().chain_id(), 137);
+        assert_eq!("eth-sepolia".parse::<Network>().unwrap(), Network::Sepolia);
+        assert!("dogechain".parse::<Network>().is_err());
+    }
+
+    #[test]
+    fn test_alchemy_price_slug() {
+        use crate::network::Network;
+
+        assert_eq!(Network::Mainnet.alchemy_price_slug(), Some("eth-mainnet"));
+        assert_eq!(Network::Polygon.alchemy_price_slug(), Some("polygon-mainnet"));
+        assert_eq!(Network::Sepolia.alchemy_price_slug(), None);
+    }
+
+    #[test]
+    fn test_circuit_breaker_transitions() {
+        use crate::circuit_breaker::{CircuitBreaker, CircuitState};
+        use std::time::Duration;
+
+        let mut breaker = CircuitBreaker::new("test", 2, Duration::from_millis(0));
+        assert!(breaker.allow_request());
+
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        breaker.record_failure();
+        assert!(matches!(breaker.state(), CircuitState::Open { .. }));
+
+        // Zero cooldown: next check moves to half-open, and a failure reopens
+        assert!(breaker.allow_request());
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        breaker.record_failure();
+        assert!(matches!(breaker.state(), CircuitState::Open { .. }));
+
+        assert!(breaker.allow_request());
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        let mut breaker = CircuitBreaker::new("test", 1, Duration::from_secs(60));
+        breaker.record_failure();
+        assert!(!breaker.allow_request());
+    }
+
+    #[test]
+    fn test_format_balance_rounds_half_up() {
+        use crate::ethereum::EthereumClient;
+        use rust_decimal_macros::dec;
+
+        assert_eq!(EthereumClient::format_balance(dec!(1.2345675), 6), "1.234568");
+        assert_eq!(EthereumClient::format_balance(dec!(0.0000005), 6), "0.000001");
+        assert_eq!(EthereumClient::format_balance(dec!(123456789.987654321), 0), "123456790");
+        assert_eq!(EthereumClient::format_balance(dec!(2.5), 0), "3");
+    }
+
+    #[test]
+    fn test_decode_revert_string() {
+        use crate::ethereum::EthereumClient;
+        use ethers::abi::{encode, Token};
+
+        let mut data = vec![0x08, 0xc3, 0x79, 0xa0];
+        data.extend(encode(&[Token::String("ERC20: transfer amount exceeds balance".to_string())]));
+        assert_eq!(
+            EthereumClient::decode_revert_string(&data).as_deref(),
+            Some("ERC20: transfer amount exceeds balance")
+        );
+        assert_eq!(EthereumClient::decode_revert_string(&[0xde, 0xad, 0xbe, 0xef]), None);
+    }
+
+    #[test]
+    fn test_swap_rejects_identical_tokens() {
+        use crate::error::{InputValidator, MCPError};
+
+        let args = json!({
+            "from_token": "0xdAC17F958D2ee523a2206206994597C13D831ec7",
+            "to_token": "0xdac17f958d2ee523a2206206994597c13d831ec7",
+            "amount": "1.0"
+        });
+        let result = InputValidator::validate_tool_parameters("swap_tokens", &args);
+        assert!(matches!(result, Err(MCPError::ValidationError(msg)) if msg == "from_token and to_token must differ"));
+    }
+
+    #[tokio::test]
+    async fn test_notifications_get_no_response() {
+        let server = offline_server().await;
+
+        let initialized: MCPRequest = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/initialized"
+        })).unwrap();
+        assert!(server.handle_message(initialized).await.unwrap().is_none());
+
+        // Unknown methods sent as notifications are not answered either
+        let unknown: MCPRequest = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "method": "does/not/exist"
+        })).unwrap();
+        assert!(server.handle_message(unknown).await.unwrap().is_none());
+
+        let ping: MCPRequest = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "ping"
+        })).unwrap();
+        assert!(server.handle_message(ping).await.unwrap().is_some());
+    }
+
+    #[test]
+    fn test_validate_gas_multiplier() {
+        use crate::error::InputValidator;
+        use rust_decimal_macros::dec;
+
+        assert_eq!(InputValidator::validate_gas_multiplier("1.5").unwrap(), dec!(1.5));
+        assert!(InputValidator::validate_gas_multiplier("1").is_ok());
+        assert!(InputValidator::validate_gas_multiplier("0.9").is_err());
+        assert!(InputValidator::validate_gas_multiplier("12").is_err());
+        assert!(InputValidator::validate_gas_multiplier("abc").is_err());
+    }
+
+    #[test]
+    fn test_request_context_client_request_id() {
+        use crate::logging::RequestContext;
+
+        let ctx = RequestContext::new("ping".to_string()).with_client_request_id(&json!(42));
+        assert_eq!(ctx.client_request_id.as_deref(), Some("42"));
+
+        let ctx = RequestContext::new("ping".to_string()).with_client_request_id(&json!("abc-1"));
+        assert_eq!(ctx.client_request_id.as_deref(), Some("abc-1"));
+
+        // Notifications carry no id
+        let ctx = RequestContext::new("ping".to_string()).with_client_request_id(&json!(null));
+        assert!(ctx.client_request_id.is_none());
+        assert!(!ctx.request_id.is_empty());
+    }
+
+    #[test]
+    fn test_parse_enabled_tools() {
+        use crate::tools::parse_enabled_tools;
+
+        let tools = parse_enabled_tools("get_balance, get_token_price,").unwrap();
+        assert_eq!(tools.len(), 2);
+        assert!(tools.contains("get_balance"));
+        assert!(tools.contains("get_token_price"));
+
+        // Empty values leave every tool enabled
+        assert!(parse_enabled_tools("").is_none());
+        assert!(parse_enabled_tools(" , ").is_none());
+    }
+
+    #[test]
+    fn test_checksum_address() {
+        use crate::ethereum::EthereumClient;
+        use ethers::types::Address;
+
+        let weth: Address = "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2".parse().unwrap();
+        assert_eq!(EthereumClient::checksum(&weth), "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+    }
+
+    #[test]
+    fn test_metadata_source_serialization() {
+        use crate::types::MetadataSource;
+
+        assert_eq!(json!(MetadataSource::Onchain), json!("onchain"));
+        assert_eq!(json!(MetadataSource::KnownList), json!("known_list"));
+        assert_eq!(json!(MetadataSource::Default), json!("default"));
+        assert_eq!(json!(MetadataSource::Alchemy), json!("alchemy"));
+    }
+
+    #[test]
+    fn test_parsed_address() {
+        use crate::address::ParsedAddress;
+
+        let checksummed = ParsedAddress::parse("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2").unwrap();
+        let lowercase = ParsedAddress::parse("0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2").unwrap();
+        assert_eq!(checksummed, lowercase);
+        assert_eq!(lowercase.to_string(), "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+
+        // Mixed case with a broken checksum is rejected
+        assert!(ParsedAddress::parse("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756CC2").is_err());
+        assert!(ParsedAddress::parse("0x123").is_err());
+    }
+
+    #[test]
+    fn test_validate_storage_slot() {
+        use crate::error::InputValidator;
+        use ethers::types::U256;
+
+        assert_eq!(InputValidator::validate_storage_slot("0x0").unwrap(), U256::zero());
+        assert_eq!(InputValidator::validate_storage_slot("10").unwrap(), U256::from(10));
+        assert_eq!(InputValidator::validate_storage_slot("0x0a").unwrap(), U256::from(10));
+        assert!(InputValidator::validate_storage_slot("0x").is_err());
+        assert!(InputValidator::validate_storage_slot("slot").is_err());
+        assert!(InputValidator::validate_storage_slot(&format!("0x{}", "f".repeat(65))).is_err());
+    }
+
+    #[test]
+    fn test_is_native_eth() {
+        use crate::address::is_native_eth;
+
+        assert!(is_native_eth("ETH"));
+        assert!(is_native_eth("eth"));
+        assert!(is_native_eth("0x0000000000000000000000000000000000000000"));
+        assert!(is_native_eth("0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE"));
+        assert!(!is_native_eth("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"));
+        assert!(!is_native_eth("WETH"));
+    }
+
+    #[test]
+    fn test_tool_result_content_blocks() {
+        use crate::mcp_server::tool_result_body;
+
+        let body = tool_result_body(json!({ "address": "0xabc", "eth_balance": "1.5" }));
+        let content = body["content"].as_array().unwrap();
+        assert_eq!(content.len(), 1);
+        assert_eq!(content[0]["type"], "text");
+
+        let text: serde_json::Value = serde_json::from_str(content[0]["text"].as_str().unwrap()).unwrap();
+        assert_eq!(text["eth_balance"], "1.5");
+        assert_eq!(body["structuredContent"]["address"], "0xabc");
+        assert_eq!(body["isError"], false);
+    }
+
+    #[test]
+    fn test_client_rate_limiter() {
+        use crate::error::MCPError;
+        use crate::rate_limit::ClientRateLimiter;
+        use std::num::NonZeroU32;
+
+        let limiter = ClientRateLimiter::new(NonZeroU32::new(2).unwrap());
+        assert!(limiter.check("client-a").is_ok());
+        assert!(limiter.check("client-a").is_ok());
+        assert!(matches!(limiter.check("client-a"), Err(MCPError::RateLimitExceeded(_))));
+
+        // Buckets are independent per client
+        assert!(limiter.check("client-b").is_ok());
+
+        // A batch is charged per element, and all-or-nothing
+        let limiter = ClientRateLimiter::new(NonZeroU32::new(3).unwrap());
+        assert!(matches!(limiter.check_n("client-a", 4), Err(MCPError::RateLimitExceeded(_))));
+        assert!(limiter.check_n("client-a", 2).is_ok());
+        assert!(matches!(limiter.check_n("client-a", 2), Err(MCPError::RateLimitExceeded(_))));
+        assert!(limiter.check("client-a").is_ok());
+    }
+
+    #[test]
+    fn test_client_key_ignores_untrusted_client_id() {
+        use crate::config::Config;
+        use crate::rate_limit::ClientRateLimiter;
+        use std::net::{IpAddr, Ipv4Addr};
+        use std::num::NonZeroU32;
+
+        let remote_ip = IpAddr::V4(Ipv4Addr::new(203, 0, 113, 7));
+
+        // By default every id a client invents maps to its IP, so rotating ids gains nothing
+        let limiter = ClientRateLimiter::new(NonZeroU32::new(1).unwrap());
+        assert_eq!(limiter.client_key(Some("fresh-id"), remote_ip), "203.0.113.7");
+        assert_eq!(limiter.client_key(None, remote_ip), "203.0.113.7");
+        assert!(limiter.check(&limiter.client_key(Some("id-1"), remote_ip)).is_ok());
+        assert!(limiter.check(&limiter.client_key(Some("id-2"), remote_ip)).is_err());
+
+        // Opted in: the header is the key, falling back to the IP when blank
+        let config = Config { rate_limit_per_minute: 1, trust_client_id_header: true, ..offline_config() };
+        let limiter = ClientRateLimiter::from_config(&config).unwrap();
+        assert_eq!(limiter.client_key(Some(" team-a "), remote_ip), "team-a");
+        assert_eq!(limiter.client_key(Some("  "), remote_ip), "203.0.113.7");
+    }
+
+    #[test]
+    fn test_get_balance_accepts_native_token() {
+        use crate::error::InputValidator;
+
+        let address = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+        for token in ["ETH", "0x0000000000000000000000000000000000000000"] {
+            let args = json!({ "address": address, "token_address": token });
+            assert!(InputValidator::validate_tool_parameters("get_balance", &args).is_ok());
+        }
+        let args = json!({ "address": address, "token_address": "USDC" });
+        assert!(InputValidator::validate_tool_parameters("get_balance", &args).is_err());
+    }
+
+    #[test]
+    fn test_split_block_range() {
+        use crate::ethereum::split_block_range;
+
+        assert_eq!(split_block_range(100, 100, 10), vec![(100, 100)]);
+        assert_eq!(split_block_range(0, 24, 10), vec![(0, 9), (10, 19), (20, 24)]);
+        assert_eq!(split_block_range(0, 19, 10), vec![(0, 9), (10, 19)]);
+        assert!(split_block_range(10, 5, 10).is_empty());
+        assert_eq!(split_block_range(u64::MAX - 1, u64::MAX, 10), vec![(u64::MAX - 1, u64::MAX)]);
+    }
+
+    #[test]
+    fn test_swap_deadline_validation() {
+        use crate::error::InputValidator;
+
+        let swap = |deadline: serde_json::Value| json!({
+            "from_token": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+            "to_token": "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+            "amount": "100",
+            "deadline_secs": deadline
+        });
+        assert!(InputValidator::validate_tool_parameters("swap_tokens", &swap(json!(1200))).is_ok());
+        assert!(InputValidator::validate_tool_parameters("swap_tokens", &swap(json!(0))).is_err());
+        assert!(InputValidator::validate_tool_parameters("swap_tokens", &swap(json!(86_401))).is_err());
+        assert!(InputValidator::validate_tool_parameters("swap_tokens", &swap(json!("1200"))).is_err());
+    }
+
+    #[test]
+    fn test_compute_create2_address() {
+        use crate::error::InputValidator;
+        use ethers::types::Address;
+
+        // EIP-1014 example 1
+        let deployer: Address = "0x0000000000000000000000000000000000000000".parse().unwrap();
+        let salt = InputValidator::validate_bytes32(&format!("0x{}", "0".repeat(64)), "salt").unwrap();
+        let init_code_hash = ethers::utils::keccak256([0x00u8]);
+        let address = ethers::utils::get_create2_address_from_hash(deployer, salt, init_code_hash);
+        assert_eq!(format!("0x{:x}", address), "0x4d1a2e2bb4f88f0250f26ffff098b0b30b26bf38");
+
+        assert!(InputValidator::validate_bytes32("0x1234", "salt").is_err());
+        let args = json!({ "deployer": "0x0000000000000000000000000000000000000000", "salt": "0x00", "init_code_hash": format!("0x{}", "0".repeat(64)) });
+        assert!(InputValidator::validate_tool_parameters("compute_create2_address", &args).is_err());
+    }
+
+    #[test]
+    fn test_request_context_outcome() {
+        use crate::logging::{RequestContext, RequestOutcome};
+
+        let mut ctx = RequestContext::new("tools/call".to_string());
+        assert_eq!(ctx.outcome, None);
+        assert_eq!(ctx.retry_count, 0);
+
+        ctx.record_retry();
+        ctx.record_rpc_calls(3);
+        ctx.record_outcome(RequestOutcome::Failure { error_code: -32603 });
+        assert_eq!(ctx.retry_count, 1);
+        assert_eq!(ctx.rpc_calls, 3);
+        assert_eq!(ctx.outcome.unwrap().as_str(), "failure");
+        assert_eq!(ctx.outcome.unwrap().error_code(), Some(-32603));
+        assert_eq!(RequestOutcome::Success.error_code(), None);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_requests_count_own_rpc_calls() {
+        use crate::logging::{count_rpc_calls, record_rpc_call};
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        let total = AtomicU64::new(0);
+        let request = |calls: u64| {
+            let total = &total;
+            async move {
+                for _ in 0..calls {
+                    record_rpc_call(total);
+                    tokio::task::yield_now().await;
+                }
+            }
+        };
+
+        let ((_, first), (_, second)) = tokio::join!(count_rpc_calls(request(2)), count_rpc_calls(request(5)));
+        assert_eq!(first, 2);
+        assert_eq!(second, 5);
+        assert_eq!(total.load(Ordering::Relaxed), 7);
+
+        // Calls made outside a counted request only reach the transport-wide total
+        record_rpc_call(&total);
+        assert_eq!(total.load(Ordering::Relaxed), 8);
+    }
+
+    #[tokio::test]
+    async fn test_rpc_failover_to_second_url() {
+        use crate::failover::FailoverTransport;
+        use ethers::providers::JsonRpcClient;
+        use ethers::types::U64;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpListener;
+
+        // A port with nothing listening on it
+        let dead_url = {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            format!("http://{}", listener.local_addr().unwrap())
+        };
+
+        // Minimal JSON-RPC endpoint answering every request with block 0x10
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let live_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 4096];
+                let _ = socket.read(&mut buf).await;
+                let body = r#"{"jsonrpc":"2.0","id":1,"result":"0x10"}"#;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(), body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let transport = FailoverTransport::new(&[dead_url, live_url.clone()]).unwrap();
+        let block: U64 = transport.request("eth_blockNumber", ()).await.unwrap();
+        assert_eq!(block, U64::from(16));
+
+        // The working endpoint is remembered for later requests
+        assert_eq!(transport.url().as_str().trim_end_matches('/'), live_url);
+
+        // Both the failed and the successful attempt count as round trips
+        assert_eq!(transport.request_counter().load(std::sync::atomic::Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_parse_rpc_urls() {
+        use crate::ethereum::parse_rpc_urls;
+
+        assert_eq!(
+            parse_rpc_urls(" https://a.example/rpc , ,https://b.example "),
+            vec!["https://a.example/rpc".to_string(), "https://b.example".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_token_amount_precision() {
+        use crate::ethereum::EthereumClient;
+        use crate::error::MCPError;
+        use ethers::types::U256;
+        use rust_decimal_macros::dec;
+
+        // USDC has 6 decimals: a 7th fractional digit would be silently dropped
+        let result = EthereumClient::token_amount_to_raw(dec!(1.1234567), 6, "USDC");
+        assert!(matches!(result, Err(MCPError::InvalidAmount(_))));
+
+        assert_eq!(EthereumClient::token_amount_to_raw(dec!(1.123456), 6, "USDC").unwrap(), U256::from(1_123_456u64));
+        // Trailing zeros are not extra precision
+        assert_eq!(EthereumClient::token_amount_to_raw(dec!(1.1000000), 6, "USDC").unwrap(), U256::from(1_100_000u64));
+    }
+
+    #[test]
+    fn test_token_standard_serialization() {
+        use crate::types::TokenStandard;
+
+        assert_eq!(json!(TokenStandard::Erc20), json!("erc20"));
+        assert_eq!(json!(TokenStandard::Erc721), json!("erc721"));
+        assert_eq!(json!(TokenStandard::Erc1155), json!("erc1155"));
+    }
+
+    #[test]
+    fn test_get_balance_confirmations_validation() {
+        use crate::error::InputValidator;
+
+        let address = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+        let args = json!({ "address": address, "confirmations": 12 });
+        assert!(InputValidator::validate_tool_parameters("get_balance", &args).is_ok());
+        let args = json!({ "address": address, "confirmations": -1 });
+        assert!(InputValidator::validate_tool_parameters("get_balance", &args).is_err());
+        let args = json!({ "address": address, "confirmations": 12, "block_number": "100" });
+        assert!(InputValidator::validate_tool_parameters("get_balance", &args).is_err());
+    }
+
+    #[test]
+    fn test_validate_address_tool() {
+        use crate::tools::ToolHandler;
+
+        let result = ToolHandler::check_address("0xd8da6bf26964af9d7eed9e03e53415d37aa96045");
+        assert!(result.valid);
+        assert_eq!(result.checksummed.as_deref(), Some("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"));
+        assert!(!result.is_ens);
+
+        let bad_checksum = ToolHandler::check_address("0xD8da6bf26964af9d7eed9e03e53415d37aa96045");
+        assert!(!bad_checksum.valid);
+        assert!(bad_checksum.reason.is_some());
+
+        let ens = ToolHandler::check_address("vitalik.eth");
+        assert!(ens.is_ens);
+        assert!(!ens.valid);
+        assert!(ens.checksummed.is_none());
+
+        assert!(!ToolHandler::check_address("0x1234").valid);
+    }
+
+    #[test]
+    fn test_select_best_swap_quote() {
+        use crate::ethereum::EthereumClient;
+        use crate::types::SwapSource;
+
+        let u = ethers::types::U256::from;
+        assert_eq!(EthereumClient::select_best_quote(Some((3000, u(100))), Some(u(101))), Some((SwapSource::UniswapV2, None, u(101))));
+        assert_eq!(EthereumClient::select_best_quote(Some((500, u(100))), Some(u(100))), Some((SwapSource::UniswapV3, Some(500), u(100))));
+        assert_eq!(EthereumClient::select_best_quote(Some((500, u(100))), None), Some((SwapSource::UniswapV3, Some(500), u(100))));
+        assert_eq!(EthereumClient::select_best_quote(None, Some(u(7))), Some((SwapSource::UniswapV2, None, u(7))));
+        assert_eq!(EthereumClient::select_best_quote(None, None), None);
+        assert_eq!(serde_json::to_value(SwapSource::UniswapV2).unwrap(), json!("uniswap_v2"));
+        assert_eq!(serde_json::to_value(SwapSource::UniswapV3).unwrap(), json!("uniswap_v3"));
+    }
+
+    #[test]
+    fn test_disable_mainnet_writes() {
+        use crate::ethereum::EthereumClient;
+
+        assert!(matches!(
+            EthereumClient::ensure_writes_allowed(1, true),
+            Err(crate::error::MCPError::ConfigurationError(_))
+        ));
+        assert!(EthereumClient::ensure_writes_allowed(11155111, true).is_ok());
+        assert!(EthereumClient::ensure_writes_allowed(1, false).is_ok());
+    }
+
+    #[test]
+    fn test_route_hop_serialization() {
+        use crate::types::{RouteHop, SwapSource};
+
+        let hop = RouteHop {
+            token_in: "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2".to_string(),
+            token_out: "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".to_string(),
+            token_in_symbol: "ETH".to_string(),
+            token_out_symbol: "USDC".to_string(),
+            source: SwapSource::UniswapV2,
+            fee_tier: None,
+        };
+        let value = serde_json::to_value(&hop).unwrap();
+        assert_eq!(value["token_in_symbol"], "ETH");
+        assert_eq!(value["source"], "uniswap_v2");
+        assert!(value["fee_tier"].is_null());
+    }
+
+    #[test]
+    fn test_parse_http_headers() {
+        use crate::ethereum::parse_http_headers;
+
+        let headers = parse_http_headers(" X-Team=payments, ,X-Env = staging ").unwrap();
+        assert_eq!(headers.len(), 2);
+        assert_eq!(headers.get("x-team").unwrap(), "payments");
+        assert_eq!(headers.get("x-env").unwrap(), "staging");
+
+        assert!(parse_http_headers("").unwrap().is_empty());
+        assert!(parse_http_headers("X-Team").is_err());
+        assert!(parse_http_headers("Bad Name=value").is_err());
+    }
+
+    #[test]
+    fn test_get_eth_price_takes_no_arguments() {
+        use crate::error::InputValidator;
+
+        assert!(InputValidator::validate_tool_parameters("get_eth_price", &json!({})).is_ok());
+    }
+
+    #[test]
+    fn test_validate_webhook_url() {
+        use crate::error::InputValidator;
+
+        assert!(InputValidator::validate_webhook_url("https://example.com/hooks/tx").is_ok());
+        assert!(InputValidator::validate_webhook_url("http://localhost:9000").is_ok());
+        assert!(InputValidator::validate_webhook_url("ftp://example.com").is_err());
+        assert!(InputValidator::validate_webhook_url("not a url").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resources_list_and_read() {
+        let server = offline_server().await;
+
+        let list = MCPRequest {
+            jsonrpc: "2.0".to_string(),
+            id: json!(1),
+            method: "resources/list".to_string(),
+            params: json!({}),
+            request_context: None,
+        };
+        let response = server.handle_request(list).await.unwrap();
+        let resources = response.result.unwrap()["resources"].as_array().unwrap().clone();
+        assert_eq!(resources.len(), 3);
+
+        let read = MCPRequest {
+            jsonrpc: "2.0".to_string(),
+            id: json!(2),
+            method: "resources/read".to_string(),
+            params: json!({ "uri": "wallet://network" }),
+            request_context: None,
+        };
+        let response = server.handle_request(read).await.unwrap();
+        let text = response.result.unwrap()["contents"][0]["text"].as_str().unwrap().to_string();
+        let network: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(network["chain_id"], 1);
+
+        assert!(server.read_resource("wallet://unknown").is_err());
+    }
+
+    #[test]
+    fn test_zero_decimals_token_balance() {
+        use crate::ethereum::{EthereumClient, TokenMetadata};
+        use crate::types::MetadataSource;
+        use ethers::types::{Address, U256};
+
+        let metadata = TokenMetadata {
+            name: "Zero Decimals".to_string(),
+            symbol: "ZERO".to_string(),
+            decimals: 0,
+            source: MetadataSource::Onchain,
+            logo: None,
+        };
+        let balance = EthereumClient::build_token_balance(Address::repeat_byte(0x11), U256::from(1_234_567u64), metadata, 6);
+
+        assert_eq!(balance.decimals, 0);
+        assert_eq!(balance.balance_formatted, "1234567");
+        assert_eq!(balance.balance_formatted, balance.balance.to_string());
+        assert_eq!(EthereumClient::scale_token_amount(rust_decimal_macros::dec!(42), 0), rust_decimal_macros::dec!(42));
+    }
+
+    #[test]
+    fn test_price_source_selection() {
+        use crate::error::InputValidator;
+        use crate::types::PriceSourceSelection;
+
+        assert_eq!("auto".parse::<PriceSourceSelection>().unwrap(), PriceSourceSelection::Auto);
+        assert_eq!("OnChain".parse::<PriceSourceSelection>().unwrap(), PriceSourceSelection::Onchain);
+        assert_eq!(PriceSourceSelection::default(), PriceSourceSelection::Auto);
+        assert!("coingecko".parse::<PriceSourceSelection>().is_err());
+
+        let token = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+        assert!(InputValidator::validate_tool_parameters("get_token_price", &json!({ "token_address": token, "source": "auto" })).is_ok());
+        assert!(InputValidator::validate_tool_parameters("get_token_price", &json!({ "token_address": token, "source": "dex" })).is_err());
+        assert!(InputValidator::validate_tool_parameters("get_token_price", &json!({ "token_address": token, "source": 1 })).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_read_only_mode_disables_signing_tools() {
+        use crate::error::MCPError;
+        use crate::ethereum::{EthereumClient, PLACEHOLDER_PRIVATE_KEY};
+        use crate::network::Network;
+        use crate::tools::ToolHandler;
+        use crate::types::ToolCall;
+
+        // Nothing listens here; chain ID validation is skipped when the RPC is unreachable
+        let client = EthereumClient::with_network("http://127.0.0.1:1".to_string(), PLACEHOLDER_PRIVATE_KEY.to_string(), Network::Mainnet)
+            .await
+            .unwrap();
+        assert!(!client.wallet_configured());
+        let handler = ToolHandler::with_config(client, &offline_config()).unwrap();
+
+        assert!(!handler.is_tool_enabled("send_transaction"));
+        assert!(!handler.is_tool_enabled("transfer_token"));
+        assert!(handler.is_tool_enabled("get_balance"));
+
+        let call = ToolCall {
+            name: "send_transaction".to_string(),
+            arguments: json!({ "to": "0x0000000000000000000000000000000000000001", "amount": "0.1" }),
+        };
+        assert!(matches!(handler.handle_tool_call(call).await, Err(MCPError::WalletNotInitialized(_))));
+    }
+
+    #[test]
+    fn test_slippage_cap() {
+        use crate::error::{InputValidator, MCPError};
+        use rust_decimal_macros::dec;
+
+        assert_eq!(InputValidator::validate_slippage_with_cap("3", dec!(3)).unwrap(), dec!(3));
+        assert!(matches!(
+            InputValidator::validate_slippage_with_cap("3.5", dec!(3)),
+            Err(MCPError::InvalidSlippage(_))
+        ));
+        assert!(InputValidator::validate_slippage_with_cap("50", dec!(50)).is_ok());
+        assert!(InputValidator::validate_slippage("50.1").is_err());
+    }
+
+    #[test]
+    fn test_get_portfolio_validation() {
+        use crate::error::InputValidator;
+
+        let address = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+        assert!(InputValidator::validate_tool_parameters("get_portfolio", &json!({ "address": address })).is_ok());
+        assert!(InputValidator::validate_tool_parameters("get_portfolio", &json!({
+            "address": address,
+            "token_addresses": ["0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"]
+        })).is_ok());
+        assert!(InputValidator::validate_tool_parameters("get_portfolio", &json!({ "address": address, "token_addresses": ["0x1234"] })).is_err());
+        assert!(InputValidator::validate_tool_parameters("get_portfolio", &json!({ "address": address, "token_addresses": "0xA0b8" })).is_err());
+        assert!(InputValidator::validate_tool_parameters("get_portfolio", &json!({})).is_err());
+    }
+
+    /// Serve every JSON-RPC request with the same `result` from a local HTTP endpoint
+    async fn spawn_mock_rpc(result: &'static str) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 8192];
+                let _ = socket.read(&mut buf).await;
+                let body = format!(r#"{{"jsonrpc":"2.0","id":1,"result":{}}}"#, result);
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(), body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        url
+    }
+
+    /// Serve the offline mock chain (as mainnet) from a local HTTP endpoint. `answer` may
+    /// replace the result or error of any call; returning None keeps the mock chain's answer.
+    async fn spawn_mock_chain<F>(answer: F) -> String
+    where
+        F: Fn(&str, &serde_json::Value) -> Option<Result<serde_json::Value, String>> + Send + Sync + 'static,
+    {
+        use axum::{routing::post, Json, Router};
+
+        let answer = std::sync::Arc::new(answer);
+        let app = Router::new().route("/", post(move |Json(request): Json<serde_json::Value>| {
+            let answer = answer.clone();
+            async move {
+                let method = request["method"].as_str().unwrap_or_default();
+                let params = &request["params"];
+                let result = answer(method, params)
+                    .unwrap_or_else(|| crate::mock::rpc_response(method, params, 1));
+                Json(match result {
+                    Ok(result) => json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }),
+                    Err(message) => json!({ "jsonrpc": "2.0", "id": request["id"], "error": { "code": -32000, "message": message } }),
+                })
+            }
+        }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_empty_metadata_response_is_invalid_token_contract() {
+        use crate::error::MCPError;
+        use crate::ethereum::{EthereumClient, PLACEHOLDER_PRIVATE_KEY};
+        use crate::network::Network;
+        use ethers::types::Address;
+
+        // Every eth_call answers 0x, as a contract without name()/symbol()/decimals() does
+        let rpc_url = spawn_mock_rpc(r#""0x""#).await;
+        let client = EthereumClient::with_network(rpc_url, PLACEHOLDER_PRIVATE_KEY.to_string(), Network::Mainnet)
+            .await
+            .unwrap();
+
+        let result = client.get_token_info(Address::repeat_byte(0x42), None).await;
+        assert!(matches!(result, Err(MCPError::InvalidTokenContract(_))));
+    }
+
+    #[tokio::test]
+    async fn test_degraded_price_reports_both_errors() {
+        use crate::address::ParsedAddress;
+        use crate::ethereum::{EthereumClient, PLACEHOLDER_PRIVATE_KEY};
+        use crate::network::Network;
+        use crate::types::PriceSourceSelection;
+
+        // Sepolia has no Alchemy price feed and the RPC is unreachable, so both sources fail
+        let client = EthereumClient::with_network("http://127.0.0.1:1".to_string(), PLACEHOLDER_PRIVATE_KEY.to_string(), Network::Sepolia)
+            .await
+            .unwrap();
+        let token = ParsedAddress::parse("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48").unwrap();
+        match client.get_token_price(token, PriceSourceSelection::Auto).await {
+            Err(crate::error::MCPError::PriceFetchFailed(msg)) => {
+                assert!(msg.contains("Alchemy:"), "{}", msg);
+                assert!(msg.contains("on-chain:"), "{}", msg);
+            },
+            other => panic!("expected PriceFetchFailed, got {:?}", other),
+        }
+    }
+
+    /// Prices every token at `price_usd` and reverts every eth_call; the builders below
+    /// add a pool quote, restrict pricing, slow it down or make it fail
+    struct MockTransport {
+        quote: Option<u128>,
+        price_usd: rust_decimal::Decimal,
+        /// Only this token is priced, when set
+        priced_token: Option<&'static str>,
+        price_delay: std::time::Duration,
+        /// Price fetches left to fail with a network error
+        price_failures: std::sync::atomic::AtomicU32,
+        price_calls: std::sync::atomic::AtomicU32,
+    }
+
+    impl MockTransport {
+        fn new(price_usd: rust_decimal::Decimal) -> Self {
+            Self {
+                quote: None,
+                price_usd,
+                priced_token: None,
+                price_delay: std::time::Duration::ZERO,
+                price_failures: std::sync::atomic::AtomicU32::new(0),
+                price_calls: std::sync::atomic::AtomicU32::new(0),
+            }
+        }
+
+        /// Answer every eth_call with `quote`
+        fn with_quote(mut self, quote: u128) -> Self {
+            self.quote = Some(quote);
+            self
+        }
+
+        /// Price only `token`; every other token is absent from the response
+        fn pricing_only(mut self, token: &'static str) -> Self {
+            self.priced_token = Some(token);
+            self
+        }
+
+        /// Answer price requests only after `delay`
+        fn with_price_delay(mut self, delay: std::time::Duration) -> Self {
+            self.price_delay = delay;
+            self
+        }
+
+        /// Fail the first `failures` price fetches with a network error
+        fn failing_prices(self, failures: u32) -> Self {
+            self.price_failures.store(failures, std::sync::atomic::Ordering::SeqCst);
+            self
+        }
+
+        fn price_calls(&self) -> u32 {
+            self.price_calls.load(std::sync::atomic::Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl crate::transport::RpcTransport for MockTransport {
+        async fn eth_call(&self, _to: ethers::types::Address, _data: Vec<u8>, _block: Option<ethers::types::BlockId>) -> Result<Vec<u8>, crate::error::MCPError> {
+            match self.quote {
+                Some(quote) => Ok(ethers::abi::encode(&[ethers::abi::Token::Uint(quote.into())])),
+                None => Err(crate::error::MCPError::EthereumRpc("execution reverted".to_string())),
+            }
+        }
+
+        async fn estimate_gas(&self, _from: ethers::types::Address, _to: ethers::types::Address, _data: Vec<u8>) -> Result<u64, crate::error::MCPError> {
+            Err(crate::error::MCPError::GasEstimationFailed("execution reverted".to_string()))
+        }
+
+        async fn fetch_prices(&self, _network_slug: &str, token_addresses: &[&str]) -> Result<std::collections::HashMap<String, rust_decimal::Decimal>, crate::error::MCPError> {
+            use std::sync::atomic::Ordering;
+
+            self.price_calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(self.price_delay).await;
+            if self.price_failures.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |left| left.checked_sub(1)).is_ok() {
+                return Err(crate::error::MCPError::NetworkError("connection reset".to_string()));
+            }
+            Ok(token_addresses.iter()
+                .filter(|address| self.priced_token.is_none_or(|token| address.eq_ignore_ascii_case(token)))
+                .map(|address| (address.to_lowercase(), self.price_usd))
+                .collect())
+        }
+
+        async fn aggregator_quote(&self, _source: crate::types::SwapSource, _api_key: &str, _chain_id: u64, _sell_token: ethers::types::Address, _buy_token: ethers::types::Address, _amount: ethers::types::U256) -> Result<crate::transport::AggregatorQuote, crate::error::MCPError> {
+            Err(crate::error::MCPError::NetworkError("no aggregator".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mock_rpc_transport() {
+        use crate::address::ParsedAddress;
+        use crate::ethereum::{EthereumClient, PLACEHOLDER_PRIVATE_KEY};
+        use crate::network::Network;
+        use crate::types::{PriceSource, PriceSourceSelection};
+        use rust_decimal_macros::dec;
+        use std::sync::Arc;
+
+        // 1 USDC quotes at 0.0005 WETH and ETH trades at $2000
+        let client = EthereumClient::with_network("http://127.0.0.1:1".to_string(), PLACEHOLDER_PRIVATE_KEY.to_string(), Network::Mainnet)
+            .await
+            .unwrap()
+            .with_rpc_transport(Arc::new(MockTransport::new(dec!(2000)).with_quote(500_000_000_000_000)));
+        let usdc = ParsedAddress::parse("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48").unwrap();
+
+        let price = client.get_token_price(usdc, PriceSourceSelection::Alchemy).await.unwrap();
+        assert_eq!(price.price_usd, dec!(2000));
+        assert_eq!(price.source, PriceSource::Alchemy);
+
+        let onchain = client.get_onchain_price(usdc).await.unwrap();
+        assert_eq!(onchain.price_weth, dec!(0.0005));
+        assert_eq!(onchain.eth_price_usd, Some(dec!(2000)));
+        assert_eq!(onchain.price_usd, Some(dec!(1)));
+    }
+
+    #[test]
+    fn test_native_symbol_and_balance_alias() {
+        use crate::network::Network;
+        use crate::types::BalanceInfo;
+
+        assert_eq!(Network::Mainnet.native_symbol(), "ETH");
+        assert_eq!(Network::Base.native_symbol(), "ETH");
+        assert_eq!(Network::Polygon.native_symbol(), "MATIC");
+
+        // Payloads from before the rename still deserialize
+        let legacy: BalanceInfo = serde_json::from_value(json!({
+            "address": "0xabc",
+            "native_symbol": "ETH",
+            "eth_balance": "1.5",
+            "eth_usd_value": null,
+            "token_balances": {},
+            "total_usd_value": null
+        })).unwrap();
+        assert_eq!(legacy.native_balance, rust_decimal_macros::dec!(1.5));
+        let value = serde_json::to_value(&legacy).unwrap();
+        assert_eq!(value["native_balance"], "1.5");
+        assert!(value.get("eth_balance").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_error_recovery_retry_counts_attempts() {
+        use crate::error::{ErrorRecovery, MCPError};
+        use crate::logging::RequestContext;
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use std::time::Duration;
+
+        // A timeout is retried (after 1s) and the second attempt succeeds
+        let mut ctx = RequestContext::new("tools/call".to_string());
+        let calls = AtomicU32::new(0);
+        let result = ErrorRecovery::retry(&mut ctx, "eth_call", Duration::from_secs(60), || async {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => Err(MCPError::RpcTimeout("slow node".to_string())),
+                _ => Ok(7),
+            }
+        }).await;
+        assert_eq!(result.unwrap(), 7);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(ctx.retry_count, 1);
+
+        // A tool timeout and an exhausted API quota are not retried
+        for error in [MCPError::Timeout("tool".to_string()), MCPError::ApiRateLimitExceeded("alchemy".to_string())] {
+            assert!(!ErrorRecovery::is_recoverable(&error));
+        }
+
+        // A retry whose delay would overrun the budget is not started
+        let mut ctx = RequestContext::new("tools/call".to_string());
+        let calls = AtomicU32::new(0);
+        let started = std::time::Instant::now();
+        let result: Result<(), MCPError> = ErrorRecovery::retry(&mut ctx, "eth_call", Duration::from_secs(3), || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(MCPError::NetworkError("connection reset".to_string()))
+        }).await;
+        assert!(matches!(result, Err(MCPError::NetworkError(_))));
+        // Delays of 1s and 2s would reach the budget, so only the first retry runs
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert!(started.elapsed() < Duration::from_secs(3));
+
+        // Non-recoverable errors are returned without retrying
+        let mut ctx = RequestContext::new("tools/call".to_string());
+        let calls = AtomicU32::new(0);
+        let result: Result<(), MCPError> = ErrorRecovery::retry(&mut ctx, "eth_call", Duration::from_secs(60), || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(MCPError::InvalidAddress("0x".to_string()))
+        }).await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(ctx.retry_count, 0);
+    }
+
+    #[test]
+    fn test_list_networks() {
+        use crate::error::InputValidator;
+        use crate::network::Network;
+        use crate::tools::ToolHandler;
+
+        let networks = ToolHandler::network_infos(Network::Polygon);
+        assert_eq!(networks.len(), Network::ALL.len());
+        let enabled: Vec<_> = networks.iter().filter(|n| n.enabled).collect();
+        assert_eq!(enabled.len(), 1);
+        assert_eq!(enabled[0].name, "polygon");
+        assert_eq!(enabled[0].chain_id, 137);
+        assert_eq!(enabled[0].native_symbol, "MATIC");
+
+        assert!(InputValidator::validate_tool_parameters("list_networks", &json!({})).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_rejects_self_transfer() {
+        use crate::address::ParsedAddress;
+        use crate::ethereum::EthereumClient;
+        use crate::network::Network;
+        use crate::types::TransactionFeeOptions;
+        use rust_decimal_macros::dec;
+
+        // Well-known development key; the guard fires before any RPC call
+        let key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+        let client = EthereumClient::with_network("http://127.0.0.1:1".to_string(), key.to_string(), Network::Mainnet)
+            .await
+            .unwrap();
+        let own = ParsedAddress::parse("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap();
+
+        let result = client.send_transaction(own, dec!(0.1), TransactionFeeOptions::default(), false, false).await;
+        assert!(matches!(result, Err(crate::error::MCPError::ValidationError(_))), "{:?}", result.err());
+
+        // With the override the request gets past the guard and fails on the unreachable RPC instead
+        let result = client.send_transaction(own, dec!(0.1), TransactionFeeOptions::default(), false, true).await;
+        assert!(!matches!(result, Err(crate::error::MCPError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_minimum_received_applies_slippage() {
+        use crate::ethereum::EthereumClient;
+        use rust_decimal_macros::dec;
+
+        assert_eq!(EthereumClient::minimum_received(dec!(2000), dec!(0.5)), dec!(1990));
+        assert_eq!(EthereumClient::minimum_received(dec!(2000), dec!(0)), dec!(2000));
+    }
+
+    #[test]
+    fn test_config_file_parsing() {
+        use crate::config::{Config, FileConfig};
+        use crate::network::Network;
+        use std::path::Path;
+
+        let toml = r#"
+            rpc_url = "https://rpc.example.com"
+            network = "polygon"
+            timeout_secs = 15
+            enabled_tools = ["get_balance", "get_token_price"]
+            default_tokens = ["0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"]
+        "#;
+        let config = Config::resolve(FileConfig::parse(Path::new("config.toml"), toml).unwrap()).unwrap();
+        assert_eq!(config.rpc_url, "https://rpc.example.com");
+        assert_eq!(config.network, Network::Polygon);
+        assert_eq!(config.timeout_secs, 15);
+        assert!(config.enabled_tools.as_ref().unwrap().contains("get_token_price"));
+        assert_eq!(config.default_tokens.len(), 1);
+
+        // JSON is chosen by extension and yields the same configuration
+        let json = r#"{
+            "rpc_url": "https://rpc.example.com",
+            "network": "polygon",
+            "timeout_secs": 15,
+            "enabled_tools": ["get_balance", "get_token_price"],
+            "default_tokens": ["0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"]
+        }"#;
+        assert_eq!(Config::resolve(FileConfig::parse(Path::new("config.json"), json).unwrap()).unwrap(), config);
+
+        // Typos and bad values are configuration errors rather than silently ignored
+        assert!(FileConfig::parse(Path::new("config.toml"), "rpc_ur = \"x\"").is_err());
+        let bad_network = FileConfig { network: Some("solana".to_string()), ..file_config() };
+        assert!(Config::resolve(bad_network).is_err());
+        let bad_token = FileConfig { default_tokens: Some(vec!["0x123".to_string()]), ..file_config() };
+        assert!(Config::resolve(bad_token).is_err());
+    }
+
+    #[test]
+    fn test_config_file_gas_and_logs_settings() {
+        use crate::config::{Config, FileConfig};
+        use rust_decimal_macros::dec;
+        use std::path::Path;
+
+        let toml = r#"
+            rpc_url = "https://rpc.example.com"
+            gas_limit_multiplier = 1.5
+            gas_warn_gwei = 40
+            logs_max_range = 5000
+            logs_chunk_size = 500
+            disable_mainnet_writes = true
+        "#;
+        let config = Config::resolve(FileConfig::parse(Path::new("config.toml"), toml).unwrap()).unwrap();
+        assert_eq!(config.gas_limit_multiplier, dec!(1.5));
+        assert_eq!(config.gas_warn_gwei, dec!(40));
+        assert_eq!(config.logs_max_range, 5000);
+        assert_eq!(config.logs_chunk_size, 500);
+        assert!(config.disable_mainnet_writes);
+
+        // File values are validated like their environment counterparts
+        let bad_multiplier = FileConfig { gas_limit_multiplier: Some(dec!(0.5)), ..file_config() };
+        assert!(Config::resolve(bad_multiplier).is_err());
+        let bad_gwei = FileConfig { gas_warn_gwei: Some(dec!(0)), ..file_config() };
+        assert!(Config::resolve(bad_gwei).is_err());
+
+        let webhook = FileConfig { confirmation_webhook_url: Some(" https://example.com/hooks/tx ".to_string()), ..file_config() };
+        assert_eq!(Config::resolve(webhook).unwrap().confirmation_webhook_url.as_deref(), Some("https://example.com/hooks/tx"));
+        let bad_webhook = FileConfig { confirmation_webhook_url: Some("ftp://example.com".to_string()), ..file_config() };
+        assert!(Config::resolve(bad_webhook).is_err());
+    }
+
+    #[test]
+    fn test_config_file_server_settings() {
+        use crate::config::{Config, FileConfig};
+        use rust_decimal_macros::dec;
+        use std::path::Path;
+
+        let toml = r#"
+            rpc_urls = ["https://rpc-a.example.com", " https://rpc-b.example.com "]
+            tool_timeout_secs = 20
+            default_slippage_percent = 1
+            max_slippage_percent = 5
+            idempotency_ttl_secs = 600
+            price_circuit_failure_threshold = 2
+            price_circuit_cooldown_secs = 10
+            max_request_bytes = 4096
+            allowed_origins = ["http://localhost:5173"]
+            pretty_json = true
+            rate_limit_per_minute = 30
+            trust_client_id_header = true
+        "#;
+        let config = Config::resolve(FileConfig::parse(Path::new("config.toml"), toml).unwrap()).unwrap();
+        assert_eq!(config.rpc_urls, vec!["https://rpc-a.example.com", "https://rpc-b.example.com"]);
+        assert_eq!(config.rpc_url, "https://rpc-a.example.com");
+        assert_eq!(config.tool_timeout_secs, 20);
+        assert_eq!(config.default_slippage_percent, dec!(1));
+        assert_eq!(config.max_slippage_percent, dec!(5));
+        assert_eq!(config.idempotency_ttl_secs, 600);
+        assert_eq!((config.price_circuit_failure_threshold, config.price_circuit_cooldown_secs), (2, 10));
+        assert_eq!(config.max_request_bytes, 4096);
+        assert_eq!(config.allowed_origins, vec!["http://localhost:5173"]);
+        assert!(config.pretty_json);
+        assert_eq!(config.rate_limit_per_minute, 30);
+        assert!(config.trust_client_id_header);
+
+        // The slippage ceiling stays within 50% and bounds the default
+        let over_absolute = FileConfig { max_slippage_percent: Some(dec!(60)), ..file_config() };
+        assert!(Config::resolve(over_absolute).is_err());
+        let over_cap = FileConfig { max_slippage_percent: Some(dec!(2)), default_slippage_percent: Some(dec!(3)), ..file_config() };
+        assert!(Config::resolve(over_cap).is_err());
+
+        // Offline mode needs no endpoint; otherwise one must be configured
+        assert!(Config::resolve(FileConfig { offline_mode: Some(true), ..FileConfig::default() }).is_ok());
+        if env::var("RPC_URL").is_err() && env::var("RPC_URLS").is_err() {
+            assert!(matches!(Config::resolve(FileConfig::default()), Err(crate::error::MCPError::ConfigurationError(_))));
+        }
+    }
+
+    #[test]
+    fn test_keccak256_and_function_selector() {
+        use crate::error::InputValidator;
+        use crate::tools::ToolHandler;
+
+        let selector = ToolHandler::function_selector("transfer(address,uint256)");
+        assert_eq!(selector.selector, "0xa9059cbb");
+        assert_eq!(ToolHandler::function_selector("balanceOf(address)").selector, "0x70a08231");
+
+        // keccak256 of empty input, and the same bytes given as text or hex
+        let empty = InputValidator::validate_hash_input("", None).unwrap();
+        assert_eq!(hex::encode(ethers::utils::keccak256(empty)), "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470");
+        assert_eq!(
+            InputValidator::validate_hash_input("abc", Some("utf8")).unwrap(),
+            InputValidator::validate_hash_input("0x616263", Some("hex")).unwrap()
+        );
+        assert!(InputValidator::validate_hash_input("0xzz", Some("hex")).is_err());
+        assert!(InputValidator::validate_hash_input("abc", Some("base64")).is_err());
+
+        assert!(InputValidator::validate_tool_parameters("function_selector", &json!({ "signature": "transfer(address,uint256)" })).is_ok());
+        assert!(InputValidator::validate_tool_parameters("function_selector", &json!({ "signature": "transfer(address to, uint256 amount)" })).is_err());
+        assert!(InputValidator::validate_tool_parameters("function_selector", &json!({ "signature": "transfer" })).is_err());
+        assert!(InputValidator::validate_tool_parameters("keccak256", &json!({ "data": "0x1234", "encoding": "hex" })).is_ok());
+        assert!(InputValidator::validate_tool_parameters("keccak256", &json!({})).is_err());
+    }
+
+    #[test]
+    fn test_huge_token_balance_does_not_panic() {
+        use crate::ethereum::{EthereumClient, TokenMetadata};
+        use crate::types::MetadataSource;
+        use ethers::types::{Address, U256};
+        use rust_decimal::Decimal;
+
+        let metadata = || TokenMetadata {
+            name: "Huge Supply".to_string(),
+            symbol: "HUGE".to_string(),
+            decimals: 18,
+            source: MetadataSource::Onchain,
+            logo: None,
+        };
+
+        // Near U256::MAX: far beyond both u128 and Decimal
+        let raw = U256::MAX - U256::from(1u64);
+        let balance = EthereumClient::build_token_balance(Address::repeat_byte(0x22), raw, metadata(), 6);
+        assert_eq!(balance.balance, Decimal::MAX);
+        assert_eq!(balance.balance_raw, raw.to_string());
+        assert_eq!(
+            balance.balance_formatted,
+            "115792089237316195423570985008687907853269984665640564039457.584008"
+        );
+
+        // Above u128::MAX but representable in the formatted units
+        let raw = U256::from(u128::MAX) * U256::from(10u64);
+        let balance = EthereumClient::build_token_balance(Address::repeat_byte(0x22), raw, metadata(), 2);
+        assert_eq!(balance.balance_formatted, "3402823669209384634633.75");
+
+        // Values in range keep the Decimal path
+        let balance = EthereumClient::build_token_balance(Address::repeat_byte(0x22), U256::exp10(18) * 3 / 2, metadata(), 6);
+        assert_eq!(balance.balance, rust_decimal_macros::dec!(1500000000000000000));
+        assert_eq!(balance.balance_raw, "1500000000000000000");
+        assert_eq!(EthereumClient::format_u256_units(U256::exp10(18) * 3 / 2, 18, 6), "1.5");
+        assert_eq!(EthereumClient::format_u256_units(U256::from(5u64), 0, 6), "5");
+        assert_eq!(EthereumClient::format_u256_units(U256::from(15u64), 2, 1), "0.2");
+    }
+
+    #[test]
+    fn test_project_fields() {
+        use crate::error::InputValidator;
+        use crate::tools::project_fields;
+
+        let balance = json!({
+            "address": "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045",
+            "native_symbol": "ETH",
+            "native_balance": "1.5",
+            "token_balances": {
+                "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48": { "symbol": "USDC", "balance": "1000000", "balance_formatted": "1" },
+                "0xdAC17F958D2ee523a2206206994597C13D831ec7": { "symbol": "USDT", "balance": "0", "balance_formatted": "0" }
+            },
+            "total_usd_value": null
+        });
+        let fields = vec!["native_balance".to_string(), "token_balances.symbol".to_string(), "token_balances.balance_formatted".to_string(), "missing".to_string()];
+        assert_eq!(project_fields(&balance, &fields), json!({
+            "native_balance": "1.5",
+            "token_balances": {
+                "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48": { "symbol": "USDC", "balance_formatted": "1" },
+                "0xdAC17F958D2ee523a2206206994597C13D831ec7": { "symbol": "USDT", "balance_formatted": "0" }
+            }
+        }));
+
+        // Arrays project each element
+        let portfolio = json!({ "tokens": [{ "symbol": "USDC", "price_usd": "1" }, { "symbol": "WETH", "price_usd": "2000" }] });
+        assert_eq!(project_fields(&portfolio, &["tokens.symbol".to_string()]), json!({ "tokens": [{ "symbol": "USDC" }, { "symbol": "WETH" }] }));
+
+        let args = |fields: serde_json::Value| json!({ "address": "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045", "fields": fields });
+        assert!(InputValidator::validate_tool_parameters("get_balance", &args(json!(["native_balance"]))).is_ok());
+        assert!(InputValidator::validate_tool_parameters("get_balance", &args(json!([]))).is_err());
+        assert!(InputValidator::validate_tool_parameters("get_balance", &args(json!("native_balance"))).is_err());
+        assert!(InputValidator::validate_tool_parameters("get_balance", &args(json!(["token_balances..symbol"]))).is_err());
+    }
+
+    #[test]
+    fn test_replace_transaction_fee_bump() {
+        use crate::error::{InputValidator, MCPError};
+        use crate::ethereum::EthereumClient;
+        use ethers::types::U256;
+        use rust_decimal_macros::dec;
+
+        assert_eq!(EthereumClient::bump_fee(U256::from(20_000_000_000u64), dec!(10)), U256::from(22_000_000_000u64));
+        // Rounds up so the replacement always outbids
+        assert_eq!(EthereumClient::bump_fee(U256::from(1u64), dec!(10)), U256::from(2u64));
+        assert_eq!(EthereumClient::bump_fee(U256::from(1000u64), dec!(12.5)), U256::from(1125u64));
+
+        let args = json!({ "nonce": 7, "bump_percent": 25, "cancel": true });
+        assert!(InputValidator::validate_tool_parameters("replace_transaction", &args).is_ok());
+        assert!(InputValidator::validate_tool_parameters("replace_transaction", &json!({ "nonce": 7, "bump_percent": "15.5" })).is_ok());
+        assert!(matches!(
+            InputValidator::validate_tool_parameters("replace_transaction", &json!({})),
+            Err(MCPError::MissingParameter(_))
+        ));
+        assert!(InputValidator::validate_tool_parameters("replace_transaction", &json!({ "nonce": -1 })).is_err());
+        assert!(matches!(
+            InputValidator::validate_tool_parameters("replace_transaction", &json!({ "nonce": 7, "bump_percent": 5 })),
+            Err(MCPError::ValidationError(_))
+        ));
+        assert!(InputValidator::validate_tool_parameters("replace_transaction", &json!({ "nonce": 7, "cancel": "yes" })).is_err());
+    }
+
+    #[test]
+    fn test_decode_transfer_log() {
+        use crate::ethereum::EthereumClient;
+        use ethers::types::{Address, Bytes, Log, H256, U256};
+
+        let transfer_topic = H256::from(ethers::utils::keccak256("Transfer(address,address,uint256)"));
+        let from = Address::from_low_u64_be(0xaa);
+        let to = Address::from_low_u64_be(0xbb);
+        let mut value = [0u8; 32];
+        U256::from(1_500_000u64).to_big_endian(&mut value);
+
+        let log = Log {
+            topics: vec![transfer_topic, H256::from(from), H256::from(to)],
+            data: Bytes::from(value.to_vec()),
+            ..Default::default()
+        };
+        assert_eq!(EthereumClient::decode_transfer_log(&log), Some((from, to, U256::from(1_500_000u64))));
+        assert_eq!(EthereumClient::format_u256_units(U256::from(1_500_000u64), 6, 6), "1.5");
+
+        // ERC721 Transfer: same signature, token ID indexed as a fourth topic
+        let nft = Log {
+            topics: vec![transfer_topic, H256::from(from), H256::from(to), H256::from_low_u64_be(7)],
+            ..Default::default()
+        };
+        assert!(EthereumClient::decode_transfer_log(&nft).is_none());
+
+        let approval = Log {
+            topics: vec![H256::from(ethers::utils::keccak256("Approval(address,address,uint256)")), H256::from(from), H256::from(to)],
+            data: Bytes::from(value.to_vec()),
+            ..Default::default()
+        };
+        assert!(EthereumClient::decode_transfer_log(&approval).is_none());
+    }
+
+    #[test]
+    fn test_idempotency_cache() {
+        use crate::error::{InputValidator, MCPError};
+        use crate::idempotency::{IdempotencyCache, Reservation};
+        use std::time::Duration;
+
+        let cache = IdempotencyCache::new(Duration::from_secs(60));
+        assert_eq!(cache.reserve("send_transaction", "retry-1").unwrap(), Reservation::New);
+        // A second call while the first is still running must not broadcast
+        assert!(matches!(cache.reserve("send_transaction", "retry-1"), Err(MCPError::ValidationError(_))));
+
+        let result = json!({ "tx_hash": "0xabc" });
+        cache.complete("send_transaction", "retry-1", &result);
+        assert_eq!(cache.reserve("send_transaction", "retry-1").unwrap(), Reservation::Replay(result));
+        // Keys are scoped per tool
+        assert_eq!(cache.reserve("transfer_token", "retry-1").unwrap(), Reservation::New);
+
+        // A failed call frees its key
+        cache.release("transfer_token", "retry-1");
+        assert_eq!(cache.reserve("transfer_token", "retry-1").unwrap(), Reservation::New);
+
+        let expired = IdempotencyCache::new(Duration::ZERO);
+        expired.complete("send_transaction", "k", &json!({}));
+        assert_eq!(expired.reserve("send_transaction", "k").unwrap(), Reservation::New);
+
+        let args = json!({ "to": "0x0000000000000000000000000000000000000001", "amount": "0.1", "idempotency_key": "" });
+        assert!(InputValidator::validate_tool_parameters("send_transaction", &args).is_err());
+        let args = json!({ "to": "0x0000000000000000000000000000000000000001", "amount": "0.1", "idempotency_key": "order-42" });
+        assert!(InputValidator::validate_tool_parameters("send_transaction", &args).is_ok());
+    }
+
+    #[test]
+    fn test_swap_quote_currency() {
+        use crate::error::InputValidator;
+        use crate::types::QuoteCurrency;
+
+        assert_eq!("USD".parse::<QuoteCurrency>().unwrap(), QuoteCurrency::Usd);
+        assert_eq!(QuoteCurrency::default(), QuoteCurrency::Eth);
+        assert!("eur".parse::<QuoteCurrency>().is_err());
+
+        let mut args = json!({
+            "from_token": "ETH",
+            "to_token": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+            "amount": "1",
+            "quote_currency": "usd"
+        });
+        assert!(InputValidator::validate_tool_parameters("swap_tokens", &args).is_ok());
+        args["quote_currency"] = json!("btc");
+        assert!(InputValidator::validate_tool_parameters("swap_tokens", &args).is_err());
+        args["quote_currency"] = json!(1);
+        assert!(InputValidator::validate_tool_parameters("swap_tokens", &args).is_err());
+    }
+
+    #[test]
+    fn test_balance_info_scanned_tokens() {
+        use crate::types::BalanceInfo;
+
+        let info: BalanceInfo = serde_json::from_value(json!({
+            "address": "0x0000000000000000000000000000000000000001",
+            "native_symbol": "ETH",
+            "native_balance": "0",
+            "eth_usd_value": null,
+            "token_balances": {},
+            "scanned_tokens": ["0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"],
+            "total_usd_value": null
+        })).unwrap();
+        // Scanned but holding nothing, as opposed to a failed scan
+        assert!(info.token_balances.is_empty());
+        assert_eq!(info.scanned_tokens.len(), 1);
+
+        // Payloads from before the field existed still deserialize
+        let legacy: BalanceInfo = serde_json::from_value(json!({
+            "address": "0x0000000000000000000000000000000000000001",
+            "native_symbol": "ETH",
+            "eth_balance": "1",
+            "eth_usd_value": null,
+            "token_balances": {},
+            "total_usd_value": null
+        })).unwrap();
+        assert!(legacy.scanned_tokens.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_offline_mode_serves_mock_data() {
+        use crate::ethereum::EthereumClient;
+        use crate::tools::ToolHandler;
+        use crate::types::ToolCall;
+
+        let config = offline_config();
+        // Anvil's first development key; nothing is broadcast in offline mode
+        let key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".to_string();
+        let client = EthereumClient::with_config(&config, key).await.unwrap();
+        assert!(client.offline_mode());
+        let handler = ToolHandler::with_config(client, &config).unwrap();
+        let call = |name: &str, arguments: serde_json::Value| ToolCall { name: name.to_string(), arguments };
+        let usdc = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+        let wallet = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+
+        let balance = handler.handle_tool_call(call("get_balance", json!({ "address": wallet, "token_address": usdc }))).await.unwrap();
+        assert_eq!(balance.content["mock"], json!(true));
+        let decimal = |value: &serde_json::Value| value.as_str().unwrap().parse::<rust_decimal::Decimal>().unwrap();
+        assert_eq!(decimal(&balance.content["native_balance"]), rust_decimal_macros::dec!(1.5));
+        assert_eq!(balance.content["token_balances"][usdc]["balance_formatted"], json!("1000"));
+
+        let price = handler.handle_tool_call(call("get_token_price", json!({ "token_address": usdc }))).await.unwrap();
+        assert_eq!(decimal(&price.content["price_usd"]), rust_decimal_macros::dec!(1));
+
+        let swap = handler.handle_tool_call(call("swap_tokens", json!({ "from_token": "ETH", "to_token": usdc, "amount": "1" }))).await.unwrap();
+        assert_eq!(swap.content["mock"], json!(true));
+        assert!(swap.content["amount_out"].as_str().unwrap().starts_with("2991"));
+
+        let logs = handler.handle_tool_call(call("get_logs", json!({ "from_block": "18999990", "to_block": "19000000" }))).await.unwrap();
+        assert_eq!(logs.content["logs"][0]["transfer"]["value"], json!("100"));
+
+        let sent = handler.handle_tool_call(call("send_transaction", json!({ "to": usdc, "amount": "0.1" }))).await.unwrap();
+        assert_eq!(sent.content["status"], json!("confirmed"));
+        assert_eq!(sent.content["mock"], json!(true));
+
+        // Results that are not objects are wrapped
+        assert_eq!(crate::tools::mark_mock(json!([1])), json!({ "result": [1], "mock": true }));
+    }
+
+    /// Mock chain transport whose 0x quote is fixed and whose 1inch quote always fails
+    struct AggregatorTransport;
+
+    #[async_trait::async_trait]
+    impl crate::transport::RpcTransport for AggregatorTransport {
+        async fn eth_call(&self, to: ethers::types::Address, data: Vec<u8>, block: Option<ethers::types::BlockId>) -> Result<Vec<u8>, crate::error::MCPError> {
+            crate::mock::OfflineTransport.eth_call(to, data, block).await
+        }
+
+        async fn estimate_gas(&self, from: ethers::types::Address, to: ethers::types::Address, data: Vec<u8>) -> Result<u64, crate::error::MCPError> {
+            crate::mock::OfflineTransport.estimate_gas(from, to, data).await
+        }
+
+        async fn fetch_prices(&self, network_slug: &str, token_addresses: &[&str]) -> Result<std::collections::HashMap<String, rust_decimal::Decimal>, crate::error::MCPError> {
+            crate::mock::OfflineTransport.fetch_prices(network_slug, token_addresses).await
+        }
+
+        async fn aggregator_quote(&self, source: crate::types::SwapSource, _api_key: &str, _chain_id: u64, _sell_token: ethers::types::Address, _buy_token: ethers::types::Address, _amount: ethers::types::U256) -> Result<crate::transport::AggregatorQuote, crate::error::MCPError> {
+            match source {
+                crate::types::SwapSource::ZeroEx => Ok(crate::transport::AggregatorQuote { amount_out: 2_995_000_000u64.into(), gas_estimate: Some(180_000) }),
+                _ => Err(crate::error::MCPError::NetworkError("unavailable".to_string())),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_swap_aggregator_quotes() {
+        use crate::address::ParsedAddress;
+        use crate::config::{Config, FileConfig};
+        use crate::ethereum::{EthereumClient, PLACEHOLDER_PRIVATE_KEY};
+        use crate::types::SwapSource;
+        use rust_decimal_macros::dec;
+        use std::sync::Arc;
+
+        let file = |sources: &[&str]| FileConfig { quote_sources: Some(sources.iter().map(|s| s.to_string()).collect()), ..file_config() };
+        assert_eq!(Config::resolve(file(&["0x", " 1INCH"])).unwrap().quote_sources, vec![SwapSource::ZeroEx, SwapSource::OneInch]);
+        assert!(Config::resolve(file(&["uniswap_v3"])).is_err());
+        assert!(Config::resolve(file(&["paraswap"])).is_err());
+        assert_eq!(serde_json::to_value(SwapSource::ZeroEx).unwrap(), json!("0x"));
+        assert_eq!(serde_json::to_value(SwapSource::OneInch).unwrap(), json!("1inch"));
+
+        // Uniswap quotes 1 WETH at 2991 USDC on the mock chain; 0x beats it and 1inch is left out
+        let config = offline_config();
+        let client = EthereumClient::with_config(&config, PLACEHOLDER_PRIVATE_KEY.to_string())
+            .await
+            .unwrap()
+            .with_rpc_transport(Arc::new(AggregatorTransport))
+            .with_aggregators(vec![(SwapSource::ZeroEx, "key".to_string()), (SwapSource::OneInch, "key".to_string())]);
+        let weth = ParsedAddress::parse("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2").unwrap();
+        let usdc = ParsedAddress::parse("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48").unwrap();
+        let simulation = client.simulate_swap(weth, usdc, dec!(1), dec!(0.5), None, None, (false, false), 1200).await.unwrap();
+
+        assert_eq!(simulation.source, SwapSource::ZeroEx);
+        assert_eq!(simulation.amount_out, dec!(2995));
+        assert_eq!(simulation.gas_estimate, 180_000);
+        assert_eq!(simulation.route[0].source, SwapSource::ZeroEx);
+        let sources: Vec<SwapSource> = simulation.quotes.iter().map(|quote| quote.source).collect();
+        assert_eq!(sources, vec![SwapSource::UniswapV3, SwapSource::UniswapV2, SwapSource::ZeroEx]);
+        let best: Vec<bool> = simulation.quotes.iter().map(|quote| quote.best).collect();
+        assert_eq!(best, vec![false, false, true]);
+    }
+
+    #[tokio::test]
+    async fn test_get_balance_changes() {
+        use crate::ethereum::{EthereumClient, PLACEHOLDER_PRIVATE_KEY};
+        use crate::error::InputValidator;
+        use crate::tools::ToolHandler;
+        use crate::types::ToolCall;
+
+        let wallet = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+        let usdc = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+        assert!(InputValidator::validate_tool_parameters("get_balance_changes", &json!({ "address": wallet })).is_err());
+        assert!(InputValidator::validate_tool_parameters("get_balance_changes", &json!({ "address": wallet, "from_block": "100", "to_block": "99" })).is_err());
+        assert!(InputValidator::validate_tool_parameters("get_balance_changes", &json!({ "address": wallet, "from_block": "0x63", "to_block": "latest" })).is_ok());
+
+        // The mock chain's balances never move, and latest resolves to its head
+        let config = offline_config();
+        let client = EthereumClient::with_config(&config, PLACEHOLDER_PRIVATE_KEY.to_string()).await.unwrap();
+        let handler = ToolHandler::with_config(client, &config).unwrap();
+        let result = handler.handle_tool_call(ToolCall {
+            name: "get_balance_changes".to_string(),
+            arguments: json!({ "address": wallet, "from_block": "18999000", "token_addresses": [usdc] }),
+        }).await.unwrap();
+        let changes = result.content;
+        assert_eq!(changes["from_block"], json!(18_999_000));
+        assert_eq!(changes["to_block"], json!(crate::mock::MOCK_BLOCK_NUMBER));
+        assert_eq!(changes["native_change"], json!("0"));
+        assert_eq!(changes["token_changes"][usdc]["symbol"], json!("USDC"));
+        assert_eq!(changes["token_changes"][usdc]["balance_after"], json!("1000"));
+        assert_eq!(changes["token_changes"][usdc]["change"], json!("0"));
+
+        // A later from_block than the head is rejected once tags are resolved
+        let late = handler.handle_tool_call(ToolCall {
+            name: "get_balance_changes".to_string(),
+            arguments: json!({ "address": wallet, "from_block": "19000001" }),
+        }).await;
+        assert!(late.is_err());
+    }
+
+    #[test]
+    fn test_decimal_json_serialization() {
+        use crate::config::{Config, FileConfig};
+        use crate::types::TokenBalanceChange;
+        use rust_decimal_macros::dec;
+
+        // Strings by default, keeping every digit
+        let change = TokenBalanceChange {
+            symbol: "USDC".to_string(),
+            decimals: 6,
+            balance_before: dec!(1234567890.123456),
+            balance_after: dec!(0.1),
+            change: dec!(-1234567890.023456),
+        };
+        let value = serde_json::to_value(&change).unwrap();
+        assert_eq!(value["balance_before"], json!("1234567890.123456"));
+        assert_eq!(value["change"], json!("-1234567890.023456"));
+
+        // numeric_json writes numbers
+        let number = crate::decimal_json::serialize_as(&dec!(0.1), true, serde_json::value::Serializer).unwrap();
+        assert_eq!(number, json!(0.1));
+        let string = crate::decimal_json::serialize_as(&dec!(0.1), false, serde_json::value::Serializer).unwrap();
+        assert_eq!(string, json!("0.1"));
+
+        // Both forms read back
+        let parsed: TokenBalanceChange = serde_json::from_value(json!({
+            "symbol": "USDC", "decimals": 6, "balance_before": 1.5, "balance_after": "2.5", "change": "1"
+        })).unwrap();
+        assert_eq!(parsed.balance_before, dec!(1.5));
+        assert_eq!(parsed.balance_after, dec!(2.5));
+
+        assert!(Config::resolve(FileConfig { numeric_json: Some(true), ..file_config() }).unwrap().numeric_json);
+    }
+
+    #[tokio::test]
+    async fn test_detect_proxy() {
+        use crate::address::ParsedAddress;
+        use crate::ethereum::{EthereumClient, EIP1967_ADMIN_SLOT, EIP1967_BEACON_SLOT, EIP1967_IMPLEMENTATION_SLOT, PLACEHOLDER_PRIVATE_KEY};
+        use ethers::types::{H256, U256};
+        use ethers::utils::keccak256;
+
+        // Each slot is keccak256(label) - 1
+        let slot = |label: &str| {
+            let mut word = [0u8; 32];
+            (U256::from_big_endian(&keccak256(label)) - 1).to_big_endian(&mut word);
+            H256::from(word)
+        };
+        assert_eq!(EIP1967_IMPLEMENTATION_SLOT, slot("eip1967.proxy.implementation"));
+        assert_eq!(EIP1967_BEACON_SLOT, slot("eip1967.proxy.beacon"));
+        assert_eq!(EIP1967_ADMIN_SLOT, slot("eip1967.proxy.admin"));
+
+        let implementation: ethers::types::Address = "0x43506849d7c04f9138d1a2050bbf3a0c054402dd".parse().unwrap();
+        assert_eq!(EthereumClient::slot_address(H256::from(implementation)), Some(implementation));
+        assert_eq!(EthereumClient::slot_address(H256::zero()), None);
+        assert_eq!(EthereumClient::slot_address(H256::repeat_byte(0xff)), None);
+
+        // Mock chain storage is empty, so its tokens are plain contracts
+        let config = offline_config();
+        let client = EthereumClient::with_config(&config, PLACEHOLDER_PRIVATE_KEY.to_string()).await.unwrap();
+        let usdc = ParsedAddress::parse("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48").unwrap();
+        let info = client.detect_proxy(usdc).await.unwrap();
+        assert!(info.is_contract);
+        assert!(!info.is_proxy);
+        assert_eq!(serde_json::to_value(&info).unwrap()["implementation"], json!(null));
+    }
+
+    #[tokio::test]
+    async fn test_network_override_validation() {
+        use crate::error::MCPError;
+        use crate::ethereum::{EthereumClient, PLACEHOLDER_PRIVATE_KEY};
+        use crate::network::Network;
+        use crate::tools::ToolHandler;
+        use crate::types::ToolCall;
+
+        let client = EthereumClient::with_network("http://127.0.0.1:1".to_string(), PLACEHOLDER_PRIVATE_KEY.to_string(), Network::Mainnet)
+            .await
+            .unwrap();
+        let handler = ToolHandler::with_config(client, &offline_config()).unwrap();
+        assert_eq!(handler.configured_networks(), vec![Network::Mainnet]);
+        let call = |arguments: serde_json::Value| ToolCall { name: "list_networks".to_string(), arguments };
+
+        // The configured network, under any of its names, is served
+        assert!(handler.handle_tool_call(call(json!({ "network": "ethereum" }))).await.is_ok());
+        assert!(handler.handle_tool_call(call(json!({}))).await.is_ok());
+
+        match handler.handle_tool_call(call(json!({ "network": "polygon" }))).await {
+            Err(MCPError::ConfigurationError(msg)) => assert_eq!(msg, "network polygon not configured (available: mainnet)"),
+            other => panic!("expected ConfigurationError, got {:?}", other),
+        }
+        assert!(matches!(handler.handle_tool_call(call(json!({ "network": "solana" }))).await, Err(MCPError::ConfigurationError(_))));
+        assert!(matches!(handler.handle_tool_call(call(json!({ "network": 137 }))).await, Err(MCPError::InvalidParameterType(_))));
+    }
+
+    #[tokio::test]
+    async fn test_batch_lookup_ens() {
+        use crate::ethereum::{EthereumClient, PLACEHOLDER_PRIVATE_KEY};
+        use crate::error::InputValidator;
+
+        assert!(InputValidator::validate_tool_parameters("batch_lookup_ens", &json!({ "addresses": [] })).is_err());
+        assert!(InputValidator::validate_tool_parameters("batch_lookup_ens", &json!({ "addresses": [1] })).is_err());
+        assert!(InputValidator::validate_tool_parameters("batch_lookup_ens", &json!({ "addresses": ["0x123"] })).is_ok());
+
+        // The mock chain has no ENS records, so valid addresses map to null
+        let config = offline_config();
+        let client = EthereumClient::with_config(&config, PLACEHOLDER_PRIVATE_KEY.to_string()).await.unwrap();
+        let wallet = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".to_string();
+        let batch = client.batch_lookup_ens(&[wallet.clone(), "0x123".to_string()]).await;
+        assert_eq!(batch.names.get(&wallet), Some(&None));
+        assert!(batch.errors.contains_key("0x123"));
+        assert_eq!(serde_json::to_value(&batch).unwrap()["names"][&wallet], json!(null));
+    }
+
+    #[tokio::test]
+    async fn test_gas_price_warning_and_wait() {
+        use crate::ethereum::{EthereumClient, PLACEHOLDER_PRIVATE_KEY};
+        use crate::error::InputValidator;
+        use crate::tools::ToolHandler;
+        use crate::types::ToolCall;
+        use rust_decimal_macros::dec;
+
+        assert!(InputValidator::validate_tool_parameters("wait_for_gas", &json!({})).is_err());
+        assert!(InputValidator::validate_tool_parameters("wait_for_gas", &json!({ "target_gwei": "0" })).is_err());
+        assert!(InputValidator::validate_tool_parameters("wait_for_gas", &json!({ "target_gwei": "30", "poll_interval_secs": 0 })).is_err());
+
+        // The mock chain's gas price and base fee are 20 gwei, below the default 100 gwei threshold
+        let config = offline_config();
+        let client = EthereumClient::with_config(&config, PLACEHOLDER_PRIVATE_KEY.to_string()).await.unwrap();
+        let gas = client.get_gas_price().await.unwrap();
+        assert_eq!(gas.gas_price_gwei, dec!(20));
+        assert_eq!(gas.base_fee_gwei, Some(dec!(20)));
+        assert_eq!(gas.warn_threshold_gwei, dec!(100));
+        assert!(!gas.is_high);
+
+        let handler = ToolHandler::with_config(client, &config).unwrap();
+        let wait = |arguments: serde_json::Value| handler.handle_tool_call(ToolCall { name: "wait_for_gas".to_string(), arguments });
+
+        let reached = wait(json!({ "target_gwei": "25" })).await.unwrap().content;
+        assert_eq!(reached["reached"], json!(true));
+        assert_eq!(reached["checks"], json!(1));
+
+        let timed_out = wait(json!({ "target_gwei": "10", "timeout_secs": 1, "poll_interval_secs": 1 })).await.unwrap().content;
+        assert_eq!(timed_out["reached"], json!(false));
+        assert_eq!(timed_out["base_fee_gwei"], json!("20"));
+
+        // The wait must end before TOOL_TIMEOUT_SECS cuts the call off
+        assert!(wait(json!({ "target_gwei": "10", "timeout_secs": 3600 })).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_wallet_key_source() {
+        use crate::ethereum::{resolve_private_key, EthereumClient, PLACEHOLDER_PRIVATE_KEY};
+        use crate::types::{KeySource, WalletMode};
+        use ethers::signers::{LocalWallet, Signer};
+
+        let anvil_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+        let (key, source) = resolve_private_key(Some(anvil_key.to_string()), None, None).unwrap();
+        assert_eq!((key.as_str(), source), (anvil_key, KeySource::Env));
+
+        let (key, source) = resolve_private_key(Some(" ".to_string()), None, None).unwrap();
+        assert_eq!((key.as_str(), source), (PLACEHOLDER_PRIVATE_KEY, KeySource::None));
+        let (_, source) = resolve_private_key(Some(PLACEHOLDER_PRIVATE_KEY.to_string()), None, None).unwrap();
+        assert_eq!(source, KeySource::None);
+
+        let dir = env::temp_dir().join(format!("web3-wallet-keystore-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (wallet, name) = LocalWallet::new_keystore(&dir, &mut ethers::core::rand::thread_rng(), "hunter2", None).unwrap();
+        let path = dir.join(name).to_string_lossy().into_owned();
+
+        assert!(resolve_private_key(None, Some(path.clone()), None).is_err());
+        assert!(resolve_private_key(None, Some(path.clone()), Some("wrong".to_string())).is_err());
+        let (key, source) = resolve_private_key(None, Some(path), Some("hunter2".to_string())).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(source, KeySource::Keystore);
+        assert_eq!(key.parse::<LocalWallet>().unwrap().address(), wallet.address());
+
+        let config = offline_config();
+        let read_only = EthereumClient::with_config(&config, PLACEHOLDER_PRIVATE_KEY.to_string()).await.unwrap();
+        assert_eq!(read_only.wallet_mode(), WalletMode::ReadOnly);
+        let signing = EthereumClient::with_config(&config, key).await.unwrap();
+        assert_eq!(signing.wallet_mode(), WalletMode::Signing);
+        assert_eq!(serde_json::to_value(WalletMode::ReadOnly).unwrap(), json!("read_only"));
+    }
+
+    #[tokio::test]
+    async fn test_get_erc1155_balance() {
+        use crate::error::InputValidator;
+        use crate::ethereum::{EthereumClient, PLACEHOLDER_PRIVATE_KEY};
+        use crate::tools::ToolHandler;
+        use crate::types::ToolCall;
+
+        let usdc = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+        let owner = "0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
+        let valid = json!({ "contract_address": usdc, "owner": owner, "token_id": "0x2a" });
+        assert!(InputValidator::validate_tool_parameters("get_erc1155_balance", &valid).is_ok());
+        assert!(InputValidator::validate_tool_parameters("get_erc1155_balance", &json!({ "contract_address": usdc, "owner": owner })).is_err());
+        assert!(InputValidator::validate_tool_parameters("get_erc1155_balance", &json!({ "contract_address": usdc, "owner": "0x123", "token_id": "1" })).is_err());
+        assert!(InputValidator::validate_tool_parameters("get_erc1155_balance", &json!({ "contract_address": usdc, "owner": owner, "token_id": 1 })).is_err());
+        assert!(InputValidator::validate_tool_parameters("get_erc1155_balance", &json!({ "contract_address": usdc, "owner": owner, "token_id": "-1" })).is_err());
+
+        let config = offline_config();
+        let client = EthereumClient::with_config(&config, PLACEHOLDER_PRIVATE_KEY.to_string()).await.unwrap();
+        let handler = ToolHandler::with_config(client, &config).unwrap();
+        let call = |arguments: serde_json::Value| handler.handle_tool_call(ToolCall { name: "get_erc1155_balance".to_string(), arguments });
+
+        let balance = call(valid).await.unwrap().content;
+        assert_eq!(balance["contract_address"], json!(usdc));
+        assert_eq!(balance["token_id"], json!("42"));
+        assert_eq!(balance["balance"], json!("5"));
+
+        // An address without code is not an ERC1155 contract
+        assert!(call(json!({ "contract_address": owner, "owner": owner, "token_id": "1" })).await.is_err());
+    }
+
+    #[test]
+    fn test_token_amounts_with_large_decimals() {
+        use crate::ethereum::{EthereumClient, TokenMetadata};
+        use crate::types::MetadataSource;
+        use ethers::types::{Address, U256};
+        use rust_decimal_macros::dec;
+
+        assert_eq!(EthereumClient::scale_token_amount(dec!(15), 0), dec!(15));
+        assert_eq!(EthereumClient::scale_token_amount(dec!(15), 1), dec!(1.5));
+        assert_eq!(EthereumClient::scale_token_amount(dec!(15_000_000_000_000_000_000_000_000_000), 30), dec!(0.015));
+        assert_eq!(EthereumClient::scale_token_amount(rust_decimal::Decimal::MAX, 30).round_dp(3), dec!(0.079));
+        assert_eq!(EthereumClient::scale_token_amount(dec!(1), 255), rust_decimal::Decimal::ZERO);
+
+        assert_eq!(EthereumClient::checked_exp10(77), Some(U256::exp10(77)));
+        assert_eq!(EthereumClient::checked_exp10(78), None);
+        assert_eq!(EthereumClient::format_u256_units(U256::exp10(30) * 5 / 2, 30, 6), "2.5");
+        assert_eq!(EthereumClient::format_u256_units(U256::MAX, 100, 6), "0");
+        assert_eq!(EthereumClient::format_u256_units(U256::from(7u64), 200, 250).len(), 202);
+
+        // A hostile decimals() must not panic the balance path
+        for decimals in [30u8, 60, 255] {
+            let metadata = TokenMetadata {
+                name: "Many Decimals".to_string(),
+                symbol: "MANY".to_string(),
+                decimals,
+                source: MetadataSource::Onchain,
+                logo: None,
+            };
+            let balance = EthereumClient::build_token_balance(Address::repeat_byte(0x33), U256::exp10(30), metadata, 6);
+            assert_eq!(balance.balance_raw, U256::exp10(30).to_string());
+            let expected = if decimals == 30 { "1" } else { "0" };
+            assert_eq!(balance.balance_formatted, expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_swap_amounts_beyond_decimal_range() {
+        use crate::address::ParsedAddress;
+        use crate::error::MCPError;
+        use crate::ethereum::{EthereumClient, PLACEHOLDER_PRIVATE_KEY};
+        use ethers::types::U256;
+        use rust_decimal_macros::dec;
+
+        assert_eq!(EthereumClient::token_amount_to_raw(dec!(1.5), 40, "MANY").unwrap(), U256::exp10(39) * 15);
+        assert_eq!(EthereumClient::token_amount_to_raw(dec!(0), 255, "MANY").unwrap(), U256::zero());
+        assert!(matches!(EthereumClient::token_amount_to_raw(dec!(1), 78, "MANY"), Err(MCPError::InvalidAmount(_))));
+        assert!(matches!(EthereumClient::token_amount_to_raw(dec!(-1), 18, "DAI"), Err(MCPError::InvalidAmount(_))));
+
+        assert_eq!(EthereumClient::raw_to_decimal(U256::exp10(30) * 3 / 2, 18), Some(dec!(1_500_000_000_000)));
+        assert_eq!(EthereumClient::raw_to_decimal(U256::from(15u64), 30), Some(dec!(0)));
+        assert_eq!(EthereumClient::raw_to_decimal(U256::exp10(30) * 15, 30), Some(dec!(15)));
+        assert_eq!(EthereumClient::raw_to_decimal(U256::MAX, 0), None);
+
+        // 1e9 WBTC quotes 6e13 DAI, 6e31 raw units: beyond Decimal but an ordinary U256
+        let config = offline_config();
+        let client = EthereumClient::with_config(&config, PLACEHOLDER_PRIVATE_KEY.to_string()).await.unwrap();
+        let wbtc = ParsedAddress::parse("0x2260FAC5E5542a773Aa44fBCfeDf7C193bc2C599").unwrap();
+        let dai = ParsedAddress::parse("0x6B175474E89094C44Da98b954EedeAC495271d0F").unwrap();
+        let swap = client.simulate_swap(wbtc, dai, dec!(1_000_000_000), dec!(0.5), None, None, (false, false), 1200).await.unwrap();
+        assert_eq!(swap.amount_out.round(), dec!(60_000_000_000_000) * dec!(0.997));
+    }
+
+    /// Quotes every eth_call with `quote`; eth_estimateGas passes only for the
+    /// fee-on-transfer-supporting router swap when `fee_on_transfer`, and reverts otherwise
+    struct FeeOnTransferTransport {
+        quote: u128,
+        fee_on_transfer: bool,
+    }
+
+    #[async_trait::async_trait]
+    impl crate::transport::RpcTransport for FeeOnTransferTransport {
+        async fn eth_call(&self, _to: ethers::types::Address, _data: Vec<u8>, _block: Option<ethers::types::BlockId>) -> Result<Vec<u8>, crate::error::MCPError> {
+            Ok(ethers::abi::encode(&[ethers::abi::Token::Uint(self.quote.into())]))
+        }
+
+        async fn estimate_gas(&self, _from: ethers::types::Address, _to: ethers::types::Address, data: Vec<u8>) -> Result<u64, crate::error::MCPError> {
+            let supporting = ethers::utils::keccak256("swapExactTokensForTokensSupportingFeeOnTransferTokens(uint256,uint256,address[],address,uint256)");
+            if self.fee_on_transfer && data.starts_with(&supporting[..4]) {
+                Ok(160_000)
+            } else {
+                Err(crate::error::MCPError::GasEstimationFailed("execution reverted: UniswapV2: K".to_string()))
+            }
+        }
+
+        async fn fetch_prices(&self, _network_slug: &str, _token_addresses: &[&str]) -> Result<std::collections::HashMap<String, rust_decimal::Decimal>, crate::error::MCPError> {
+            Ok(std::collections::HashMap::new())
+        }
+
+        async fn aggregator_quote(&self, _source: crate::types::SwapSource, _api_key: &str, _chain_id: u64, _sell_token: ethers::types::Address, _buy_token: ethers::types::Address, _amount: ethers::types::U256) -> Result<crate::transport::AggregatorQuote, crate::error::MCPError> {
+            Err(crate::error::MCPError::NetworkError("no aggregator".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_swap_fee_on_transfer_warning() {
+        use crate::address::ParsedAddress;
+        use crate::ethereum::{EthereumClient, PLACEHOLDER_PRIVATE_KEY};
+        use rust_decimal_macros::dec;
+        use std::sync::Arc;
+
+        let config = offline_config();
+        let usdc = ParsedAddress::parse("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48").unwrap();
+        let dai = ParsedAddress::parse("0x6B175474E89094C44Da98b954EedeAC495271d0F").unwrap();
+        let simulate = |client: EthereumClient| async move {
+            client.simulate_swap(usdc, dai, dec!(100), dec!(0.5), None, None, (false, false), 1200).await.unwrap()
+        };
+
+        // The mock chain's standard router swap passes: no fee on transfer
+        let client = EthereumClient::with_config(&config, PLACEHOLDER_PRIVATE_KEY.to_string()).await.unwrap();
+        assert_eq!(simulate(client).await.fee_on_transfer_warning, Some(false));
+
+        let quote = 100_000_000_000_000_000_000u128;
+        let client = EthereumClient::with_config(&config, PLACEHOLDER_PRIVATE_KEY.to_string()).await.unwrap()
+            .with_rpc_transport(Arc::new(FeeOnTransferTransport { quote, fee_on_transfer: true }));
+        assert_eq!(simulate(client).await.fee_on_transfer_warning, Some(true));
+
+        // Both swaps revert, e.g. without an allowance: unknown rather than a false all-clear
+        let client = EthereumClient::with_config(&config, PLACEHOLDER_PRIVATE_KEY.to_string()).await.unwrap()
+            .with_rpc_transport(Arc::new(FeeOnTransferTransport { quote, fee_on_transfer: false }));
+        let swap = simulate(client).await;
+        assert_eq!(swap.fee_on_transfer_warning, None);
+        assert_eq!(serde_json::to_value(&swap).unwrap()["fee_on_transfer_warning"], json!(null));
+    }
+
+    /// WebSocket JSON-RPC endpoint answering every request with block 0x10. The first
+    /// connection is closed after `answers_before_close` responses; returns the URL and a
+    /// counter of accepted connections. Dropping the returned sender stops accepting.
+    async fn spawn_ws_endpoint(answers_before_close: usize) -> (String, std::sync::Arc<std::sync::atomic::AtomicUsize>, tokio::sync::oneshot::Sender<()>) {
+        use futures::{SinkExt, StreamExt};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tokio_tungstenite::tungstenite::Message;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let connections = std::sync::Arc::new(AtomicUsize::new(0));
+        let (stop, mut stopped) = tokio::sync::oneshot::channel::<()>();
+        let accepted = connections.clone();
+        tokio::spawn(async move {
+            loop {
+                let socket = tokio::select! {
+                    accepted = listener.accept() => match accepted {
+                        Ok((socket, _)) => socket,
+                        Err(_) => break,
+                    },
+                    _ = &mut stopped => break,
+                };
+                let index = accepted.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(async move {
+                    let mut ws = tokio_tungstenite::accept_async(socket).await.unwrap();
+                    let mut answered = 0;
+                    while let Some(Ok(Message::Text(text))) = ws.next().await {
+                        let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+                        let response = json!({ "jsonrpc": "2.0", "id": request["id"], "result": "0x10" });
+                        ws.send(Message::Text(response.to_string())).await.unwrap();
+                        answered += 1;
+                        if index == 0 && answered == answers_before_close {
+                            let _ = ws.close(None).await;
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        (url, connections, stop)
+    }
+
+    #[tokio::test]
+    async fn test_ws_provider_reconnects() {
+        use crate::error::InputValidator;
+        use crate::failover::{FailoverTransport, WsReconnect};
+        use crate::ws::ReconnectingWs;
+        use ethers::providers::JsonRpcClient;
+        use ethers::types::U64;
+        use std::sync::atomic::Ordering;
+        use std::time::Duration;
+
+        assert!(InputValidator::validate_rpc_url("wss://eth-mainnet.example/ws").is_ok());
+        assert!(InputValidator::validate_rpc_url("ftp://eth-mainnet.example").is_err());
+        assert!(FailoverTransport::new(&["ws://127.0.0.1:1".to_string()]).is_err());
+
+        // The first connection drops after one answer; the next request reconnects and succeeds
+        let (url, connections, _stop) = spawn_ws_endpoint(1).await;
+        let ws = ReconnectingWs::connect(&url, 3, Duration::from_millis(10)).await.unwrap();
+        let block: U64 = ws.request("eth_blockNumber", ()).await.unwrap();
+        assert_eq!(block, U64::from(16));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let block: U64 = ws.request("eth_blockNumber", ()).await.unwrap();
+        assert_eq!(block, U64::from(16));
+        assert_eq!(ws.reconnect_count().await, 1);
+        assert_eq!(connections.load(Ordering::SeqCst), 2);
+
+        // Once the endpoint is gone for good, the error surfaces after the reconnect attempts
+        let (url, connections, stop) = spawn_ws_endpoint(1).await;
+        let reconnect = WsReconnect { max_reconnects: 2, base_delay: Duration::from_millis(10) };
+        let transport = FailoverTransport::connect(&[url], reqwest::Client::new(), reconnect).await.unwrap();
+        let _: U64 = transport.request("eth_blockNumber", ()).await.unwrap();
+        drop(stop);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let error: crate::error::MCPError = transport.request::<_, U64>("eth_blockNumber", ()).await.unwrap_err().into();
+        assert!(matches!(error, crate::error::MCPError::NetworkError(_)), "{:?}", error);
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_stdio_line_limit() {
+        use crate::ethereum::PLACEHOLDER_PRIVATE_KEY;
+
+        let config = offline_config();
+        let server = MCPServer::from_config(&config, PLACEHOLDER_PRIVATE_KEY.to_string()).await.unwrap();
+
+        // An oversized line is rejected without disturbing the requests around it
+        let oversized = format!(r#"{{"jsonrpc":"2.0","id":2,"method":"ping","params":"{}"}}"#, "x".repeat(512));
+        let input = format!(
+            "{}\n{}\n\n{}\nnot json\n",
+            r#"{"jsonrpc":"2.0","id":1,"method":"tools/list","params":{}}"#,
+            oversized,
+            r#"[{"jsonrpc":"2.0","id":3,"method":"tools/list","params":{}}]"#,
+        );
+        let mut output = Vec::new();
+        server.serve_lines(input.as_bytes(), &mut output, 256).await.unwrap();
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(output).unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0]["id"], json!(1));
+        assert!(lines[0]["result"]["tools"].is_array());
+        assert_eq!(lines[1]["id"], json!(null));
+        assert_eq!(lines[1]["error"]["code"], json!(-32600));
+        assert!(lines[1]["error"]["message"].as_str().unwrap().contains("256 bytes"));
+        assert_eq!(lines[2][0]["id"], json!(3));
+        assert_eq!(lines[3]["error"]["code"], json!(-32600));
+    }
+
+    #[tokio::test]
+    async fn test_http_body_limit() {
+        use crate::request_limit::with_body_limit;
+        use axum::{routing::post, Router};
+
+        let app = with_body_limit(Router::new().route("/mcp", post(|body: String| async move { body })), 64);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/mcp", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let client = reqwest::Client::new();
+        let response = client.post(&url).body("x".repeat(64)).send().await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+        let response = client.post(&url).body("x".repeat(65)).send().await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::PAYLOAD_TOO_LARGE);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["jsonrpc"], json!("2.0"));
+        assert_eq!(body["id"], json!(null));
+        assert_eq!(body["error"]["code"], json!(-32600));
+        assert!(body["error"]["message"].as_str().unwrap().contains("64 bytes"));
+    }
+
+    #[tokio::test]
+    async fn test_batch_size_limit() {
+        use crate::error::MCPError;
+        use crate::ethereum::PLACEHOLDER_PRIVATE_KEY;
+        use crate::mcp_server::MAX_BATCH_SIZE;
+
+        let config = offline_config();
+        let server = MCPServer::from_config(&config, PLACEHOLDER_PRIVATE_KEY.to_string()).await.unwrap();
+
+        let batch = |size: usize| (0..size)
+            .map(|id| json!({ "jsonrpc": "2.0", "id": id, "method": "tools/list", "params": {} }))
+            .collect::<Vec<_>>();
+
+        let responses = server.handle_batch(batch(MAX_BATCH_SIZE)).await.unwrap();
+        assert_eq!(responses.len(), MAX_BATCH_SIZE);
+
+        let error = server.handle_batch(batch(MAX_BATCH_SIZE + 1)).await.unwrap_err();
+        assert!(matches!(error, MCPError::InvalidJsonRpcRequest(_)), "{:?}", error);
+        assert_eq!(error.error_code(), -32600);
+    }
+
+    #[tokio::test]
+    async fn test_error_response_echoes_client_id() {
+        use crate::ethereum::PLACEHOLDER_PRIVATE_KEY;
+
+        let config = offline_config();
+        let server = MCPServer::from_config(&config, PLACEHOLDER_PRIVATE_KEY.to_string()).await.unwrap();
+
+        for id in [json!(7), json!("req-7")] {
+            let read = MCPRequest {
+                jsonrpc: "2.0".to_string(),
+                id: id.clone(),
+                method: "resources/read".to_string(),
+                params: json!({ "uri": "wallet://unknown" }),
+                request_context: None,
+            };
+            let response = server.handle_request(read).await.unwrap();
+            assert_eq!(response.id, id);
+            let error = response.error.unwrap();
+            assert_eq!(error.code, -32602);
+
+            // The internal request id is still reported, but only in the error data
+            let internal_id = error.data.unwrap()["request_id"].as_str().unwrap().to_string();
+            assert_ne!(json!(internal_id), id);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tool_call_retries_transient_failures() {
+        use crate::ethereum::{EthereumClient, PLACEHOLDER_PRIVATE_KEY};
+        use crate::tools::ToolHandler;
+        use std::sync::Arc;
+
+        let config = offline_config();
+        let transport = Arc::new(MockTransport::new(rust_decimal::Decimal::ONE).failing_prices(1));
+        let client = EthereumClient::with_config(&config, PLACEHOLDER_PRIVATE_KEY.to_string()).await.unwrap()
+            .with_rpc_transport(transport.clone());
+        let server = MCPServer::with_tool_handler(ToolHandler::with_config(client, &config).unwrap());
+
+        // The first price fetch fails with a network error; the retried call succeeds
+        let request = MCPRequest {
+            jsonrpc: "2.0".to_string(),
+            id: json!(1),
+            method: "tools/call".to_string(),
+            params: json!({
+                "name": "get_token_price",
+                "arguments": { "token_address": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", "source": "alchemy" }
+            }),
+            request_context: None,
+        };
+        let response = server.handle_request(request).await.unwrap();
+        assert!(response.error.is_none(), "{:?}", response.error);
+        assert_eq!(response.result.unwrap()["structuredContent"]["price_usd"], json!("1"));
+        assert_eq!(transport.price_calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_native_usd_value_uses_network_wrapped_token() {
+        use crate::config::Config;
+        use crate::ethereum::{EthereumClient, PLACEHOLDER_PRIVATE_KEY};
+        use crate::network::Network;
+        use crate::tools::ToolHandler;
+        use crate::types::ToolCall;
+        use std::sync::Arc;
+
+        // Polygon's native balance is priced via WMATIC, not mainnet WETH
+        let config = Config { network: Network::Polygon, ..offline_config() };
+        let transport = Arc::new(MockTransport::new(rust_decimal_macros::dec!(2)).pricing_only(Network::Polygon.weth_address()));
+        let client = EthereumClient::with_config(&config, PLACEHOLDER_PRIVATE_KEY.to_string()).await.unwrap()
+            .with_rpc_transport(transport);
+        let handler = ToolHandler::with_config(client, &config).unwrap();
+
+        let result = handler.handle_tool_call(ToolCall {
+            name: "get_balance".to_string(),
+            arguments: json!({
+                "address": "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045",
+                "token_address": "ETH",
+                "include_usd_value": true
+            }),
+        }).await.unwrap();
+        assert_eq!(result.content["native_symbol"], json!("MATIC"));
+        assert_eq!(result.content["eth_usd_value"], json!("3.00"));
+    }
+
+    #[tokio::test]
+    async fn test_onchain_price_uses_network_wrapped_token() {
+        use crate::address::ParsedAddress;
+        use crate::config::Config;
+        use crate::ethereum::{EthereumClient, PLACEHOLDER_PRIVATE_KEY};
+        use crate::network::Network;
+        use rust_decimal_macros::dec;
+        use std::sync::Arc;
+
+        // On Polygon the wrapped native token is WMATIC: it needs no pool quote, and the
+        // USD conversion uses WMATIC's price rather than mainnet WETH's
+        let config = Config { network: Network::Polygon, ..offline_config() };
+        let transport = Arc::new(MockTransport::new(rust_decimal_macros::dec!(2)).pricing_only(Network::Polygon.weth_address()));
+        let client = EthereumClient::with_config(&config, PLACEHOLDER_PRIVATE_KEY.to_string()).await.unwrap()
+            .with_rpc_transport(transport);
+
+        let wmatic = ParsedAddress::parse(Network::Polygon.weth_address()).unwrap();
+        let price = client.get_onchain_price(wmatic).await.unwrap();
+        assert_eq!(price.fee_tier, None);
+        assert_eq!(price.price_weth, dec!(1));
+        assert_eq!(price.eth_price_usd, Some(dec!(2)));
+        assert_eq!(price.price_usd, Some(dec!(2)));
+    }
+
+    #[tokio::test]
+    async fn test_prefer_known_list() {
+        use crate::config::{Config, FileConfig};
+        use crate::ethereum::{EthereumClient, PLACEHOLDER_PRIVATE_KEY};
+        use crate::tools::ToolHandler;
+        use crate::types::ToolCall;
+
+        assert!(Config::resolve(FileConfig { prefer_known_list: Some(true), ..file_config() }).unwrap().prefer_known_list);
+        assert!(!Config::resolve(FileConfig { prefer_known_list: Some(false), ..file_config() }).unwrap().prefer_known_list);
+
+        // USDC balance: returns the RPC calls made and the metadata source used
+        let usdc_balance = |prefer_known_list: bool| async move {
+            let config = Config { prefer_known_list, ..offline_config() };
+            let client = EthereumClient::with_config(&config, PLACEHOLDER_PRIVATE_KEY.to_string()).await.unwrap();
+            let handler = ToolHandler::with_config(client, &config).unwrap();
+            let calls_before = handler.ethereum_client().rpc_call_count();
+            let result = handler.handle_tool_call(ToolCall {
+                name: "get_balance".to_string(),
+                arguments: json!({
+                    "address": "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045",
+                    "token_address": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"
+                }),
+            }).await.unwrap();
+            let token = result.content["token_balances"].as_object().unwrap().values().next().unwrap().clone();
+            (handler.ethereum_client().rpc_call_count() - calls_before, token["metadata_source"].clone())
+        };
+
+        // Known list first: no name/symbol/decimals calls
+        let (known_calls, known_source) = usdc_balance(true).await;
+        assert_eq!(known_source, json!("known_list"));
+
+        // Disabled: metadata is read on-chain first, as before the flag existed
+        let (onchain_calls, onchain_source) = usdc_balance(false).await;
+        assert_eq!(onchain_source, json!("onchain"));
+        assert_eq!(onchain_calls, known_calls + 3);
+    }
+
+    #[tokio::test]
+    async fn test_tool_call_timeout() {
+        use crate::error::MCPError;
+        use crate::ethereum::{EthereumClient, PLACEHOLDER_PRIVATE_KEY};
+        use crate::tools::ToolHandler;
+        use crate::types::ToolCall;
+        use std::sync::Arc;
+        use std::time::{Duration, Instant};
+
+        let config = offline_config();
+        let client = EthereumClient::with_config(&config, PLACEHOLDER_PRIVATE_KEY.to_string()).await.unwrap()
+            .with_rpc_transport(Arc::new(MockTransport::new(rust_decimal::Decimal::ONE).with_price_delay(Duration::from_secs(30))));
+        let handler = ToolHandler::with_config(client, &config).unwrap()
+            .with_tool_timeout(Duration::from_secs(1));
+
+        // The price lookup would take 30s; the call is cut off after the 1s limit
+        let started = Instant::now();
+        let result = handler.handle_tool_call(ToolCall {
+            name: "get_token_price".to_string(),
+            arguments: json!({ "token_address": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", "source": "alchemy" }),
+        }).await;
+        assert!(started.elapsed() < Duration::from_secs(5));
+        match result {
+            Err(MCPError::Timeout(message)) => assert_eq!(message, "get_token_price did not complete within 1s"),
+            other => panic!("expected Timeout, got {:?}", other),
+        }
+
+        // Fast tools are unaffected by the same limit
+        let result = handler.handle_tool_call(ToolCall {
+            name: "get_balance".to_string(),
+            arguments: json!({ "address": "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045", "token_address": "ETH" }),
+        }).await;
+        assert!(result.is_ok(), "{:?}", result.err());
+    }
+
+    #[tokio::test]
+    async fn test_slow_receipt_returns_pending_before_tool_timeout() {
+        use crate::config::{Config, FileConfig};
+        use crate::ethereum::EthereumClient;
+        use crate::tools::ToolHandler;
+        use crate::types::ToolCall;
+        use std::time::{Duration, Instant};
+
+        // The transaction is accepted but never mined
+        let rpc_url = spawn_mock_chain(|method, _| (method == "eth_getTransactionReceipt").then_some(Ok(json!(null)))).await;
+        let config = Config::resolve(FileConfig { rpc_url: Some(rpc_url), ..FileConfig::default() }).unwrap();
+        let key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".to_string();
+        let client = EthereumClient::with_config(&config, key).await.unwrap();
+        let handler = ToolHandler::with_config(client, &config).unwrap()
+            .with_tool_timeout(Duration::from_secs(3));
+
+        let started = Instant::now();
+        let result = handler.handle_tool_call(ToolCall {
+            name: "send_transaction".to_string(),
+            arguments: json!({
+                "to": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+                "amount": "0.1",
+                "idempotency_key": "slow-receipt"
+            }),
+        }).await.unwrap();
+        assert!(started.elapsed() < Duration::from_secs(3));
+        assert_eq!(result.content["status"], json!("pending"));
+        let tx_hash = result.content["tx_hash"].as_str().unwrap().to_string();
+        assert_eq!(tx_hash.len(), 66);
+
+        // The key now replays the pending result instead of broadcasting again
+        let replay = handler.handle_tool_call(ToolCall {
+            name: "send_transaction".to_string(),
+            arguments: json!({
+                "to": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+                "amount": "0.1",
+                "idempotency_key": "slow-receipt"
+            }),
+        }).await.unwrap();
+        assert_eq!(replay.content["tx_hash"], json!(tx_hash));
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_kept_once_broadcast() {
+        use crate::config::{Config, FileConfig};
+        use crate::error::MCPError;
+        use crate::ethereum::EthereumClient;
+        use crate::tools::ToolHandler;
+        use crate::types::ToolCall;
+        use std::time::Duration;
+
+        let handler = |rpc_url: String| async move {
+            let config = Config::resolve(FileConfig { rpc_url: Some(rpc_url), ..FileConfig::default() }).unwrap();
+            let key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".to_string();
+            let client = EthereumClient::with_config(&config, key).await.unwrap()
+                .with_poll_interval(Duration::from_millis(50));
+            ToolHandler::with_config(client, &config).unwrap().with_tool_timeout(Duration::from_secs(5))
+        };
+        let send = |idempotency_key: &str| ToolCall {
+            name: "send_transaction".to_string(),
+            arguments: json!({
+                "to": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+                "amount": "0.1",
+                "idempotency_key": idempotency_key
+            }),
+        };
+
+        // The node accepted the transaction but the receipt lookup fails: the hash is
+        // returned as pending and the key replays it
+        let rpc_url = spawn_mock_chain(|method, _| {
+            (method == "eth_getTransactionReceipt").then(|| Err("receipt lookup failed".to_string()))
+        }).await;
+        let tools = handler(rpc_url).await;
+        let sent = tools.handle_tool_call(send("receipt-error")).await.unwrap();
+        assert_eq!(sent.content["status"], json!("pending"));
+        let replay = tools.handle_tool_call(send("receipt-error")).await.unwrap();
+        assert_eq!(replay.content["tx_hash"], sent.content["tx_hash"]);
+
+        // The node rejected the transaction: nothing was broadcast, so the key is released
+        let rpc_url = spawn_mock_chain(|method, _| {
+            (method == "eth_sendRawTransaction").then(|| Err("nonce too low".to_string()))
+        }).await;
+        let tools = handler(rpc_url).await;
+        for _ in 0..2 {
+            let error = tools.handle_tool_call(send("rejected")).await.unwrap_err();
+            assert!(matches!(error, MCPError::TransactionFailed(_)), "{:?}", error);
+        }
+
+        // An unreadable answer to the send may hide an accepted transaction: the key stays reserved
+        let rpc_url = spawn_mock_chain(|method, _| (method == "eth_sendRawTransaction").then(|| Ok(json!(42)))).await;
+        let tools = handler(rpc_url).await;
+        let error = tools.handle_tool_call(send("unknown")).await.unwrap_err();
+        assert!(matches!(error, MCPError::TransactionOutcomeUnknown(_)), "{:?}", error);
+        let error = tools.handle_tool_call(send("unknown")).await.unwrap_err();
+        assert!(matches!(error, MCPError::ValidationError(ref message) if message.contains("unknown outcome")), "{:?}", error);
+    }
+
+    #[tokio::test]
+    async fn test_transfer_token_rejects_guessed_decimals() {
+        use crate::config::{Config, FileConfig};
+        use crate::error::MCPError;
+        use crate::ethereum::EthereumClient;
+        use crate::tools::ToolHandler;
+        use crate::types::ToolCall;
+
+        // No token answers decimals(), so an unknown token falls back to a guessed 18
+        let rpc_url = spawn_mock_chain(|method, _| (method == "eth_call").then(|| Err("execution reverted".to_string()))).await;
+        let config = Config::resolve(FileConfig { rpc_url: Some(rpc_url), ..FileConfig::default() }).unwrap();
+        let key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".to_string();
+        let client = EthereumClient::with_config(&config, key).await.unwrap();
+        let tools = ToolHandler::with_config(client, &config).unwrap();
+
+        let error = tools.handle_tool_call(ToolCall {
+            name: "transfer_token".to_string(),
+            arguments: json!({
+                "token_address": "0x1111111111111111111111111111111111111111",
+                "to": "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+                "amount": "1"
+            }),
+        }).await.unwrap_err();
+        assert!(matches!(error, MCPError::InvalidTokenContract(ref message) if message.contains("decimals()")), "{:?}", error);
+    }
+
+    /// Answer eth_calls whose calldata starts with `selector` with `result`, leaving the rest to the mock chain
+    fn answer_selector(selector: &'static str, result: String) -> impl Fn(&str, &serde_json::Value) -> Option<Result<serde_json::Value, String>> + Send + Sync + 'static {
+        move |method, params| {
+            let call = &params[0];
+            let data = call["data"].as_str().or_else(|| call["input"].as_str()).unwrap_or_default();
+            (method == "eth_call" && data.starts_with(selector)).then(|| Ok(json!(result)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_oversized_balance_response_is_invalid_token_contract() {
+        use crate::address::ParsedAddress;
+        use crate::error::MCPError;
+        use crate::ethereum::{EthereumClient, PLACEHOLDER_PRIVATE_KEY};
+        use crate::network::Network;
+
+        // balanceOf answers 64 bytes, which U256::from would panic on
+        let rpc_url = spawn_mock_chain(answer_selector("0x70a08231", format!("0x{}", "11".repeat(64)))).await;
+        let client = EthereumClient::with_network(rpc_url, PLACEHOLDER_PRIVATE_KEY.to_string(), Network::Mainnet)
+            .await
+            .unwrap();
+
+        let wallet = ParsedAddress::parse("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap();
+        let token = ParsedAddress::parse("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48").unwrap();
+        let result = client.get_balance(wallet, &[token], None, false, 6).await;
+        assert!(matches!(result, Err(MCPError::InvalidTokenContract(_))), "{:?}", result);
+    }
+
+    #[tokio::test]
+    async fn test_malformed_decimals_is_invalid_token_contract() {
+        use crate::error::MCPError;
+        use crate::ethereum::{EthereumClient, PLACEHOLDER_PRIVATE_KEY};
+        use crate::network::Network;
+        use ethers::types::Address;
+
+        let oversized = format!("0x{}", "00".repeat(64));
+        let too_many_decimals = format!("0x{:064x}", 78);
+        let wraps_to_u8 = format!("0x{:064x}", 256);
+        for response in [oversized, too_many_decimals, wraps_to_u8] {
+            let rpc_url = spawn_mock_chain(answer_selector("0x313ce567", response.clone())).await;
+            let client = EthereumClient::with_network(rpc_url, PLACEHOLDER_PRIVATE_KEY.to_string(), Network::Mainnet)
+                .await
+                .unwrap();
+
+            let result = client.get_token_info(Address::repeat_byte(0x42), None).await;
+            assert!(matches!(result, Err(MCPError::InvalidTokenContract(_))), "{}: {:?}", response, result);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_confirmation_webhook_sent_after_pending_return() {
+        use crate::config::{Config, FileConfig};
+        use crate::ethereum::EthereumClient;
+        use crate::tools::ToolHandler;
+        use crate::types::ToolCall;
+        use axum::{routing::post, Json, Router};
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        // Receipts only appear once `mined` is set, after the tool call has returned
+        let mined = Arc::new(AtomicBool::new(false));
+        let chain_mined = mined.clone();
+        let rpc_url = spawn_mock_chain(move |method, _| {
+            (method == "eth_getTransactionReceipt" && !chain_mined.load(Ordering::SeqCst)).then_some(Ok(json!(null)))
+        }).await;
+
+        let (webhook_tx, mut webhook_rx) = tokio::sync::mpsc::unbounded_channel();
+        let hook = Router::new().route("/hook", post(move |Json(payload): Json<serde_json::Value>| {
+            let webhook_tx = webhook_tx.clone();
+            async move { webhook_tx.send(payload).unwrap(); }
+        }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let webhook_url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, hook).await.unwrap() });
+
+        let config = Config::resolve(FileConfig {
+            rpc_url: Some(rpc_url),
+            confirmation_webhook_url: Some(webhook_url),
+            ..FileConfig::default()
+        }).unwrap();
+        let key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".to_string();
+        let client = EthereumClient::with_config(&config, key).await.unwrap()
+            .with_poll_interval(Duration::from_millis(50));
+        let tools = ToolHandler::with_config(client, &config).unwrap().with_tool_timeout(Duration::from_secs(2));
+
+        let sent = tools.handle_tool_call(ToolCall {
+            name: "send_transaction".to_string(),
+            arguments: json!({ "to": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", "amount": "0.1" }),
+        }).await.unwrap();
+        assert_eq!(sent.content["status"], json!("pending"));
+        assert!(webhook_rx.try_recv().is_err());
+
+        mined.store(true, Ordering::SeqCst);
+        let payload = tokio::time::timeout(Duration::from_secs(5), webhook_rx.recv()).await.unwrap().unwrap();
+        assert_eq!(payload["tx_hash"], sent.content["tx_hash"]);
+        assert_eq!(payload["status"], json!("confirmed"));
+        assert_eq!(payload["block_number"], json!(19_000_000));
+    }
+
+    #[tokio::test]
+    async fn test_eth_price_uses_native_symbol() {
+        use crate::ethereum::{EthereumClient, PLACEHOLDER_PRIVATE_KEY};
+        use crate::network::Network;
+        use crate::types::PriceSource;
+        use rust_decimal_macros::dec;
+        use std::sync::Arc;
+
+        let client = EthereumClient::with_network("http://127.0.0.1:1".to_string(), PLACEHOLDER_PRIVATE_KEY.to_string(), Network::Polygon)
+            .await
+            .unwrap()
+            .with_rpc_transport(Arc::new(MockTransport::new(dec!(0.7))));
+
+        let price = client.get_eth_price().await.unwrap();
+        assert_eq!(price.symbol, "MATIC");
+        assert_eq!(price.price_usd, dec!(0.7));
+        assert_eq!(price.token_address, Network::Polygon.weth_address());
+        assert_eq!(price.source, PriceSource::Alchemy);
+        assert!(!price.degraded);
+    }
 }
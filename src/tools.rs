@@ -1,20 +1,142 @@
 use crate::error::MCPError;
-use crate::types::{ToolCall, ToolResult};
-use crate::ethereum::EthereumClient;
+use crate::types::{AddressValidation, Create2Address, FunctionSelector, Keccak256Hash, NetworkInfo, PriceSourceSelection, QuoteCurrency, ToolCall, ToolResult, TransactionFeeOptions};
+use crate::ethereum::{with_tool_deadline, EthereumClient, DEFAULT_DECIMALS_DISPLAY};
 use crate::error::InputValidator;
+use crate::address::ParsedAddress;
+use crate::network::Network;
+use crate::idempotency::{IdempotencyCache, Reservation};
 use serde_json::{Value, json};
+use ethers::types::{BlockId, BlockNumber, H256};
 use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::collections::HashSet;
 use std::str::FromStr;
-use tracing::{instrument, info, error, warn};
-use std::time::Instant;
+use tracing::{instrument, info, error, warn, debug};
+use std::time::{Duration, Instant};
+
+/// Default upper bound on a single tool call
+pub const DEFAULT_TOOL_TIMEOUT_SECS: u64 = 60;
+
+/// Default number of blocks returned by get_fee_history
+const DEFAULT_FEE_HISTORY_BLOCKS: u64 = 10;
+
+/// Default wait_for_gas timeout, in seconds; must stay below the tool timeout
+const DEFAULT_GAS_WAIT_SECS: u64 = 30;
+
+/// Default wait_for_gas polling interval, about one mainnet block
+const DEFAULT_GAS_POLL_INTERVAL_SECS: u64 = 12;
+
+/// Default swap deadline, in seconds from now
+const DEFAULT_SWAP_DEADLINE_SECS: u64 = 1200;
+
+/// Tools that sign with the server wallet; unavailable in read-only mode
+pub const SIGNING_TOOLS: [&str; 3] = ["send_transaction", "transfer_token", "replace_transaction"];
+
+/// Fee increase replace_transaction applies when `bump_percent` is omitted; also the
+/// minimum most nodes accept for a same-nonce replacement
+pub const DEFAULT_BUMP_PERCENT: Decimal = dec!(10);
+
+/// Tools whose responses can be trimmed with the `fields` parameter
+pub const FIELD_SELECTABLE_TOOLS: [&str; 4] = ["get_balance", "get_balances_for_addresses", "get_all_token_balances", "get_portfolio"];
+
+/// Slippage tolerance percentage applied when swap_tokens omits one
+pub const DEFAULT_SLIPPAGE: Decimal = dec!(0.5);
 
 pub struct ToolHandler {
     ethereum_client: EthereumClient,
+    /// Tools allowed by `ENABLED_TOOLS`; None enables every tool
+    enabled_tools: Option<HashSet<String>>,
+    /// Upper bound on a single tool call, from `TOOL_TIMEOUT_SECS`
+    tool_timeout: Duration,
+    /// Swap slippage default, from `DEFAULT_SLIPPAGE_PERCENT`
+    default_slippage: Decimal,
+    /// Highest swap slippage accepted, from `MAX_SLIPPAGE_PERCENT`
+    max_slippage: Decimal,
+    /// Signing tool results by `idempotency_key`, kept for `IDEMPOTENCY_TTL_SECS`
+    idempotency: IdempotencyCache,
 }
 
 impl ToolHandler {
-    pub fn new(ethereum_client: EthereumClient) -> Self {
-        Self { ethereum_client }
+    /// Create a handler with the tool allow-list, timeouts and swap defaults from `config`
+    pub fn with_config(ethereum_client: EthereumClient, config: &crate::config::Config) -> Result<Self, MCPError> {
+        let enabled_tools = config.enabled_tools.clone();
+        if let Some(tools) = &enabled_tools {
+            info!(enabled_tools = ?tools, "Restricting available tools");
+        }
+
+        if config.numeric_json {
+            info!("Decimal fields are serialized as JSON numbers (NUMERIC_JSON=true)");
+        }
+        crate::decimal_json::set_numeric_json(config.numeric_json);
+
+        Ok(Self {
+            ethereum_client,
+            enabled_tools,
+            tool_timeout: Duration::from_secs(config.tool_timeout_secs),
+            default_slippage: config.default_slippage_percent,
+            max_slippage: config.max_slippage_percent,
+            idempotency: IdempotencyCache::new(Duration::from_secs(config.idempotency_ttl_secs)),
+        })
+    }
+
+    /// Replace the per-call limit taken from `TOOL_TIMEOUT_SECS`
+    pub fn with_tool_timeout(mut self, tool_timeout: Duration) -> Self {
+        self.tool_timeout = tool_timeout;
+        self
+    }
+
+    /// Time limit for a single tool call
+    pub fn tool_timeout(&self) -> Duration {
+        self.tool_timeout
+    }
+
+    /// Client the tools run against
+    pub fn ethereum_client(&self) -> &EthereumClient {
+        &self.ethereum_client
+    }
+
+    /// Slippage tolerance used when swap_tokens omits one
+    pub fn default_slippage(&self) -> Decimal {
+        self.default_slippage
+    }
+
+    /// Highest slippage tolerance swap_tokens accepts
+    pub fn max_slippage(&self) -> Decimal {
+        self.max_slippage
+    }
+
+    /// Whether a tool is exposed by this deployment. Signing tools are hidden in read-only mode.
+    pub fn is_tool_enabled(&self, name: &str) -> bool {
+        if !self.ethereum_client.wallet_configured() && SIGNING_TOOLS.contains(&name) {
+            return false;
+        }
+        self.enabled_tools.as_ref().is_none_or(|tools| tools.contains(name))
+    }
+
+    /// Networks tool calls can be served on: the one the client's RPC endpoint is configured for
+    pub fn configured_networks(&self) -> Vec<Network> {
+        vec![self.ethereum_client.network()]
+    }
+
+    /// Reject a `network` argument naming a network without a configured RPC endpoint,
+    /// rather than letting the call fail downstream against the wrong chain
+    fn check_network(&self, args: &Value) -> Result<(), MCPError> {
+        let requested = match args.get("network") {
+            None | Some(Value::Null) => return Ok(()),
+            Some(Value::String(name)) => name,
+            Some(other) => return Err(MCPError::InvalidParameterType(format!("network must be a string: {}", other))),
+        };
+
+        let configured = self.configured_networks();
+        match requested.parse::<Network>() {
+            Ok(network) if configured.contains(&network) => Ok(()),
+            _ => {
+                let available: Vec<&str> = configured.iter().map(|network| network.name()).collect();
+                Err(MCPError::ConfigurationError(format!(
+                    "network {} not configured (available: {})", requested, available.join(", ")
+                )))
+            },
+        }
     }
 
     #[instrument(skip(self), fields(tool_name = %tool_call.name))]
@@ -27,6 +149,23 @@ impl ToolHandler {
             "Tool call started"
         );
         
+        if !self.ethereum_client.wallet_configured() && SIGNING_TOOLS.contains(&tool_call.name.as_str()) {
+            warn!(tool_name = %tool_call.name, "Signing tool requested in read-only mode");
+            return Err(MCPError::WalletNotInitialized(
+                format!("{} requires PRIVATE_KEY; the server is running in read-only mode", tool_call.name)
+            ));
+        }
+
+        if !self.is_tool_enabled(&tool_call.name) {
+            warn!(tool_name = %tool_call.name, "Disabled tool requested");
+            return Err(MCPError::ValidationError(format!("tool disabled: {}", tool_call.name)));
+        }
+
+        if let Err(network_error) = self.check_network(&tool_call.arguments) {
+            warn!(tool_name = %tool_call.name, error = %network_error, "Unconfigured network requested");
+            return Err(network_error);
+        }
+        
         // Validate input parameters
         if let Err(validation_error) = InputValidator::validate_tool_parameters(&tool_call.name, &tool_call.arguments) {
             error!(
@@ -37,14 +176,59 @@ impl ToolHandler {
             return Err(validation_error);
         }
         
-        let result = match tool_call.name.as_str() {
-            "get_balance" => self.handle_get_balance(tool_call.arguments).await,
-            "get_token_price" => self.handle_get_token_price(tool_call.arguments).await,
-            "swap_tokens" => self.handle_swap_tokens(tool_call.arguments).await,
-            _ => {
-                error!(tool_name = %tool_call.name, "Unknown tool requested");
-                Err(MCPError::ValidationError(format!("Unknown tool: {}", tool_call.name)))
-            },
+        let fields = if FIELD_SELECTABLE_TOOLS.contains(&tool_call.name.as_str()) {
+            InputValidator::validate_fields(&tool_call.arguments)?
+        } else {
+            None
+        };
+        
+        let idempotency_key = tool_call.arguments.get("idempotency_key")
+            .and_then(|v| v.as_str())
+            .filter(|_| SIGNING_TOOLS.contains(&tool_call.name.as_str()))
+            .map(str::to_string);
+        if let Some(key) = &idempotency_key {
+            if let Reservation::Replay(content) = self.idempotency.reserve(&tool_call.name, key)? {
+                return Ok(ToolResult { content, is_error: false });
+            }
+        }
+        
+        let deadline = Instant::now() + self.tool_timeout;
+        let call = with_tool_deadline(deadline, self.dispatch(&tool_call.name, tool_call.arguments));
+        let result = match tokio::time::timeout(self.tool_timeout, call).await {
+            Ok(result) => result,
+            Err(_) => {
+                warn!(
+                    tool_name = %tool_call.name,
+                    timeout_secs = self.tool_timeout.as_secs(),
+                    "Tool call timed out"
+                );
+                Err(MCPError::Timeout(format!(
+                    "{} did not complete within {}s", tool_call.name, self.tool_timeout.as_secs()
+                )))
+            }
+        };
+        if let Some(key) = &idempotency_key {
+            match &result {
+                Ok(tool_result) => self.idempotency.complete(&tool_call.name, key, &tool_result.content),
+                // A timed-out call or an unconfirmed send may still have broadcast, so its key stays reserved
+                Err(MCPError::Timeout(_)) | Err(MCPError::TransactionOutcomeUnknown(_)) => {},
+                Err(_) => self.idempotency.release(&tool_call.name, key),
+            }
+        }
+        let result = match fields {
+            Some(fields) => result.map(|tool_result| ToolResult {
+                content: project_fields(&tool_result.content, &fields),
+                ..tool_result
+            }),
+            None => result,
+        };
+        let result = if self.ethereum_client.offline_mode() {
+            result.map(|tool_result| ToolResult {
+                content: mark_mock(tool_result.content),
+                ..tool_result
+            })
+        } else {
+            result
         };
 
         let duration = start_time.elapsed();
@@ -95,26 +279,95 @@ impl ToolHandler {
         result
     }
 
+    /// Route a validated tool call to its handler
+    async fn dispatch(&self, name: &str, args: Value) -> Result<ToolResult, MCPError> {
+        match name {
+            "get_balance" => self.handle_get_balance(args).await,
+            "get_balances_for_addresses" => self.handle_get_balances_for_addresses(args).await,
+            "get_balance_changes" => self.handle_get_balance_changes(args).await,
+            "get_all_token_balances" => self.handle_get_all_token_balances(args).await,
+            "get_portfolio" => self.handle_get_portfolio(args).await,
+            "get_token_price" => self.handle_get_token_price(args).await,
+            "get_onchain_price" => self.handle_get_onchain_price(args).await,
+            "get_eth_price" => self.handle_get_eth_price().await,
+            "get_token_metadata" => self.handle_get_token_metadata(args).await,
+            "get_erc1155_balance" => self.handle_get_erc1155_balance(args).await,
+            "swap_tokens" => self.handle_swap_tokens(args).await,
+            "simulate_token_transfer" => self.handle_simulate_token_transfer(args).await,
+            "transfer_token" => self.handle_transfer_token(args).await,
+            "lookup_ens_name" => self.handle_lookup_ens_name(args).await,
+            "batch_lookup_ens" => self.handle_batch_lookup_ens(args).await,
+            "get_code" => self.handle_get_code(args).await,
+            "get_storage_at" => self.handle_get_storage_at(args).await,
+            "detect_proxy" => self.handle_detect_proxy(args).await,
+            "get_pending_transactions" => self.handle_get_pending_transactions(args).await,
+            "get_fee_history" => self.handle_get_fee_history(args).await,
+            "get_gas_price" => self.handle_get_gas_price().await,
+            "wait_for_gas" => self.handle_wait_for_gas(args).await,
+            "get_logs" => self.handle_get_logs(args).await,
+            "compute_create2_address" => Self::handle_compute_create2_address(args),
+            "validate_address" => Self::handle_validate_address(args),
+            "list_networks" => self.handle_list_networks(),
+            "keccak256" => Self::handle_keccak256(args),
+            "function_selector" => Self::handle_function_selector(args),
+            "send_transaction" => self.handle_send_transaction(args).await,
+            "replace_transaction" => self.handle_replace_transaction(args).await,
+            "build_transaction" => self.handle_build_transaction(args).await,
+            _ => {
+                error!(tool_name = %name, "Unknown tool requested");
+                Err(MCPError::ValidationError(format!("Unknown tool: {}", name)))
+            },
+        }
+    }
+
     #[instrument(skip(self), fields(address = %args.get("address").and_then(|v| v.as_str()).unwrap_or("unknown")))]
     async fn handle_get_balance(&self, args: Value) -> Result<ToolResult, MCPError> {
-        let address = args.get("address")
+        let address = Self::parse_address(&args, "address")?;
+
+        // The native token needs no ERC20 lookups
+        let native_only = args.get("token_address")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| MCPError::JsonRpc("Missing 'address' parameter".to_string()))?;
+            .is_some_and(crate::address::is_native_eth);
+        let token_address = if native_only {
+            None
+        } else {
+            Self::parse_optional_address(&args, "token_address")?
+        };
 
-        let token_address = args.get("token_address")
-            .and_then(|v| v.as_str());
+        let confirmations = args.get("confirmations")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        let block = match args.get("block_number") {
+            Some(block) => InputValidator::validate_block_tag(block)?,
+            // Read `confirmations` blocks behind the head for reorg safety
+            None if confirmations > 0 => Some(BlockNumber::Number(self.ethereum_client.confirmed_block_number(confirmations).await?.into())),
+            None => None,
+        }
+        .map(BlockId::Number);
+
+        let include_usd_value = args.get("include_usd_value")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let decimals_display = Self::parse_decimals_display(&args);
 
         info!(
             address = %address,
-            token_address = token_address.unwrap_or("all"),
+            token_address = token_address.map(|t| t.to_string()).as_deref().unwrap_or(if native_only { "native" } else { "all" }),
+            block = ?block,
             "Fetching balance information"
         );
 
-        let balance_info = self.ethereum_client.get_balance(address, token_address).await?;
+        let balance_info = if native_only {
+            self.ethereum_client.get_native_balance(address, block, include_usd_value).await?
+        } else {
+            self.ethereum_client.get_balance(address, token_address.as_slice(), block, include_usd_value, decimals_display).await?
+        };
 
         info!(
             address = %address,
-            eth_balance = %balance_info.eth_balance,
+            native_balance = %balance_info.native_balance,
+            native_symbol = %balance_info.native_symbol,
             token_count = balance_info.token_balances.len(),
             "Balance information retrieved successfully"
         );
@@ -125,22 +378,115 @@ impl ToolHandler {
         })
     }
 
+    #[instrument(skip(self), fields(address = %args.get("address").and_then(|v| v.as_str()).unwrap_or("unknown")))]
+    async fn handle_get_all_token_balances(&self, args: Value) -> Result<ToolResult, MCPError> {
+        let address = Self::parse_address(&args, "address")?;
+
+        let include_usd_value = args.get("include_usd_value")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let decimals_display = Self::parse_decimals_display(&args);
+
+        info!(
+            address = %address,
+            "Fetching all token balances"
+        );
+
+        let balance_info = self.ethereum_client.get_all_token_balances(address, include_usd_value, decimals_display).await?;
+
+        Ok(ToolResult {
+            content: json!(balance_info),
+            is_error: false,
+        })
+    }
+
+    #[instrument(skip(self, args))]
+    async fn handle_get_balances_for_addresses(&self, args: Value) -> Result<ToolResult, MCPError> {
+        let addresses: Vec<String> = args.get("addresses")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| MCPError::JsonRpc("Missing 'addresses' parameter".to_string()))?
+            .iter()
+            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+            .collect();
+
+        let token_addresses = Self::parse_token_addresses(&args)?;
+
+        info!(
+            address_count = addresses.len(),
+            token_count = token_addresses.len(),
+            "Fetching balances for multiple addresses"
+        );
+
+        let decimals_display = Self::parse_decimals_display(&args);
+
+        let multi = self.ethereum_client.get_balances_for_addresses(&addresses, &token_addresses, decimals_display).await;
+
+        Ok(ToolResult {
+            content: json!(multi),
+            is_error: false,
+        })
+    }
+
+    #[instrument(skip(self), fields(address = %args.get("address").and_then(|v| v.as_str()).unwrap_or("unknown")))]
+    async fn handle_get_balance_changes(&self, args: Value) -> Result<ToolResult, MCPError> {
+        let address = Self::parse_address(&args, "address")?;
+        let token_addresses = Self::parse_token_addresses(&args)?;
+        let from_block = InputValidator::validate_block_tag(
+            args.get("from_block").ok_or_else(|| MCPError::MissingParameter("from_block".to_string()))?
+        )?;
+        let to_block = args.get("to_block")
+            .map(InputValidator::validate_block_tag)
+            .transpose()?
+            .flatten();
+
+        info!(address = %address, from_block = ?from_block, to_block = ?to_block, token_count = token_addresses.len(), "Comparing balances between blocks");
+
+        let changes = self.ethereum_client.get_balance_changes(address, &token_addresses, from_block, to_block).await?;
+
+        Ok(ToolResult {
+            content: json!(changes),
+            is_error: false,
+        })
+    }
+
+    #[instrument(skip(self), fields(address = %args.get("address").and_then(|v| v.as_str()).unwrap_or("unknown")))]
+    async fn handle_get_portfolio(&self, args: Value) -> Result<ToolResult, MCPError> {
+        let address = Self::parse_address(&args, "address")?;
+        let token_addresses = Self::parse_token_addresses(&args)?;
+        let decimals_display = Self::parse_decimals_display(&args);
+
+        info!(address = %address, token_count = token_addresses.len(), "Fetching portfolio");
+
+        let portfolio = self.ethereum_client.get_portfolio(address, &token_addresses, decimals_display).await?;
+
+        Ok(ToolResult {
+            content: json!(portfolio),
+            is_error: false,
+        })
+    }
+
     #[instrument(skip(self), fields(token_address = %args.get("token_address").and_then(|v| v.as_str()).unwrap_or("unknown")))]
     async fn handle_get_token_price(&self, args: Value) -> Result<ToolResult, MCPError> {
-        let token_address = args.get("token_address")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| MCPError::JsonRpc("Missing 'token_address' parameter".to_string()))?;
+        let token_address = Self::parse_address(&args, "token_address")?;
 
         info!(
             token_address = %token_address,
             "Fetching token price information"
         );
 
-        let price_info = self.ethereum_client.get_token_price(token_address).await?;
+        let source = args.get("source")
+            .and_then(|v| v.as_str())
+            .map(|source| source.parse::<PriceSourceSelection>())
+            .transpose()?
+            .unwrap_or_default();
+
+        let price_info = self.ethereum_client.get_token_price(token_address, source).await?;
 
         info!(
             token_address = %token_address,
             price_usd = %price_info.price_usd,
+            source = ?price_info.source,
             "Token price information retrieved successfully"
         );
 
@@ -150,15 +496,82 @@ impl ToolHandler {
         })
     }
 
+    #[instrument(skip(self))]
+    async fn handle_get_eth_price(&self) -> Result<ToolResult, MCPError> {
+        let price_info = self.ethereum_client.get_eth_price().await?;
+
+        info!(price_usd = %price_info.price_usd, "ETH price retrieved successfully");
+
+        Ok(ToolResult {
+            content: json!(price_info),
+            is_error: false,
+        })
+    }
+
+    #[instrument(skip(self), fields(token_address = %args.get("token_address").and_then(|v| v.as_str()).unwrap_or("unknown")))]
+    async fn handle_get_onchain_price(&self, args: Value) -> Result<ToolResult, MCPError> {
+        let token_address = Self::parse_address(&args, "token_address")?;
+
+        info!(
+            token_address = %token_address,
+            "Fetching on-chain token price"
+        );
+
+        let price_info = self.ethereum_client.get_onchain_price(token_address).await?;
+
+        Ok(ToolResult {
+            content: json!(price_info),
+            is_error: false,
+        })
+    }
+
+    #[instrument(skip(self), fields(token_address = %args.get("token_address").and_then(|v| v.as_str()).unwrap_or("unknown")))]
+    async fn handle_get_token_metadata(&self, args: Value) -> Result<ToolResult, MCPError> {
+        let token_address = Self::parse_address(&args, "token_address")?;
+
+        info!(
+            token_address = %token_address,
+            "Fetching token metadata"
+        );
+
+        let metadata = self.ethereum_client.get_token_metadata(token_address).await;
+
+        Ok(ToolResult {
+            content: json!(metadata),
+            is_error: false,
+        })
+    }
+
+    #[instrument(skip(self), fields(contract_address = %args.get("contract_address").and_then(|v| v.as_str()).unwrap_or("unknown")))]
+    async fn handle_get_erc1155_balance(&self, args: Value) -> Result<ToolResult, MCPError> {
+        let contract_address = Self::parse_address(&args, "contract_address")?;
+        let owner = Self::parse_address(&args, "owner")?;
+
+        let token_id_str = args.get("token_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::JsonRpc("Missing 'token_id' parameter".to_string()))?;
+        let token_id = InputValidator::validate_token_id(token_id_str)?;
+
+        info!(
+            contract_address = %contract_address,
+            owner = %owner,
+            token_id = %token_id_str,
+            "Fetching ERC1155 balance"
+        );
+
+        let balance = self.ethereum_client.get_erc1155_balance(contract_address, owner, token_id).await?;
+
+        Ok(ToolResult {
+            content: json!(balance),
+            is_error: false,
+        })
+    }
+
     #[instrument(skip(self), fields(from_token = %args.get("from_token").and_then(|v| v.as_str()).unwrap_or("unknown"), to_token = %args.get("to_token").and_then(|v| v.as_str()).unwrap_or("unknown")))]
     async fn handle_swap_tokens(&self, args: Value) -> Result<ToolResult, MCPError> {
-        let from_token = args.get("from_token")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| MCPError::JsonRpc("Missing 'from_token' parameter".to_string()))?;
+        let (from_token, from_native) = self.parse_swap_token(&args, "from_token")?;
 
-        let to_token = args.get("to_token")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| MCPError::JsonRpc("Missing 'to_token' parameter".to_string()))?;
+        let (to_token, to_native) = self.parse_swap_token(&args, "to_token")?;
 
         let amount_str = args.get("amount")
             .and_then(|v| v.as_str())
@@ -167,28 +580,57 @@ impl ToolHandler {
         let amount = Decimal::from_str(amount_str)
             .map_err(|e| MCPError::JsonRpc(format!("Invalid amount: {}", e)))?;
 
-        let slippage_str = args.get("slippage_tolerance")
-            .and_then(|v| v.as_str())
-            .unwrap_or("0.5");
+        let slippage = match args.get("slippage_tolerance").and_then(|v| v.as_str()) {
+            Some(slippage_str) => InputValidator::validate_slippage_with_cap(slippage_str, self.max_slippage)?,
+            None => self.default_slippage,
+        };
+
+        let deadline_secs = args.get("deadline_secs")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_SWAP_DEADLINE_SECS);
 
-        let slippage = Decimal::from_str(slippage_str)
-            .map_err(|e| MCPError::JsonRpc(format!("Invalid slippage: {}", e)))?;
+        // Check the balance of the given wallet (default: server wallet) unless skipped for price discovery
+        let skip_balance_check = args.get("skip_balance_check")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let balance_owner = if skip_balance_check {
+            None
+        } else {
+            match Self::parse_optional_address(&args, "wallet_address")? {
+                Some(wallet) => Some(wallet.address()),
+                None => Some(self.ethereum_client.require_wallet("swap_tokens balance check without 'wallet_address'")?),
+            }
+        };
 
         info!(
             from_token = %from_token,
             to_token = %to_token,
             amount = %amount,
             slippage = %slippage,
+            from_native,
+            to_native,
             "Simulating token swap"
         );
 
-        let simulation = self.ethereum_client.simulate_swap(from_token, to_token, amount, slippage).await?;
+        let quote_currency = args.get("quote_currency")
+            .and_then(|v| v.as_str())
+            .map(|currency| currency.parse::<QuoteCurrency>())
+            .transpose()?
+            .unwrap_or_default();
+
+        let mut simulation = self.ethereum_client.simulate_swap(from_token, to_token, amount, slippage, balance_owner, Self::parse_gas_multiplier(&args)?, (from_native, to_native), deadline_secs).await?;
+
+        if quote_currency == QuoteCurrency::Usd {
+            let native_price_usd = self.ethereum_client.get_native_price_usd().await?;
+            simulation.total_cost_usd = Some(simulation.total_cost * native_price_usd);
+        }
 
         info!(
             from_token = %from_token,
             to_token = %to_token,
             amount_in = %simulation.amount_in,
             amount_out = %simulation.amount_out,
+            minimum_received = %simulation.minimum_received,
             gas_estimate = simulation.gas_estimate,
             "Token swap simulation completed successfully"
         );
@@ -198,4 +640,640 @@ impl ToolHandler {
             is_error: false,
         })
     }
+
+    #[instrument(skip(self), fields(token_address = %args.get("token_address").and_then(|v| v.as_str()).unwrap_or("unknown"), to = %args.get("to").and_then(|v| v.as_str()).unwrap_or("unknown")))]
+    async fn handle_simulate_token_transfer(&self, args: Value) -> Result<ToolResult, MCPError> {
+        let token_address = Self::parse_address(&args, "token_address")?;
+
+        let to = Self::parse_address(&args, "to")?;
+
+        let amount_str = args.get("amount")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::JsonRpc("Missing 'amount' parameter".to_string()))?;
+
+        let amount = Decimal::from_str(amount_str)
+            .map_err(|e| MCPError::JsonRpc(format!("Invalid amount: {}", e)))?;
+
+        info!(
+            token_address = %token_address,
+            to = %to,
+            amount = %amount,
+            "Simulating token transfer"
+        );
+
+        let simulation = self.ethereum_client.simulate_token_transfer(token_address, to, amount, Self::parse_gas_multiplier(&args)?).await?;
+
+        Ok(ToolResult {
+            content: json!(simulation),
+            is_error: false,
+        })
+    }
+
+    #[instrument(skip(self), fields(token_address = %args.get("token_address").and_then(|v| v.as_str()).unwrap_or("unknown"), to = %args.get("to").and_then(|v| v.as_str()).unwrap_or("unknown")))]
+    async fn handle_transfer_token(&self, args: Value) -> Result<ToolResult, MCPError> {
+        let token_address = Self::parse_address(&args, "token_address")?;
+
+        let to = Self::parse_address(&args, "to")?;
+
+        let amount_str = args.get("amount")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::JsonRpc("Missing 'amount' parameter".to_string()))?;
+
+        let amount = Decimal::from_str(amount_str)
+            .map_err(|e| MCPError::JsonRpc(format!("Invalid amount: {}", e)))?;
+
+        info!(
+            token_address = %token_address,
+            to = %to,
+            amount = %amount,
+            "Transferring tokens"
+        );
+
+        let transfer = self.ethereum_client.transfer_token(token_address, to, amount, Self::parse_gas_multiplier(&args)?).await?;
+
+        Ok(ToolResult {
+            content: json!(transfer),
+            is_error: false,
+        })
+    }
+
+    #[instrument(skip(self), fields(address = %args.get("address").and_then(|v| v.as_str()).unwrap_or("unknown")))]
+    async fn handle_lookup_ens_name(&self, args: Value) -> Result<ToolResult, MCPError> {
+        let address = Self::parse_address(&args, "address")?;
+
+        info!(
+            address = %address,
+            "Looking up ENS name"
+        );
+
+        let lookup = self.ethereum_client.lookup_ens_name(address).await?;
+
+        Ok(ToolResult {
+            content: json!(lookup),
+            is_error: false,
+        })
+    }
+
+    #[instrument(skip(self, args))]
+    async fn handle_batch_lookup_ens(&self, args: Value) -> Result<ToolResult, MCPError> {
+        let addresses: Vec<String> = args.get("addresses")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| MCPError::JsonRpc("Missing 'addresses' parameter".to_string()))?
+            .iter()
+            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+            .collect();
+
+        info!(
+            address_count = addresses.len(),
+            "Looking up ENS names for multiple addresses"
+        );
+
+        let batch = self.ethereum_client.batch_lookup_ens(&addresses).await;
+
+        Ok(ToolResult {
+            content: json!(batch),
+            is_error: false,
+        })
+    }
+
+    #[instrument(skip(self), fields(address = %args.get("address").and_then(|v| v.as_str()).unwrap_or("unknown")))]
+    async fn handle_get_code(&self, args: Value) -> Result<ToolResult, MCPError> {
+        let address = Self::parse_address(&args, "address")?;
+
+        info!(
+            address = %address,
+            "Fetching contract code"
+        );
+
+        let code = self.ethereum_client.get_code(address).await?;
+
+        Ok(ToolResult {
+            content: json!(code),
+            is_error: false,
+        })
+    }
+
+    #[instrument(skip(self), fields(address = %args.get("address").and_then(|v| v.as_str()).unwrap_or("unknown")))]
+    async fn handle_get_pending_transactions(&self, args: Value) -> Result<ToolResult, MCPError> {
+        let address = Self::parse_address(&args, "address")?;
+
+        info!(
+            address = %address,
+            "Fetching pending transactions"
+        );
+
+        let pending = self.ethereum_client.get_pending_transactions(address).await?;
+
+        Ok(ToolResult {
+            content: json!(pending),
+            is_error: false,
+        })
+    }
+
+    #[instrument(skip(self), fields(address = %args.get("address").and_then(|v| v.as_str()).unwrap_or("unknown")))]
+    async fn handle_get_storage_at(&self, args: Value) -> Result<ToolResult, MCPError> {
+        let address = Self::parse_address(&args, "address")?;
+
+        let slot_str = args.get("slot")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::JsonRpc("Missing 'slot' parameter".to_string()))?;
+        let slot = InputValidator::validate_storage_slot(slot_str)?;
+
+        info!(
+            address = %address,
+            slot = %slot_str,
+            "Reading storage slot"
+        );
+
+        let storage = self.ethereum_client.get_storage_at(address, slot).await?;
+
+        Ok(ToolResult {
+            content: json!(storage),
+            is_error: false,
+        })
+    }
+
+    #[instrument(skip(self), fields(address = %args.get("address").and_then(|v| v.as_str()).unwrap_or("unknown")))]
+    async fn handle_detect_proxy(&self, args: Value) -> Result<ToolResult, MCPError> {
+        let address = Self::parse_address(&args, "address")?;
+
+        info!(
+            address = %address,
+            "Detecting proxy"
+        );
+
+        let proxy = self.ethereum_client.detect_proxy(address).await?;
+
+        Ok(ToolResult {
+            content: json!(proxy),
+            is_error: false,
+        })
+    }
+
+    #[instrument(skip(self), fields(to = %args.get("to").and_then(|v| v.as_str()).unwrap_or("unknown")))]
+    async fn handle_send_transaction(&self, args: Value) -> Result<ToolResult, MCPError> {
+        let to = Self::parse_address(&args, "to")?;
+
+        let amount_str = args.get("amount")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::JsonRpc("Missing 'amount' parameter".to_string()))?;
+
+        let amount = Decimal::from_str(amount_str)
+            .map_err(|e| MCPError::JsonRpc(format!("Invalid amount: {}", e)))?;
+
+        let fees = Self::parse_fee_options(&args)?;
+
+        info!(
+            to = %to,
+            amount = %amount,
+            legacy = fees.legacy,
+            "Sending transaction"
+        );
+
+        let skip_simulation = args.get("skip_simulation")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let allow_self_transfer = args.get("allow_self_transfer")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let tx_info = self.ethereum_client.send_transaction(to, amount, fees, skip_simulation, allow_self_transfer).await?;
+
+        info!(
+            tx_hash = %tx_info.tx_hash,
+            status = %tx_info.status,
+            "Transaction sent"
+        );
+
+        Ok(ToolResult {
+            content: json!(tx_info),
+            is_error: false,
+        })
+    }
+
+    #[instrument(skip(self), fields(nonce = ?args.get("nonce")))]
+    async fn handle_replace_transaction(&self, args: Value) -> Result<ToolResult, MCPError> {
+        let nonce = args.get("nonce")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| MCPError::JsonRpc("Missing 'nonce' parameter".to_string()))?;
+        let bump_percent = match args.get("bump_percent") {
+            Some(bump) => InputValidator::validate_bump_percent(bump)?,
+            None => DEFAULT_BUMP_PERCENT,
+        };
+        let cancel = args.get("cancel")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        info!(
+            nonce = nonce,
+            bump_percent = %bump_percent,
+            cancel = cancel,
+            "Replacing transaction"
+        );
+
+        let replacement = self.ethereum_client.replace_transaction(nonce, bump_percent, cancel).await?;
+
+        info!(
+            tx_hash = %replacement.tx_hash,
+            status = %replacement.status,
+            "Replacement transaction sent"
+        );
+
+        Ok(ToolResult {
+            content: json!(replacement),
+            is_error: false,
+        })
+    }
+
+    #[instrument(skip(self), fields(to = %args.get("to").and_then(|v| v.as_str()).unwrap_or("unknown")))]
+    async fn handle_build_transaction(&self, args: Value) -> Result<ToolResult, MCPError> {
+        let to = Self::parse_address(&args, "to")?;
+
+        let amount_str = args.get("amount")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::JsonRpc("Missing 'amount' parameter".to_string()))?;
+
+        let amount = Decimal::from_str(amount_str)
+            .map_err(|e| MCPError::JsonRpc(format!("Invalid amount: {}", e)))?;
+
+        let from = Self::parse_optional_address(&args, "from")?;
+        let fees = Self::parse_fee_options(&args)?;
+
+        info!(
+            from = from.map(|f| f.to_string()).as_deref().unwrap_or("server wallet"),
+            to = %to,
+            amount = %amount,
+            legacy = fees.legacy,
+            "Building unsigned transaction"
+        );
+
+        let unsigned = self.ethereum_client.build_transaction(from, to, amount, fees).await?;
+
+        Ok(ToolResult {
+            content: json!(unsigned),
+            is_error: false,
+        })
+    }
+
+    #[instrument(skip(self))]
+    async fn handle_get_gas_price(&self) -> Result<ToolResult, MCPError> {
+        info!("Fetching gas price");
+
+        let gas_price = self.ethereum_client.get_gas_price().await?;
+
+        Ok(ToolResult {
+            content: json!(gas_price),
+            is_error: false,
+        })
+    }
+
+    #[instrument(skip(self, args))]
+    async fn handle_wait_for_gas(&self, args: Value) -> Result<ToolResult, MCPError> {
+        let target_str = args.get("target_gwei")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::JsonRpc("Missing 'target_gwei' parameter".to_string()))?;
+        let target_gwei = InputValidator::validate_gwei(target_str)?;
+
+        let timeout_secs = args.get("timeout_secs")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_GAS_WAIT_SECS);
+        // The wait has to finish, even unsuccessfully, before the tool call is cut off
+        if timeout_secs >= self.tool_timeout.as_secs() {
+            return Err(MCPError::ValidationError(format!(
+                "timeout_secs ({}) must be below TOOL_TIMEOUT_SECS ({})", timeout_secs, self.tool_timeout.as_secs()
+            )));
+        }
+        let poll_interval_secs = args.get("poll_interval_secs")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_GAS_POLL_INTERVAL_SECS);
+
+        info!(target_gwei = %target_gwei, timeout_secs, poll_interval_secs, "Waiting for gas price");
+
+        let wait = self.ethereum_client
+            .wait_for_gas(target_gwei, Duration::from_secs(timeout_secs), Duration::from_secs(poll_interval_secs))
+            .await?;
+
+        Ok(ToolResult {
+            content: json!(wait),
+            is_error: false,
+        })
+    }
+
+    #[instrument(skip(self, args))]
+    async fn handle_get_fee_history(&self, args: Value) -> Result<ToolResult, MCPError> {
+        let block_count = args.get("block_count")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_FEE_HISTORY_BLOCKS);
+
+        let reward_percentiles: Vec<f64> = args.get("reward_percentiles")
+            .and_then(|v| v.as_array())
+            .map(|values| values.iter().filter_map(|v| v.as_f64()).collect())
+            .unwrap_or_default();
+
+        info!(
+            block_count = block_count,
+            reward_percentiles = ?reward_percentiles,
+            "Fetching fee history"
+        );
+
+        let fee_history = self.ethereum_client.get_fee_history(block_count, reward_percentiles).await?;
+
+        Ok(ToolResult {
+            content: json!(fee_history),
+            is_error: false,
+        })
+    }
+
+    #[instrument(skip(self), fields(address = %args.get("address").and_then(|v| v.as_str()).unwrap_or("any")))]
+    async fn handle_get_logs(&self, args: Value) -> Result<ToolResult, MCPError> {
+        let address = Self::parse_optional_address(&args, "address")?;
+
+        let from_block = args.get("from_block")
+            .map(InputValidator::validate_block_number)
+            .transpose()?
+            .flatten()
+            .ok_or_else(|| MCPError::JsonRpc("Missing 'from_block' parameter".to_string()))?;
+        let to_block = match args.get("to_block") {
+            Some(block) => InputValidator::validate_block_number(block)?,
+            None => None,
+        };
+
+        let topics = args.get("topics")
+            .and_then(|v| v.as_array())
+            .map(|topics| topics.iter()
+                .map(|topic| topic.as_str().map(H256::from_str).transpose())
+                .collect::<Result<Vec<_>, _>>())
+            .transpose()
+            .map_err(|e| MCPError::InvalidParameterType(format!("Invalid topic: {}", e)))?
+            .unwrap_or_default();
+
+        info!(
+            from_block = from_block,
+            to_block = ?to_block,
+            topic_count = topics.len(),
+            "Fetching logs"
+        );
+
+        let logs = self.ethereum_client.get_logs(address, topics, from_block, to_block).await?;
+
+        Ok(ToolResult {
+            content: json!(logs),
+            is_error: false,
+        })
+    }
+
+    /// Pure computation: keccak256(0xff ++ deployer ++ salt ++ init_code_hash)[12..]
+    fn handle_compute_create2_address(args: Value) -> Result<ToolResult, MCPError> {
+        let deployer = Self::parse_address(&args, "deployer")?;
+        let bytes32 = |field: &str| args.get(field)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::JsonRpc(format!("Missing '{}' parameter", field)))
+            .and_then(|value| InputValidator::validate_bytes32(value, field));
+        let salt = bytes32("salt")?;
+        let init_code_hash = bytes32("init_code_hash")?;
+
+        let address = ethers::utils::get_create2_address_from_hash(deployer.address(), salt, init_code_hash);
+        info!(deployer = %deployer, address = %format!("0x{:x}", address), "CREATE2 address computed");
+
+        Ok(ToolResult {
+            content: json!(Create2Address {
+                deployer: deployer.checksummed(),
+                salt: format!("{:#x}", salt),
+                init_code_hash: format!("{:#x}", init_code_hash),
+                address: ParsedAddress::from(address).checksummed(),
+            }),
+            is_error: false,
+        })
+    }
+
+    /// Pure compute: keccak256 of UTF-8 text or hex bytes
+    fn handle_keccak256(args: Value) -> Result<ToolResult, MCPError> {
+        let data = args.get("data")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::JsonRpc("Missing 'data' parameter".to_string()))?;
+        let bytes = InputValidator::validate_hash_input(data, args.get("encoding").and_then(|v| v.as_str()))?;
+
+        Ok(ToolResult {
+            content: json!(Keccak256Hash {
+                input_length: bytes.len(),
+                hash: format!("0x{}", hex::encode(ethers::utils::keccak256(&bytes))),
+            }),
+            is_error: false,
+        })
+    }
+
+    /// Pure compute: 4-byte selector of a canonical function signature
+    fn handle_function_selector(args: Value) -> Result<ToolResult, MCPError> {
+        let signature = args.get("signature")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::JsonRpc("Missing 'signature' parameter".to_string()))?;
+
+        Ok(ToolResult {
+            content: json!(Self::function_selector(signature)),
+            is_error: false,
+        })
+    }
+
+    /// Selector and full hash of `signature`
+    pub fn function_selector(signature: &str) -> FunctionSelector {
+        let hash = ethers::utils::keccak256(signature.as_bytes());
+        debug!(signature = %signature, selector = %hex::encode(&hash[..4]), "Function selector computed");
+        FunctionSelector {
+            signature: signature.to_string(),
+            selector: format!("0x{}", hex::encode(&hash[..4])),
+            hash: format!("0x{}", hex::encode(hash)),
+        }
+    }
+
+    /// Pure check: address format, EIP-55 checksum and ENS detection, without any RPC call
+    fn handle_validate_address(args: Value) -> Result<ToolResult, MCPError> {
+        let address = args.get("address")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::JsonRpc("Missing 'address' parameter".to_string()))?;
+
+        Ok(ToolResult {
+            content: json!(Self::check_address(address)),
+            is_error: false,
+        })
+    }
+
+    fn handle_list_networks(&self) -> Result<ToolResult, MCPError> {
+        Ok(ToolResult {
+            content: json!(Self::network_infos(self.ethereum_client.network())),
+            is_error: false,
+        })
+    }
+
+    /// Every supported network, with `enabled` set for the one this server is configured for
+    pub fn network_infos(configured: crate::network::Network) -> Vec<NetworkInfo> {
+        crate::network::Network::ALL.iter()
+            .map(|network| NetworkInfo {
+                name: network.name().to_string(),
+                chain_id: network.chain_id(),
+                native_symbol: network.native_symbol().to_string(),
+                enabled: *network == configured,
+            })
+            .collect()
+    }
+
+    /// Classify an address string for the validate_address tool
+    pub fn check_address(address: &str) -> AddressValidation {
+        let is_ens = InputValidator::is_ens_name(address);
+        let (valid, checksummed, reason) = if is_ens {
+            (false, None, Some("ENS name; resolve it to an address first".to_string()))
+        } else {
+            match ParsedAddress::parse(address) {
+                Ok(parsed) => (true, Some(parsed.checksummed()), None),
+                Err(e) => (false, None, Some(e.to_string())),
+            }
+        };
+        debug!(address = %address, valid = valid, is_ens = is_ens, "Address validated");
+
+        AddressValidation {
+            address: address.to_string(),
+            valid,
+            checksummed,
+            is_ens,
+            reason,
+        }
+    }
+
+    /// Parse the optional `token_addresses` array
+    fn parse_token_addresses(args: &Value) -> Result<Vec<ParsedAddress>, MCPError> {
+        Ok(args.get("token_addresses")
+            .and_then(|v| v.as_array())
+            .map(|tokens| tokens.iter()
+                .filter_map(|v| v.as_str())
+                .map(ParsedAddress::parse)
+                .collect::<Result<Vec<_>, _>>())
+            .transpose()?
+            .unwrap_or_default())
+    }
+
+    /// Parse the display precision shared by the balance tools
+    fn parse_decimals_display(args: &Value) -> u32 {
+        args.get("decimals_display")
+            .and_then(|v| v.as_u64())
+            .map(|dp| dp as u32)
+            .unwrap_or(DEFAULT_DECIMALS_DISPLAY)
+    }
+
+    /// Parse a required address argument
+    fn parse_address(args: &Value, key: &str) -> Result<ParsedAddress, MCPError> {
+        let address = args.get(key)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::JsonRpc(format!("Missing '{}' parameter", key)))?;
+        ParsedAddress::parse(address)
+    }
+
+    /// Parse an optional address argument
+    fn parse_optional_address(args: &Value, key: &str) -> Result<Option<ParsedAddress>, MCPError> {
+        args.get(key)
+            .and_then(|v| v.as_str())
+            .map(ParsedAddress::parse)
+            .transpose()
+    }
+
+    /// Parse a swap token, substituting the network's WETH for native ETH markers.
+    /// Returns the token and whether it was native ETH.
+    fn parse_swap_token(&self, args: &Value, key: &str) -> Result<(ParsedAddress, bool), MCPError> {
+        let token = args.get(key)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| MCPError::JsonRpc(format!("Missing '{}' parameter", key)))?;
+
+        if crate::address::is_native_eth(token) {
+            let weth = ParsedAddress::parse(self.ethereum_client.network().weth_address())?;
+            debug!(token = %token, weth = %weth, "Substituting WETH for native ETH");
+            Ok((weth, true))
+        } else {
+            Ok((ParsedAddress::parse(token)?, false))
+        }
+    }
+
+    /// Parse the fee overrides shared by the transaction tools
+    fn parse_fee_options(args: &Value) -> Result<TransactionFeeOptions, MCPError> {
+        Ok(TransactionFeeOptions {
+            max_fee_per_gas: args.get("max_fee_per_gas")
+                .and_then(|v| v.as_str())
+                .map(InputValidator::validate_gwei)
+                .transpose()?,
+            max_priority_fee_per_gas: args.get("max_priority_fee_per_gas")
+                .and_then(|v| v.as_str())
+                .map(InputValidator::validate_gwei)
+                .transpose()?,
+            legacy: args.get("legacy").and_then(|v| v.as_bool()).unwrap_or(false),
+            gas_multiplier: Self::parse_gas_multiplier(args)?,
+        })
+    }
+
+    /// Parse the optional per-call gas limit multiplier
+    fn parse_gas_multiplier(args: &Value) -> Result<Option<Decimal>, MCPError> {
+        args.get("gas_multiplier")
+            .and_then(|v| v.as_str())
+            .map(InputValidator::validate_gas_multiplier)
+            .transpose()
+    }
+}
+
+/// Label an offline-mode result as mock data: objects gain `mock: true`, anything else
+/// is wrapped as `{ result, mock: true }`
+pub fn mark_mock(content: Value) -> Value {
+    match content {
+        Value::Object(mut map) => {
+            map.insert("mock".to_string(), Value::Bool(true));
+            Value::Object(map)
+        },
+        other => json!({ "result": other, "mock": true }),
+    }
+}
+
+/// Keep only the dotted `fields` paths of a tool response. Arrays, and objects keyed by
+/// address such as `token_balances`, apply the path to each entry; unknown fields are omitted.
+pub fn project_fields(value: &Value, fields: &[String]) -> Value {
+    let paths: Vec<Vec<&str>> = fields.iter().map(|field| field.split('.').collect()).collect();
+    project_paths(value, &paths).unwrap_or_else(|| json!({}))
+}
+
+fn project_paths(value: &Value, paths: &[Vec<&str>]) -> Option<Value> {
+    if paths.iter().any(|path| path.is_empty()) {
+        return Some(value.clone());
+    }
+    match value {
+        Value::Array(items) => Some(Value::Array(
+            items.iter().map(|item| project_paths(item, paths).unwrap_or_else(|| json!({}))).collect()
+        )),
+        Value::Object(map) if !map.is_empty() && map.keys().all(|key| ParsedAddress::parse(key).is_ok()) => Some(Value::Object(
+            map.iter()
+                .filter_map(|(key, entry)| project_paths(entry, paths).map(|entry| (key.clone(), entry)))
+                .collect()
+        )),
+        Value::Object(map) => {
+            let projected: serde_json::Map<String, Value> = map.iter()
+                .filter_map(|(key, entry)| {
+                    let rest: Vec<Vec<&str>> = paths.iter()
+                        .filter(|path| path[0] == key)
+                        .map(|path| path[1..].to_vec())
+                        .collect();
+                    if rest.is_empty() {
+                        return None;
+                    }
+                    project_paths(entry, &rest).map(|entry| (key.clone(), entry))
+                })
+                .collect();
+            Some(Value::Object(projected))
+        },
+        // A scalar has no sub-fields to select
+        _ => None,
+    }
+}
+
+/// Parse a comma-separated tool list; an empty list means no restriction
+pub(crate) fn parse_enabled_tools(value: &str) -> Option<HashSet<String>> {
+    let tools: HashSet<String> = value.split(',')
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty())
+        .map(|name| name.to_string())
+        .collect();
+
+    if tools.is_empty() { None } else { Some(tools) }
 }
@@ -0,0 +1,222 @@
+use crate::error::MCPError;
+use async_trait::async_trait;
+use crate::types::SwapSource;
+use ethers::types::{Address, BlockId, BlockNumber, U256};
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::AtomicU64;
+use std::sync::Arc;
+
+const ALCHEMY_RPC_URL: &str = "https://eth-mainnet.g.alchemy.com/v2/JZUYcRpkXq25weYd16Fuu";
+const ALCHEMY_PRICES_URL: &str = "https://api.g.alchemy.com/prices/v1/JZUYcRpkXq25weYd16Fuu/tokens/by-address";
+const ZEROX_PRICE_URL: &str = "https://api.0x.org/swap/permit2/price";
+const ONEINCH_API_URL: &str = "https://api.1inch.dev/swap/v6.0";
+
+/// Output of a DEX aggregator quote, in the buy token's smallest unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AggregatorQuote {
+    pub amount_out: U256,
+    /// Gas the aggregator expects its route to use, when it reports one
+    pub gas_estimate: Option<u64>,
+}
+
+/// Direct HTTP calls that bypass the provider: Uniswap quotes, swap gas estimates,
+/// aggregator quotes and USD prices. `EthereumClient` goes through this trait so tests can inject a mock.
+#[async_trait]
+pub trait RpcTransport: Send + Sync {
+    /// `eth_call` returning the raw return data (`block` defaults to latest)
+    async fn eth_call(&self, to: Address, data: Vec<u8>, block: Option<BlockId>) -> Result<Vec<u8>, MCPError>;
+
+    /// `eth_estimateGas`; a JSON-RPC error response (e.g. a revert) is `GasEstimationFailed`
+    async fn estimate_gas(&self, from: Address, to: Address, data: Vec<u8>) -> Result<u64, MCPError>;
+
+    /// USD prices keyed by lowercase address; tokens without a price are absent
+    async fn fetch_prices(&self, network_slug: &str, token_addresses: &[&str]) -> Result<HashMap<String, Decimal>, MCPError>;
+
+    /// Quote selling `amount` raw units of `sell_token` for `buy_token` through a DEX aggregator
+    async fn aggregator_quote(&self, source: SwapSource, api_key: &str, chain_id: u64, sell_token: Address, buy_token: Address, amount: U256) -> Result<AggregatorQuote, MCPError>;
+}
+
+/// `RpcTransport` backed by the Alchemy node and Price API
+pub struct AlchemyTransport {
+    client: reqwest::Client,
+    /// Shared with the provider's counter so every outbound round trip is counted once
+    request_count: Arc<AtomicU64>,
+}
+
+impl AlchemyTransport {
+    pub fn new(client: reqwest::Client, request_count: Arc<AtomicU64>) -> Self {
+        Self { client, request_count }
+    }
+
+    /// POST a JSON-RPC request to the Alchemy node and return the parsed response
+    async fn rpc_request(&self, method: &str, params: Value) -> Result<Value, MCPError> {
+        crate::logging::record_rpc_call(&self.request_count);
+        let request_body = json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+            "id": 1
+        });
+
+        let response = self.client
+            .post(ALCHEMY_RPC_URL)
+            .header("Content-Type", "application/json")
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(|e| MCPError::NetworkError(format!("Failed to call Alchemy API ({}): {}", method, e)))?;
+
+        let response_text = response.text().await
+            .map_err(|e| MCPError::NetworkError(format!("Failed to read {} response: {}", method, e)))?;
+
+        serde_json::from_str(&response_text)
+            .map_err(|e| MCPError::NetworkError(format!("Failed to parse {} response: {}", method, e)))
+    }
+}
+
+/// A quantity an aggregator API returns either as a decimal string or a JSON number
+fn quantity(value: Option<&Value>) -> Option<U256> {
+    match value? {
+        Value::String(s) => U256::from_dec_str(s).ok(),
+        Value::Number(n) => n.as_u64().map(U256::from),
+        _ => None,
+    }
+}
+
+/// The `0x`-prefixed string in a JSON-RPC response's `result`
+fn hex_result(response: &Value) -> Option<&str> {
+    response.get("result")
+        .and_then(|result| result.as_str())
+        .and_then(|result| result.strip_prefix("0x"))
+}
+
+#[async_trait]
+impl RpcTransport for AlchemyTransport {
+    async fn eth_call(&self, to: Address, data: Vec<u8>, block: Option<BlockId>) -> Result<Vec<u8>, MCPError> {
+        let response = self.rpc_request("eth_call", json!([
+            {
+                "to": format!("0x{:x}", to),
+                "data": format!("0x{}", hex::encode(&data))
+            },
+            block.unwrap_or(BlockId::Number(BlockNumber::Latest))
+        ])).await?;
+
+        if let Some(hex_data) = hex_result(&response) {
+            return hex::decode(hex_data)
+                .map_err(|e| MCPError::NetworkError(format!("Failed to decode hex: {}", e)));
+        }
+
+        if let Some(error) = response.get("error") {
+            return Err(MCPError::SwapSimulationFailed(format!("Alchemy API error: {}", error)));
+        }
+
+        Err(MCPError::SwapSimulationFailed("No result in Alchemy response".to_string()))
+    }
+
+    async fn estimate_gas(&self, from: Address, to: Address, data: Vec<u8>) -> Result<u64, MCPError> {
+        let response = self.rpc_request("eth_estimateGas", json!([
+            {
+                "to": format!("0x{:x}", to),
+                "data": format!("0x{}", hex::encode(&data)),
+                "from": format!("0x{:x}", from)
+            }
+        ])).await?;
+
+        if let Some(hex_data) = hex_result(&response) {
+            return u64::from_str_radix(hex_data, 16)
+                .map_err(|e| MCPError::NetworkError(format!("Failed to parse gas estimate: {}", e)));
+        }
+
+        if let Some(error) = response.get("error") {
+            return Err(MCPError::GasEstimationFailed(format!("Alchemy API error: {}", error)));
+        }
+
+        Err(MCPError::SwapSimulationFailed("No result in gas estimation response".to_string()))
+    }
+
+    async fn fetch_prices(&self, network_slug: &str, token_addresses: &[&str]) -> Result<HashMap<String, Decimal>, MCPError> {
+        crate::logging::record_rpc_call(&self.request_count);
+        let addresses: Vec<Value> = token_addresses.iter()
+            .map(|address| json!({ "network": network_slug, "address": address }))
+            .collect();
+        let request_body = json!({ "addresses": addresses });
+        let response = self.client
+            .post(ALCHEMY_PRICES_URL)
+            .header("Content-Type", "application/json")
+            .json(&request_body)
+            .timeout(std::time::Duration::from_secs(10))
+            .send()
+            .await
+            .map_err(|e| MCPError::NetworkError(format!("Failed to call Alchemy API: {}", e)))?;
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(MCPError::ApiRateLimitExceeded("Alchemy Price API rate limit reached".to_string()));
+        }
+        if !response.status().is_success() {
+            return Err(MCPError::PriceFetchFailed(format!("Alchemy API returned status: {}", response.status())));
+        }
+        let json: Value = response
+            .json()
+            .await
+            .map_err(|e| MCPError::PriceFetchFailed(format!("Failed to parse Alchemy API response: {}", e)))?;
+
+        let mut prices = HashMap::new();
+        if let Some(data_array) = json.get("data").and_then(|d| d.as_array()) {
+            for token_data in data_array {
+                let address = token_data.get("address").and_then(|a| a.as_str());
+                let price_usd_str = token_data.get("prices")
+                    .and_then(|p| p.as_array())
+                    .and_then(|p| p.first())
+                    .and_then(|price_info| price_info.get("value"))
+                    .and_then(|v| v.as_str());
+                if let (Some(address), Some(price_usd_str)) = (address, price_usd_str) {
+                    let price_usd_decimal = price_usd_str
+                        .parse::<Decimal>()
+                        .map_err(|_| MCPError::PriceFetchFailed("Failed to parse price value".to_string()))?;
+                    prices.insert(address.to_lowercase(), price_usd_decimal);
+                }
+            }
+        }
+        Ok(prices)
+    }
+
+    async fn aggregator_quote(&self, source: SwapSource, api_key: &str, chain_id: u64, sell_token: Address, buy_token: Address, amount: U256) -> Result<AggregatorQuote, MCPError> {
+        let (sell_token, buy_token, amount) = (format!("0x{:x}", sell_token), format!("0x{:x}", buy_token), amount.to_string());
+        let request = match source {
+            SwapSource::ZeroEx => self.client
+                .get(ZEROX_PRICE_URL)
+                .query(&[("chainId", chain_id.to_string()), ("sellToken", sell_token), ("buyToken", buy_token), ("sellAmount", amount)])
+                .header("0x-api-key", api_key)
+                .header("0x-version", "v2"),
+            SwapSource::OneInch => self.client
+                .get(format!("{}/{}/quote", ONEINCH_API_URL, chain_id))
+                .query(&[("src", sell_token), ("dst", buy_token), ("amount", amount), ("includeGas", "true".to_string())])
+                .bearer_auth(api_key),
+            SwapSource::UniswapV2 | SwapSource::UniswapV3 => {
+                return Err(MCPError::ValidationError(format!("{:?} is not an aggregator", source)));
+            },
+        };
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| MCPError::NetworkError(format!("Failed to call {:?} quote API: {}", source, e)))?;
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(MCPError::ApiRateLimitExceeded(format!("{:?} quote API rate limit reached", source)));
+        }
+        if !response.status().is_success() {
+            return Err(MCPError::NetworkError(format!("{:?} quote API returned status: {}", source, response.status())));
+        }
+        let json: Value = response
+            .json()
+            .await
+            .map_err(|e| MCPError::NetworkError(format!("Failed to parse {:?} quote response: {}", source, e)))?;
+
+        let amount_field = if source == SwapSource::ZeroEx { "buyAmount" } else { "dstAmount" };
+        let amount_out = quantity(json.get(amount_field))
+            .ok_or_else(|| MCPError::SwapSimulationFailed(format!("{:?} returned no {} (no liquidity for this pair?)", source, amount_field)))?;
+        let gas_estimate = quantity(json.get("gas")).filter(|gas| *gas <= U256::from(u64::MAX)).map(|gas| gas.as_u64());
+        Ok(AggregatorQuote { amount_out, gas_estimate })
+    }
+}
@@ -5,8 +5,87 @@ use std::collections::HashMap;
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BalanceInfo {
     pub address: String,
-    pub eth_balance: Decimal,
+    /// Native currency of the network, e.g. ETH or MATIC
+    pub native_symbol: String,
+    /// Native currency balance; formerly `eth_balance`, which is still accepted when deserializing
+    #[serde(alias = "eth_balance")]
+    #[serde(with = "crate::decimal_json")]
+    pub native_balance: Decimal,
+    #[serde(default, with = "crate::decimal_json::option")]
+    pub eth_usd_value: Option<Decimal>,
     pub token_balances: HashMap<String, TokenBalance>,
+    /// Token contracts whose balance was read successfully, zero or not; a token missing
+    /// here but expected in `token_balances` failed to scan
+    #[serde(default)]
+    pub scanned_tokens: Vec<String>,
+    #[serde(default, with = "crate::decimal_json::option")]
+    pub total_usd_value: Option<Decimal>,
+}
+
+/// How a wallet's balances changed between two blocks
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BalanceChanges {
+    pub address: String,
+    pub native_symbol: String,
+    /// Blocks compared; tags such as `latest` are resolved to numbers
+    pub from_block: u64,
+    pub to_block: u64,
+    #[serde(with = "crate::decimal_json")]
+    pub native_before: Decimal,
+    #[serde(with = "crate::decimal_json")]
+    pub native_after: Decimal,
+    #[serde(with = "crate::decimal_json")]
+    pub native_change: Decimal,
+    /// Keyed by token address; tokens that could not be read at both blocks are left out
+    pub token_changes: HashMap<String, TokenBalanceChange>,
+}
+
+/// A token balance at two blocks, in whole tokens
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TokenBalanceChange {
+    pub symbol: String,
+    pub decimals: u8,
+    #[serde(with = "crate::decimal_json")]
+    pub balance_before: Decimal,
+    #[serde(with = "crate::decimal_json")]
+    pub balance_after: Decimal,
+    #[serde(with = "crate::decimal_json")]
+    pub change: Decimal,
+}
+
+/// A token holding with its current price
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PortfolioToken {
+    #[serde(flatten)]
+    pub balance: TokenBalance,
+    #[serde(default, with = "crate::decimal_json::option")]
+    pub price_usd: Option<Decimal>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PortfolioInfo {
+    pub address: String,
+    #[serde(with = "crate::decimal_json")]
+    pub eth_balance: Decimal,
+    #[serde(default, with = "crate::decimal_json::option")]
+    pub eth_price_usd: Option<Decimal>,
+    #[serde(default, with = "crate::decimal_json::option")]
+    pub eth_usd_value: Option<Decimal>,
+    /// Holdings sorted by USD value, unpriced tokens last
+    pub tokens: Vec<PortfolioToken>,
+    /// Sum of every priced holding, ETH included
+    #[serde(with = "crate::decimal_json")]
+    pub total_usd_value: Decimal,
+    /// Balance lookup failures, keyed by token address
+    pub errors: HashMap<String, String>,
+    /// Set when prices could not be fetched at all; balances are still returned
+    pub price_error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MultiBalanceInfo {
+    pub balances: HashMap<String, BalanceInfo>,
+    pub errors: HashMap<String, String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -15,40 +94,639 @@ pub struct TokenBalance {
     pub symbol: String,
     pub name: String,
     pub decimals: u8,
+    /// Raw balance in base units; saturates at Decimal's maximum for huge balances
+    #[serde(with = "crate::decimal_json")]
     pub balance: Decimal,
+    /// Exact raw balance as a decimal string
+    pub balance_raw: String,
     pub balance_formatted: String,
+    #[serde(default, with = "crate::decimal_json::option")]
+    pub usd_value: Option<Decimal>,
+    /// Where name/symbol/decimals came from; `default` means decimals were guessed
+    pub metadata_source: MetadataSource,
+}
+
+/// Origin of token metadata in a TokenBalance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataSource {
+    /// Read from the token contract
+    Onchain,
+    /// Taken from the built-in list of well-known tokens
+    KnownList,
+    /// Hard-coded fallback (18 decimals); balance_formatted may be wrong
+    Default,
+    /// Returned by `alchemy_getTokenMetadata`
+    Alchemy,
+}
+
+/// Token interface detected via ERC-165 `supportsInterface`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenStandard {
+    /// No ERC-165 NFT interface; assumed fungible
+    Erc20,
+    Erc721,
+    Erc1155,
+}
+
+impl TokenStandard {
+    /// Display name, e.g. `ERC721`
+    pub fn name(&self) -> &'static str {
+        match self {
+            TokenStandard::Erc20 => "ERC20",
+            TokenStandard::Erc721 => "ERC721",
+            TokenStandard::Erc1155 => "ERC1155",
+        }
+    }
+}
+
+/// Balance of one ERC1155 token id, from get_erc1155_balance
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Erc1155Balance {
+    pub contract_address: String,
+    pub owner: String,
+    /// Decimal token id
+    pub token_id: String,
+    /// Raw amount as a decimal string; ERC1155 defines no decimals
+    pub balance: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TokenMetadataInfo {
+    pub token_address: String,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    /// Logo URL; only available from Alchemy
+    pub logo: Option<String>,
+    pub metadata_source: MetadataSource,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PriceInfo {
     pub token_address: String,
     pub symbol: String,
+    #[serde(with = "crate::decimal_json")]
     pub price_usd: Decimal,
+    /// Where the price actually came from
+    pub source: PriceSource,
+    /// True when the requested source failed and this is a fallback estimate
+    pub degraded: bool,
+}
+
+/// Origin of a token price
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PriceSource {
+    /// Alchemy Price API
+    Alchemy,
+    /// Uniswap V3 quote against WETH, converted with the ETH price
+    Onchain,
+}
+
+/// Price source requested by `get_token_price`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PriceSourceSelection {
+    Alchemy,
+    Onchain,
+    /// Alchemy, falling back to on-chain when it fails
+    #[default]
+    Auto,
+}
+
+impl std::str::FromStr for PriceSourceSelection {
+    type Err = crate::error::MCPError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "alchemy" => Ok(PriceSourceSelection::Alchemy),
+            "onchain" => Ok(PriceSourceSelection::Onchain),
+            "auto" => Ok(PriceSourceSelection::Auto),
+            _ => Err(crate::error::MCPError::ValidationError(
+                format!("Invalid price source '{}': expected alchemy, onchain or auto", s)
+            )),
+        }
+    }
+}
+
+/// Currency `swap_tokens` reports its gas cost in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuoteCurrency {
+    /// `total_cost` in the native currency only
+    #[default]
+    Eth,
+    /// Also `total_cost_usd`, priced at the current native/USD rate
+    Usd,
+}
+
+impl std::str::FromStr for QuoteCurrency {
+    type Err = crate::error::MCPError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "eth" => Ok(QuoteCurrency::Eth),
+            "usd" => Ok(QuoteCurrency::Usd),
+            _ => Err(crate::error::MCPError::ValidationError(
+                format!("Invalid quote currency '{}': expected eth or usd", s)
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OnchainPriceInfo {
+    pub token_address: String,
+    pub symbol: String,
+    /// Uniswap V3 pool fee tier used for the quote; None for WETH itself
+    pub fee_tier: Option<u32>,
+    #[serde(with = "crate::decimal_json")]
+    pub price_weth: Decimal,
+    #[serde(default, with = "crate::decimal_json::option")]
+    pub eth_price_usd: Option<Decimal>,
+    #[serde(default, with = "crate::decimal_json::option")]
+    pub price_usd: Option<Decimal>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SwapSimulation {
     pub from_token: String,
     pub to_token: String,
+    #[serde(with = "crate::decimal_json")]
     pub amount_in: Decimal,
+    /// Expected output at the quoted price
+    #[serde(with = "crate::decimal_json")]
     pub amount_out: Decimal,
+    /// Output guaranteed after `slippage_tolerance`, i.e. the swap's amountOutMin
+    #[serde(with = "crate::decimal_json")]
+    pub minimum_received: Decimal,
     pub gas_estimate: u64,
+    pub gas_limit: u64,
+    #[serde(with = "crate::decimal_json")]
     pub gas_price: Decimal,
+    #[serde(with = "crate::decimal_json")]
     pub total_cost: Decimal,
-    pub route: Vec<String>,
+    /// `total_cost` in USD; only set when `quote_currency` is `usd`
+    #[serde(default, with = "crate::decimal_json::option", skip_serializing_if = "Option::is_none")]
+    pub total_cost_usd: Option<Decimal>,
+    /// Hops from `from_token` to `to_token`
+    pub route: Vec<RouteHop>,
+    /// Flattened token addresses along the route (`ETH` for native legs)
+    pub route_addresses: Vec<String>,
+    #[serde(with = "crate::decimal_json")]
     pub slippage_tolerance: Decimal,
+    #[serde(default, with = "crate::decimal_json::option")]
+    pub price_impact_percent: Option<Decimal>,
+    pub high_price_impact: bool,
+    /// The quote is unrealizable with swapExactTokensForTokens because a token takes a fee on transfer;
+    /// None when the simulations were inconclusive (e.g. the sender has not approved the router)
+    pub fee_on_transfer_warning: Option<bool>,
+    /// Unix timestamp encoded as the router deadline
+    pub deadline: u64,
+    /// Pool version or aggregator that produced the winning quote
+    pub source: SwapSource,
+    /// Every source that returned a quote; the best one sets `amount_out` and `source`
+    #[serde(default)]
+    pub quotes: Vec<SwapQuote>,
+}
+
+/// One source's output for the simulated swap
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapQuote {
+    pub source: SwapSource,
+    #[serde(with = "crate::decimal_json")]
+    pub amount_out: Decimal,
+    /// Uniswap V3 fee tier; None for V2 pools and aggregators
+    pub fee_tier: Option<u32>,
+    /// Gas the aggregator expects its route to use; None for Uniswap pools
+    pub gas_estimate: Option<u64>,
+    /// This quote was selected
+    pub best: bool,
+}
+
+/// One pool traversed by a swap
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteHop {
+    pub token_in: String,
+    pub token_out: String,
+    pub token_in_symbol: String,
+    pub token_out_symbol: String,
+    pub source: SwapSource,
+    /// Uniswap V3 fee tier in hundredths of a basis point; None for V2 pools
+    pub fee_tier: Option<u32>,
+}
+
+/// DEX or aggregator a swap quote came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapSource {
+    UniswapV2,
+    UniswapV3,
+    /// 0x Swap API
+    #[serde(rename = "0x")]
+    ZeroEx,
+    /// 1inch Swap API
+    #[serde(rename = "1inch")]
+    OneInch,
+}
+
+impl SwapSource {
+    /// Environment variable holding the API key an aggregator needs; None for Uniswap pools
+    pub fn api_key_env(&self) -> Option<&'static str> {
+        match self {
+            SwapSource::ZeroEx => Some("ZEROX_API_KEY"),
+            SwapSource::OneInch => Some("ONEINCH_API_KEY"),
+            SwapSource::UniswapV2 | SwapSource::UniswapV3 => None,
+        }
+    }
+}
+
+impl std::str::FromStr for SwapSource {
+    type Err = crate::error::MCPError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "uniswap_v2" => Ok(SwapSource::UniswapV2),
+            "uniswap_v3" => Ok(SwapSource::UniswapV3),
+            "0x" => Ok(SwapSource::ZeroEx),
+            "1inch" => Ok(SwapSource::OneInch),
+            _ => Err(crate::error::MCPError::ValidationError(
+                format!("Invalid quote source '{}': expected 0x or 1inch", s)
+            )),
+        }
+    }
+}
+
+/// Body POSTed to `CONFIRMATION_WEBHOOK_URL` once a broadcast transaction has a receipt
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConfirmationWebhookPayload {
+    pub tx_hash: String,
+    /// `confirmed` or `failed`
+    pub status: String,
+    pub block_number: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EnsLookup {
+    pub address: String,
+    pub ens_name: Option<String>,
+}
+
+/// Result of batch_lookup_ens
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EnsBatchLookup {
+    /// Primary ENS name per requested address; null when none is set
+    pub names: HashMap<String, Option<String>>,
+    /// Lookup failures, keyed by requested address
+    pub errors: HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContractCode {
+    pub address: String,
+    pub is_contract: bool,
+    pub code_size: usize,
+}
+
+/// EIP-1967 proxy layout found by detect_proxy
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProxyInfo {
+    pub address: String,
+    pub is_contract: bool,
+    pub is_proxy: bool,
+    /// `eip1967` (implementation slot set) or `eip1967_beacon` (beacon slot set)
+    pub proxy_type: Option<ProxyType>,
+    /// Logic contract calls are delegated to; for a beacon proxy, as reported by the beacon
+    pub implementation: Option<String>,
+    pub beacon: Option<String>,
+    /// Address in the EIP-1967 admin slot, when set
+    pub admin: Option<String>,
+}
+
+/// Whether the server can sign transactions, reported by `/health`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WalletMode {
+    Signing,
+    ReadOnly,
+}
+
+/// Where the server wallet key came from, reported by `/health`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeySource {
+    /// `PRIVATE_KEY`
+    Env,
+    /// The JSON keystore at `KEYSTORE_PATH`
+    Keystore,
+    /// No key; the placeholder is used and the server is read-only
+    None,
+}
+
+/// How a proxy locates its implementation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProxyType {
+    Eip1967,
+    Eip1967Beacon,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PendingTransaction {
+    pub hash: String,
+    pub nonce: u64,
+    pub to: Option<String>,
+    pub value: String,
+    /// `pending` (executable next) or `queued` (blocked by a nonce gap)
+    pub pool: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PendingTransactionsInfo {
+    pub address: String,
+    /// Nonce of the next transaction to be mined
+    pub latest_nonce: u64,
+    /// Nonce including transactions in the mempool
+    pub pending_nonce: u64,
+    /// pending_nonce - latest_nonce: transactions broadcast but not yet mined
+    pub pending_count: u64,
+    /// Whether the provider exposed the mempool via `txpool_contentFrom`
+    pub mempool_available: bool,
+    /// Mempool transactions from this address, sorted by nonce; empty when unavailable
+    pub transactions: Vec<PendingTransaction>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Keccak256Hash {
+    /// Number of bytes hashed after decoding `data`
+    pub input_length: usize,
+    pub hash: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FunctionSelector {
+    pub signature: String,
+    /// First 4 bytes of keccak256(signature), 0x-prefixed
+    pub selector: String,
+    pub hash: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Create2Address {
+    pub deployer: String,
+    pub salt: String,
+    pub init_code_hash: String,
+    pub address: String,
+}
+
+/// A supported network as reported by `list_networks`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NetworkInfo {
+    pub name: String,
+    pub chain_id: u64,
+    pub native_symbol: String,
+    /// Whether this server instance is configured for the network
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AddressValidation {
+    pub address: String,
+    pub valid: bool,
+    /// EIP-55 checksummed form when the address is valid
+    pub checksummed: Option<String>,
+    pub is_ens: bool,
+    /// Why the input was rejected, when it is not a valid address
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StorageSlot {
+    pub address: String,
+    /// Slot as a 32-byte hex word
+    pub slot: String,
+    /// Raw 32-byte value as hex
+    pub value: String,
+}
+
+/// Fee options for outgoing transactions; fee values are in gwei
+#[derive(Debug, Default, Clone)]
+pub struct TransactionFeeOptions {
+    pub max_fee_per_gas: Option<Decimal>,
+    pub max_priority_fee_per_gas: Option<Decimal>,
+    pub legacy: bool,
+    /// Gas limit padding over the raw estimate; None uses `GAS_LIMIT_MULTIPLIER`
+    pub gas_multiplier: Option<Decimal>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransactionInfo {
+    pub tx_hash: String,
+    pub from: String,
+    pub to: String,
+    #[serde(with = "crate::decimal_json")]
+    pub value: Decimal,
+    pub transaction_type: String,
+    pub gas_estimate: u64,
+    pub gas_limit: u64,
+    #[serde(default, with = "crate::decimal_json::option")]
+    pub max_fee_per_gas_gwei: Option<Decimal>,
+    #[serde(default, with = "crate::decimal_json::option")]
+    pub max_priority_fee_per_gas_gwei: Option<Decimal>,
+    #[serde(default, with = "crate::decimal_json::option")]
+    pub gas_price_gwei: Option<Decimal>,
+    pub status: String,
+    pub block_number: Option<u64>,
+    pub gas_used: Option<u64>,
+}
+
+/// Result of `replace_transaction`: a higher-fee transaction at an already-used nonce
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReplacementTransactionInfo {
+    pub tx_hash: String,
+    /// Hash of the transaction being replaced, when it was found in the mempool
+    pub replaced_tx_hash: Option<String>,
+    /// "speed_up" or "cancel"
+    pub mode: String,
+    pub nonce: u64,
+    pub from: String,
+    pub to: String,
+    pub value_wei: String,
+    pub gas_limit: u64,
+    #[serde(with = "crate::decimal_json")]
+    pub max_fee_per_gas_gwei: Decimal,
+    #[serde(with = "crate::decimal_json")]
+    pub max_priority_fee_per_gas_gwei: Decimal,
+    pub status: String,
+    pub block_number: Option<u64>,
+    pub gas_used: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TokenTransferInfo {
+    pub tx_hash: String,
+    pub from: String,
+    pub to: String,
+    pub token_address: String,
+    pub symbol: String,
+    #[serde(with = "crate::decimal_json")]
+    pub amount: Decimal,
+    pub amount_raw: String,
+    pub status: String,
+    pub block_number: Option<u64>,
+    pub gas_used: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnsignedTransaction {
+    pub transaction: serde_json::Value,
+    pub rlp: String,
+    pub from: String,
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub transaction_type: String,
+    pub gas_estimate: u64,
+    pub gas_limit: u64,
+    #[serde(default, with = "crate::decimal_json::option")]
+    pub max_fee_per_gas_gwei: Option<Decimal>,
+    #[serde(default, with = "crate::decimal_json::option")]
+    pub max_priority_fee_per_gas_gwei: Option<Decimal>,
+    #[serde(default, with = "crate::decimal_json::option")]
+    pub gas_price_gwei: Option<Decimal>,
+}
+
+/// Current gas price with a spike warning
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GasPriceInfo {
+    /// `eth_gasPrice`, i.e. base fee plus a typical priority fee
+    #[serde(with = "crate::decimal_json")]
+    pub gas_price_gwei: Decimal,
+    /// Latest block's base fee; None on chains without EIP-1559
+    #[serde(default, with = "crate::decimal_json::option")]
+    pub base_fee_gwei: Option<Decimal>,
+    /// `GAS_WARN_GWEI`
+    #[serde(with = "crate::decimal_json")]
+    pub warn_threshold_gwei: Decimal,
+    /// `gas_price_gwei` is above `warn_threshold_gwei`
+    pub is_high: bool,
+}
+
+/// Outcome of wait_for_gas
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GasWaitInfo {
+    /// The base fee reached `target_gwei` before the timeout
+    pub reached: bool,
+    #[serde(with = "crate::decimal_json")]
+    pub target_gwei: Decimal,
+    /// Base fee at the last check (gas price on chains without EIP-1559)
+    #[serde(with = "crate::decimal_json")]
+    pub base_fee_gwei: Decimal,
+    pub waited_secs: u64,
+    pub checks: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FeeHistoryInfo {
+    pub oldest_block: u64,
+    pub reward_percentiles: Vec<f64>,
+    pub blocks: Vec<FeeHistoryBlock>,
+    #[serde(default, with = "crate::decimal_json::option")]
+    pub next_base_fee_per_gas_gwei: Option<Decimal>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub address: String,
+    pub block_number: Option<u64>,
+    pub transaction_hash: Option<String>,
+    pub log_index: Option<u64>,
+    pub topics: Vec<String>,
+    pub data: String,
+    /// Decoded ERC20 `Transfer(address,address,uint256)`; absent for other events
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfer: Option<DecodedTransfer>,
+}
+
+/// ERC20 Transfer event fields, with `value` scaled by the token's decimals
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DecodedTransfer {
+    pub from: String,
+    pub to: String,
+    pub value: String,
+    pub value_raw: String,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+/// A block sub-range whose `eth_getLogs` call failed
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FailedLogChunk {
+    pub from_block: u64,
+    pub to_block: u64,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LogsInfo {
+    pub from_block: u64,
+    pub to_block: u64,
+    pub chunk_size: u64,
+    pub chunk_count: usize,
+    /// Logs from every successful chunk, in block order
+    pub logs: Vec<LogEntry>,
+    /// Set when some chunks failed and `logs` is incomplete
+    pub partial: bool,
+    pub failed_chunks: Vec<FailedLogChunk>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FeeHistoryBlock {
+    pub block_number: u64,
+    #[serde(with = "crate::decimal_json")]
+    pub base_fee_per_gas_gwei: Decimal,
+    pub gas_used_ratio: f64,
+    #[serde(with = "crate::decimal_json::vec")]
+    pub priority_fees_gwei: Vec<Decimal>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransferSimulation {
+    pub token_address: String,
+    pub symbol: String,
+    pub from: String,
+    pub to: String,
+    #[serde(with = "crate::decimal_json")]
+    pub amount: Decimal,
+    pub amount_raw: String,
+    pub gas_estimate: u64,
+    pub gas_limit: u64,
+    #[serde(with = "crate::decimal_json")]
+    pub gas_price: Decimal,
+    #[serde(with = "crate::decimal_json")]
+    pub total_cost: Decimal,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MCPRequest {
     pub jsonrpc: String,
+    #[serde(default)]
     pub id: serde_json::Value,
     pub method: String,
+    #[serde(default)]
     pub params: serde_json::Value,
     #[serde(skip)]
     pub request_context: Option<crate::logging::RequestContext>,
 }
 
+impl MCPRequest {
+    /// Requests without an id are notifications and must not receive a response
+    pub fn is_notification(&self) -> bool {
+        self.id.is_null()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MCPResponse {
     pub jsonrpc: String,
@@ -0,0 +1,135 @@
+use crate::error::MCPError;
+use async_trait::async_trait;
+use ethers::providers::{JsonRpcClient, Ws, WsClientError};
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::Debug;
+use std::time::Duration;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{info, warn};
+use url::Url;
+
+/// Default number of reconnect attempts after a WebSocket provider drops
+pub const DEFAULT_WS_MAX_RECONNECTS: u32 = 5;
+
+/// Default delay before the first reconnect attempt; it doubles with each further attempt
+pub const DEFAULT_WS_RECONNECT_DELAY_MS: u64 = 500;
+
+/// WebSocket JSON-RPC transport that rebuilds its connection when the provider drops it.
+///
+/// A request failing because the socket closed triggers up to `max_reconnects` attempts
+/// to open a new connection, `base_delay` apart and doubling each time; the request is
+/// then retried once on the new connection. When every attempt fails the error is returned
+/// and the next request starts over.
+#[derive(Debug)]
+pub struct ReconnectingWs {
+    url: Url,
+    /// Current connection and how many times it has been rebuilt
+    connection: RwLock<(u64, Ws)>,
+    /// Held while reconnecting so concurrent failures rebuild the connection once
+    reconnecting: Mutex<()>,
+    max_reconnects: u32,
+    base_delay: Duration,
+}
+
+impl ReconnectingWs {
+    pub async fn connect(url: &str, max_reconnects: u32, base_delay: Duration) -> Result<Self, MCPError> {
+        let parsed = Url::parse(url)
+            .map_err(|e| MCPError::ConfigurationError(format!("Invalid RPC URL: {}", e)))?;
+        let ws = Self::open(&parsed).await
+            .map_err(|e| MCPError::NetworkError(format!("Failed to connect to {}: {}", parsed, e)))?;
+
+        Ok(Self {
+            url: parsed,
+            connection: RwLock::new((0, ws)),
+            reconnecting: Mutex::new(()),
+            max_reconnects,
+            base_delay,
+        })
+    }
+
+    /// Endpoint this transport connects to
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Number of times the connection has been rebuilt
+    pub async fn reconnect_count(&self) -> u64 {
+        self.connection.read().await.0
+    }
+
+    /// Open a connection without ethers' built-in reconnects, which retry immediately
+    /// and without logging; dropped connections are handled by `reconnect` instead
+    async fn open(url: &Url) -> Result<Ws, WsClientError> {
+        Ws::connect_with_reconnects(url.as_str(), 0).await
+    }
+
+    /// Replace connection `generation` with a new one. Returns at once if another request
+    /// already replaced it.
+    async fn reconnect(&self, generation: u64) -> Result<(), WsClientError> {
+        let _guard = self.reconnecting.lock().await;
+        if self.connection.read().await.0 != generation {
+            return Ok(());
+        }
+
+        let mut last_error = WsClientError::UnexpectedClose;
+        for attempt in 1..=self.max_reconnects {
+            let delay = self.base_delay.saturating_mul(2u32.saturating_pow(attempt - 1));
+            warn!(
+                rpc_host = ?self.url.host_str(),
+                attempt = attempt,
+                max_reconnects = self.max_reconnects,
+                delay_ms = delay.as_millis() as u64,
+                "Reconnecting WebSocket RPC provider"
+            );
+            tokio::time::sleep(delay).await;
+
+            match Self::open(&self.url).await {
+                Ok(ws) => {
+                    *self.connection.write().await = (generation + 1, ws);
+                    info!(rpc_host = ?self.url.host_str(), attempt = attempt, "WebSocket RPC provider reconnected");
+                    return Ok(());
+                },
+                Err(e) => {
+                    warn!(rpc_host = ?self.url.host_str(), attempt = attempt, error = %e, "WebSocket reconnect attempt failed");
+                    last_error = e;
+                },
+            }
+        }
+
+        warn!(rpc_host = ?self.url.host_str(), max_reconnects = self.max_reconnects, "WebSocket reconnect attempts exhausted");
+        Err(last_error)
+    }
+}
+
+/// Whether the error means the socket is gone, rather than a response the node sent
+fn is_disconnect(error: &WsClientError) -> bool {
+    matches!(
+        error,
+        WsClientError::UnexpectedClose
+            | WsClientError::DeadChannel
+            | WsClientError::TooManyReconnects
+            | WsClientError::InternalError(_)
+    )
+}
+
+#[async_trait]
+impl JsonRpcClient for ReconnectingWs {
+    type Error = WsClientError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        let (generation, ws) = self.connection.read().await.clone();
+        match ws.request(method, &params).await {
+            Err(e) if is_disconnect(&e) => {
+                warn!(rpc_host = ?self.url.host_str(), method, error = %e, "WebSocket RPC connection dropped");
+                self.reconnect(generation).await?;
+                let ws = self.connection.read().await.1.clone();
+                ws.request(method, &params).await
+            },
+            result => result,
+        }
+    }
+}
@@ -6,10 +6,14 @@ echo "===================================="
 
 # Set environment variables
 export PRIVATE_KEY="0x0000000000000000000000000000000000000000000000000000000000000001"
+if [ -z "$RPC_URL" ] && [ -z "$RPC_URLS" ]; then
+    echo "❌ Set RPC_URL to your Ethereum RPC endpoint"
+    exit 1
+fi
 
 echo "🔧 Environment variables set:"
 echo "   PRIVATE_KEY: ${PRIVATE_KEY:0:10}..."
-echo "   ETHEREUM_RPC_URL: Hardcoded in code (Alchemy)"
+echo "   RPC_URL: ${RPC_URL:-$RPC_URLS}"
 echo ""
 
 echo "🏗️  Compiling and starting server..."